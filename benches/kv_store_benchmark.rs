@@ -1,14 +1,22 @@
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 use num_cpus;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use redis::AsyncCommands;
+use rustx::kv::serializer::register_serde_serializers;
 use rustx::kv::store::{
-    DashMapStore, DashMapStoreConfig, RwLockHashMapStore, RwLockHashMapStoreConfig, SetOptions,
-    SyncStore, UnsafeHashMapStore, UnsafeHashMapStoreConfig,
+    AsyncStore, DashMapStore, DashMapStoreConfig, RedisStore, RedisStoreConfig,
+    RwLockHashMapStore, RwLockHashMapStoreConfig, SetOptions, SyncStore, UnsafeHashMapStore,
+    UnsafeHashMapStoreConfig,
 };
 use std::sync::Arc;
+use std::time::Duration;
 
 const NUM_ITEMS: usize = 100_000;
 
+// Redis 基准测试使用本地 Redis，不可用时跳过
+const REDIS_ENDPOINT: &str = "localhost:6379";
+const REDIS_OPS: usize = 1000;
+
 // ========== 辅助函数 ==========
 
 fn generate_key(i: usize) -> String {
@@ -490,6 +498,71 @@ fn benchmark_batch_read(c: &mut Criterion) {
     group.finish();
 }
 
+// ========== 8. Redis 连接复用 ==========
+
+fn benchmark_redis_connection(c: &mut Criterion) {
+    let client = redis::Client::open(format!("redis://{}", REDIS_ENDPOINT)).unwrap();
+    if client
+        .get_connection_with_timeout(Duration::from_secs(1))
+        .is_err()
+    {
+        eprintln!("skip redis_connection: redis is not available on {}", REDIS_ENDPOINT);
+        return;
+    }
+
+    register_serde_serializers::<String>().unwrap();
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let store = RedisStore::<String, String>::new(RedisStoreConfig {
+        endpoint: Some(REDIS_ENDPOINT.to_string()),
+        ..Default::default()
+    })
+    .unwrap();
+    let key = "bench_redis_key".to_string();
+    runtime
+        .block_on(store.set(&key, &generate_value(0), &SetOptions::new()))
+        .unwrap();
+
+    let mut group = c.benchmark_group("redis_connection");
+    group.sample_size(10);
+
+    // 改进前：每次操作建立新连接
+    group.bench_function(BenchmarkId::new("per_call_connect", REDIS_OPS), |b| {
+        b.iter(|| {
+            runtime.block_on(async {
+                for _ in 0..REDIS_OPS {
+                    let mut con = client.get_multiplexed_async_connection().await.unwrap();
+                    let value: Option<Vec<u8>> = con.get("\"bench_redis_key\"").await.unwrap();
+                    black_box(value);
+                }
+            })
+        })
+    });
+
+    // 改进后：RedisStore 复用 ConnectionManager
+    group.bench_function(BenchmarkId::new("RedisStore", REDIS_OPS), |b| {
+        b.iter(|| {
+            runtime.block_on(async {
+                for _ in 0..REDIS_OPS {
+                    black_box(store.get(&key).await.unwrap());
+                }
+            })
+        })
+    });
+
+    // 并发请求共享同一个连接
+    group.bench_function(BenchmarkId::new("RedisStore_concurrent", REDIS_OPS), |b| {
+        b.iter(|| {
+            runtime.block_on(async {
+                let gets = (0..REDIS_OPS).map(|_| store.get(&key));
+                black_box(futures::future::join_all(gets).await);
+            })
+        })
+    });
+
+    runtime.block_on(store.del(&key)).unwrap();
+    group.finish();
+}
+
 // ========== 主函数 ==========
 
 criterion_group!(
@@ -500,6 +573,7 @@ criterion_group!(
     benchmark_concurrent_write,
     benchmark_mixed_read_write,
     benchmark_batch_write,
-    benchmark_batch_read
+    benchmark_batch_read,
+    benchmark_redis_connection
 );
 criterion_main!(benches);
@@ -123,6 +123,7 @@ fn main() -> anyhow::Result<()> {
     let source = FileSource::new(FileSourceConfig {
         base_path: "examples/configs/cfg".to_string(),
         logger: None,
+        interpolate: false,
     });
 
     // 3. 从配置创建并自动监听 trait object
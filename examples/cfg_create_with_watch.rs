@@ -48,6 +48,7 @@ fn main() -> anyhow::Result<()> {
     let source = FileSource::new(FileSourceConfig {
         base_path: "examples/configs/cfg".to_string(),
         logger: None,
+        interpolate: false,
     });
 
     // 2. 一次性创建对象（无热更新）
@@ -60,10 +61,8 @@ fn main() -> anyhow::Result<()> {
     let service = source.create_with_watch::<DatabaseService, DatabaseConfig>("database.json5", None)?;
 
     // 读取当前配置
-    {
-        let guard = service.read().unwrap();
-        println!("当前连接信息: {}", guard.connection_info());
-    }
+    println!("当前连接信息: {}", service.current().connection_info());
+    println!("配置版本: {}", service.version());
 
     println!("\n提示: 修改 examples/configs/cfg/database.json5 文件查看热更新效果");
     println!("按 Ctrl+C 退出");
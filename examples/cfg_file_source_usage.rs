@@ -28,6 +28,7 @@ fn main() -> anyhow::Result<()> {
     let source = FileSource::new(FileSourceConfig {
         base_path: "examples/configs/cfg".to_string(),
         logger: None,
+        interpolate: false,
     });
 
     // 2. 使用 load 加载配置并反序列化为结构体
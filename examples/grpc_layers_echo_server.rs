@@ -96,6 +96,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let source = FileSource::new(FileSourceConfig {
         base_path: "examples/configs/grpc_layers_echo_server".to_string(),
         logger: None,
+        interpolate: false,
     });
 
     // 从文件加载配置
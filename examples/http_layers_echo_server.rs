@@ -97,6 +97,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let source = FileSource::new(FileSourceConfig {
         base_path: "examples/configs/http_layers_echo_server".to_string(),
         logger: None,
+        interpolate: false,
     });
 
     // 从文件加载配置
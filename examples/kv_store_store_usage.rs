@@ -21,6 +21,7 @@ async fn main() -> Result<()> {
     let source = FileSource::new(FileSourceConfig {
         base_path: "examples/configs/kv_store".to_string(),
         logger: None,
+        interpolate: false,
     });
 
     let type_options: TypeOptions = source.load("redis_store.json5", None)?.into_type()?;
@@ -12,6 +12,7 @@ use prometheus_client::{
         histogram::{exponential_buckets, Histogram},
     },
 };
+use schemars::JsonSchema;
 use serde::Deserialize;
 use smart_default::SmartDefault;
 use std::collections::HashMap;
@@ -139,7 +140,7 @@ pub fn extract_fixed_labels(
 }
 
 /// AOP 创建配置（用于创建新的 AOP 实例）
-#[derive(Debug, Clone, Deserialize, SmartDefault, PartialEq)]
+#[derive(Debug, Clone, Deserialize, SmartDefault, PartialEq, JsonSchema)]
 #[serde(default)]
 pub struct AopCreateConfig {
     /// Retry 配置
@@ -157,7 +158,7 @@ pub struct AopCreateConfig {
 
 /// Retry 配置
 #[serde_as]
-#[derive(Debug, Clone, Deserialize, SmartDefault, Validate, PartialEq)]
+#[derive(Debug, Clone, Deserialize, SmartDefault, Validate, PartialEq, JsonSchema)]
 #[serde(default)]
 pub struct RetryConfig {
     /// 最大重试次数
@@ -200,7 +201,7 @@ pub struct RetryConfig {
 }
 
 /// Logging 配置
-#[derive(Debug, Clone, Deserialize, SmartDefault, Validate, PartialEq)]
+#[derive(Debug, Clone, Deserialize, SmartDefault, Validate, PartialEq, JsonSchema)]
 #[serde(default)]
 pub struct LoggingConfig {
     /// Logger 配置
@@ -219,7 +220,7 @@ pub struct LoggingConfig {
 }
 
 /// Tracing 配置
-#[derive(Debug, Clone, Deserialize, SmartDefault, Validate, PartialEq)]
+#[derive(Debug, Clone, Deserialize, SmartDefault, Validate, PartialEq, JsonSchema)]
 #[serde(default)]
 pub struct TracingConfig {
     /// Span 名称字段
@@ -234,7 +235,7 @@ pub struct TracingConfig {
 }
 
 /// Metrics 配置（运行时，用于记录指标）
-#[derive(Debug, Clone, Deserialize, SmartDefault, Validate, PartialEq)]
+#[derive(Debug, Clone, Deserialize, SmartDefault, Validate, PartialEq, JsonSchema)]
 #[serde(default)]
 pub struct MetricsConfig {
     /// Metric 名称前缀
@@ -259,7 +260,7 @@ pub struct MetricsConfig {
 /// 支持两种模式：
 /// - Reference: 引用已存在的 aop 实例（通过 $instance 字段）
 /// - Create: 创建新的 aop 实例
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum AopConfig {
     /// 引用一个已存在的 aop 实例
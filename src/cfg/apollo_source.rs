@@ -3,6 +3,7 @@
 //! 支持从 Apollo 配置中心加载配置，支持长轮询监听配置变化
 
 use anyhow::{anyhow, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
 use std::collections::HashMap;
@@ -15,7 +16,7 @@ use crate::{impl_box_from, impl_from};
 use crate::log::{Logger, LoggerConfig};
 
 /// Apollo 配置中心源的配置
-#[derive(Debug, Clone, Deserialize, Serialize, SmartDefault)]
+#[derive(Debug, Clone, Deserialize, Serialize, SmartDefault, JsonSchema)]
 #[serde(default)]
 pub struct ApolloSourceConfig {
     /// Apollo 服务器地址，如 "http://localhost:8080"
@@ -2,18 +2,30 @@
 //!
 //! 提供基于配置创建对象的能力，支持自动热更新
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use arc_swap::ArcSwap;
+use garde::Validate;
 use serde::de::DeserializeOwned;
 use std::sync::{Arc, RwLock};
 
-use super::source::{ConfigChange, ConfigSource};
+use super::group::{watch_group, ConfigGroup, ConfigGroupHandle};
+use super::handle::ConfigHandle;
+use super::reloader::ConfigReloader;
+use super::source::{ConfigChange, ConfigSource, ConfigValue};
 use super::type_options::TypeOptions;
+use super::validation::parse_and_validate;
+
+/// 配置重载失败回调
+///
+/// 参数为配置键和错误信息，校验失败时可通过 `downcast_ref::<ValidationError>()` 获取字段错误
+pub type ReloadErrorHandler = Arc<dyn Fn(&str, &anyhow::Error) + Send + Sync + 'static>;
 
 /// 配置源扩展功能
 ///
 /// 此 trait 为所有配置源提供创建对象的能力，包括：
 /// - 一次性创建对象
 /// - 创建对象并自动监听配置变化
+/// - 创建一组对象并以事务方式监听配置变化
 /// - 创建 trait object 并自动监听配置变化
 ///
 /// # 示例
@@ -88,55 +100,126 @@ pub trait Configurable: ConfigSource {
     /// - `format`: 配置格式，支持 "json", "json5", "yaml", "toml"，None 表示自动推断
     ///
     /// # 返回
-    /// - 成功返回包装在 Arc<RwLock<T>> 中的对象实例
+    /// - 成功返回 `ConfigHandle<T>`，通过 `current()` 读取对象
     ///
     /// # 线程安全性
-    /// - 返回的 Arc<RwLock<T>> 可以在线程间安全共享
-    /// - 配置更新时会自动获取写锁并调用 reload_config
+    /// - 返回的 `ConfigHandle<T>` 可以克隆并在线程间安全共享
+    /// - 配置更新时会自动获取写锁并调用 reload_config，成功后递增 `version()` 并通知 `subscribe()` 的订阅者
     ///
     /// # 错误处理
-    /// - 如果 reload_config 失败，会记录错误并保留旧配置
+    /// - 如果解析或 reload_config 失败，会保留旧配置，错误可通过 `last_reload_error()` 查询
+    /// - 重载结果输出到默认 logger（`crate::log::get_default()`）
     fn create_with_watch<T, Config>(
         &self,
         key: &str,
         format: Option<&str>,
-    ) -> Result<Arc<RwLock<T>>>
+    ) -> Result<ConfigHandle<T>>
     where
-        T: From<Config> + crate::cfg::ConfigReloader<Config> + Send + Sync + 'static,
+        T: From<Config> + ConfigReloader<Config> + Send + Sync + 'static,
         Config: DeserializeOwned + Clone + Send + Sync + 'static,
     {
         // 加载初始配置
         let config_value = self.load(key, format)?;
         let config: Config = config_value.into_type()?;
-        let instance = T::from(config);
-        let instance = Arc::new(RwLock::new(instance));
-        let instance_clone = instance.clone();
+        let handle = ConfigHandle::new(T::from(config));
+
+        watch_and_reload(
+            self,
+            key,
+            format,
+            handle.clone(),
+            |_, value| value.into_type::<Config>(),
+            Arc::new(|key, e| {
+                let _ = crate::log::get_default().warn_sync(format!(
+                    "[ERROR] config reload_failed - key={} error={}",
+                    key, e
+                ));
+            }),
+        )?;
 
-        // 监听配置变化
-        let key_owned = key.to_string();
-        self.watch(key, format, Box::new(move |change| {
-            if let ConfigChange::Updated(config_value) = change {
-                match config_value.into_type::<Config>() {
-                    Ok(new_config) => {
-                        if let Ok(mut guard) = instance_clone.write() {
-                            if let Err(e) = guard.reload_config(new_config) {
-                                eprintln!("重载配置失败 [{}]: {}", key_owned, e);
-                                // 保留旧配置
-                            } else {
-                                println!("配置已更新 [{}]", key_owned);
-                            }
-                        } else {
-                            eprintln!("获取写锁失败 [{}]", key_owned);
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("解析配置失败 [{}]: {}", key_owned, e);
-                    }
-                }
-            }
-        }))?;
+        Ok(handle)
+    }
+
+    /// 创建对象并校验配置（一次性）
+    ///
+    /// 与 `create` 相同，但在创建对象前使用 `garde::Validate` 校验配置
+    ///
+    /// # 返回
+    /// - 校验失败返回 `ValidationError`，包含每个字段的路径、规则和原始值
+    fn create_validated<T, Config>(&self, key: &str, format: Option<&str>) -> Result<T>
+    where
+        T: From<Config> + Send + Sync + 'static,
+        Config: DeserializeOwned + Validate + Clone + Send + Sync + 'static,
+        Config::Context: Default,
+    {
+        let config_value = self.load(key, format)?;
+        let config: Config = parse_and_validate(key, config_value)?;
+        Ok(T::from(config))
+    }
+
+    /// 创建对象、校验配置并自动监听配置变化
+    ///
+    /// 与 `create_with_watch` 相同，但初始加载和每次重载时都会使用 `garde::Validate` 校验配置
+    ///
+    /// # 参数
+    /// - `key`: 配置键
+    /// - `format`: 配置格式，None 表示自动推断
+    /// - `on_error`: 重载失败回调，解析失败、校验失败、`reload_config` 失败以及配置源上报的错误都会触发
+    ///
+    /// # 错误处理
+    /// - 初始配置校验失败时直接返回 `ValidationError`
+    /// - 重载失败时保留旧配置并调用 `on_error`
+    fn create_with_watch_validated<T, Config>(
+        &self,
+        key: &str,
+        format: Option<&str>,
+        on_error: ReloadErrorHandler,
+    ) -> Result<ConfigHandle<T>>
+    where
+        T: From<Config> + ConfigReloader<Config> + Send + Sync + 'static,
+        Config: DeserializeOwned + Validate + Clone + Send + Sync + 'static,
+        Config::Context: Default,
+    {
+        let config_value = self.load(key, format)?;
+        let config: Config = parse_and_validate(key, config_value)?;
+        let handle = ConfigHandle::new(T::from(config));
+
+        watch_and_reload(
+            self,
+            key,
+            format,
+            handle.clone(),
+            parse_and_validate::<Config>,
+            on_error,
+        )?;
 
-        Ok(instance)
+        Ok(handle)
+    }
+
+    /// 创建一组对象并以事务方式监听配置变化
+    ///
+    /// 组内所有配置键作为一个整体加载和重载：
+    /// - 时间窗口（默认 100ms）内多个键的变化合并为一次重载
+    /// - 所有变化的对象 reload_config 全部成功才生效，任意一个失败时逆序回滚已应用的对象
+    /// - 任意配置解析失败时不调用任何 reload_config
+    ///
+    /// # 参数
+    /// - `group`: 配置组定义，通过 `ConfigGroup::add` 添加配置键和对象类型
+    ///
+    /// # 返回
+    /// - 成功返回 `ConfigGroupHandle`，通过 `get::<T>(key)` 获取各个对象的 `ConfigHandle<T>`
+    ///
+    /// # 示例
+    /// ```ignore
+    /// let group = source.create_group_with_watch(
+    ///     ConfigGroup::new()
+    ///         .add::<DatabaseService, DatabaseConfig>("database.json", None)
+    ///         .add::<CacheService, CacheConfig>("cache.json", None),
+    /// )?;
+    /// let database = group.get::<DatabaseService>("database.json")?;
+    /// ```
+    fn create_group_with_watch(&self, group: ConfigGroup) -> Result<ConfigGroupHandle> {
+        watch_group(self, group)
     }
 
     /// 创建 trait object 并自动监听配置变化
@@ -186,35 +269,144 @@ pub trait Configurable: ConfigSource {
         let inner_clone = inner.clone();
 
         // 监听配置变化
-        let key_owned = key.to_string();
-        self.watch(key, format, Box::new(move |change| {
-            if let ConfigChange::Updated(config_value) = change {
-                match config_value.into_type::<TypeOptions>() {
-                    Ok(new_type_options) => {
-                        // 通过 registry 重新创建 trait object
-                        match crate::cfg::create_trait_from_type_options::<Trait>(&new_type_options) {
-                            Ok(new_trait_obj) => {
-                                if let Ok(mut guard) = inner_clone.write() {
-                                    *guard = new_trait_obj;
-                                    println!("配置已更新 [{}]", key_owned);
-                                } else {
-                                    eprintln!("获取写锁失败 [{}]", key_owned);
-                                }
-                            }
-                            Err(e) => {
-                                eprintln!("重载配置失败 [{}]: {}", key_owned, e);
-                            }
+        watch_and_rebuild::<_, Trait, _>(self, key, format, move |key, new_trait_obj| {
+            if let Ok(mut guard) = inner_clone.write() {
+                *guard = new_trait_obj;
+                let _ = crate::log::get_default()
+                    .info_sync(format!("[RELOAD] trait updated - key={}", key));
+            } else {
+                let _ = crate::log::get_default()
+                    .warn_sync(format!("[ERROR] trait lock_failed - key={}", key));
+            }
+        })?;
+
+        Ok(inner)
+    }
+
+    /// 创建 trait object 并自动监听配置变化，返回无锁快照
+    ///
+    /// 与 `create_trait_with_watch` 相同，但使用 `ArcSwap` 替代 `RwLock`：
+    /// 读取时通过 `load()` 获取当前 trait object 的快照，不需要加锁；
+    /// 配置变化时创建新的 trait object 并原子替换，正在使用旧快照的读者不受影响
+    ///
+    /// # 类型参数
+    /// - `Trait`: 目标 trait 类型
+    /// - `Config`: 配置类型
+    ///
+    /// # 参数
+    /// - `key`: 配置键，配置内容需包含 type 和 options 字段
+    /// - `format`: 配置格式，支持 "json", "json5", "yaml", "toml"，None 表示自动推断
+    ///
+    /// # 返回
+    /// - 成功返回包装在 Arc<ArcSwap<Box<Trait>>> 中的 trait object
+    ///
+    /// # 示例
+    /// ```ignore
+    /// let cache = source.create_trait_with_watch_swap::<dyn Cache, RedisConfig>("cache.json", None)?;
+    /// let value = cache.load().get("key");
+    /// ```
+    fn create_trait_with_watch_swap<Trait, Config>(
+        &self,
+        key: &str,
+        format: Option<&str>,
+    ) -> Result<Arc<ArcSwap<Box<Trait>>>>
+    where
+        Trait: ?Sized + Send + Sync + 'static,
+        Config: DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        // 加载 TypeOptions
+        let config_value = self.load(key, format)?;
+        let type_options: TypeOptions = config_value.into_type()?;
+
+        // 通过 registry 创建 trait object
+        let trait_obj = crate::cfg::create_trait_from_type_options::<Trait>(&type_options)?;
+
+        let inner = Arc::new(ArcSwap::from_pointee(trait_obj));
+        let inner_clone = inner.clone();
+
+        // 监听配置变化，新对象创建成功后原子替换
+        watch_and_rebuild::<_, Trait, _>(self, key, format, move |key, new_trait_obj| {
+            inner_clone.store(Arc::new(new_trait_obj));
+            let _ = crate::log::get_default()
+                .info_sync(format!("[RELOAD] trait updated - key={}", key));
+        })?;
+
+        Ok(inner)
+    }
+}
+
+/// 监听配置变化，根据新的 TypeOptions 重新创建 trait object 并交给 `replace` 替换
+///
+/// 解析或创建失败时保留旧对象
+fn watch_and_rebuild<S, Trait, F>(source: &S, key: &str, format: Option<&str>, replace: F) -> Result<()>
+where
+    S: ConfigSource + ?Sized,
+    Trait: ?Sized + Send + Sync + 'static,
+    F: Fn(&str, Box<Trait>) + Send + Sync + 'static,
+{
+    let key_owned = key.to_string();
+    source.watch(key, format, Box::new(move |change| {
+        if let ConfigChange::Updated(config_value) = change {
+            match config_value.into_type::<TypeOptions>() {
+                Ok(new_type_options) => {
+                    // 通过 registry 重新创建 trait object
+                    match crate::cfg::create_trait_from_type_options::<Trait>(&new_type_options) {
+                        Ok(new_trait_obj) => replace(&key_owned, new_trait_obj),
+                        Err(e) => {
+                            let _ = crate::log::get_default().warn_sync(format!(
+                                "[ERROR] trait create_failed - key={} error={}",
+                                key_owned, e
+                            ));
                         }
                     }
-                    Err(e) => {
-                        eprintln!("解析配置失败 [{}]: {}", key_owned, e);
-                    }
+                }
+                Err(e) => {
+                    let _ = crate::log::get_default().warn_sync(format!(
+                        "[ERROR] trait parse_failed - key={} error={}",
+                        key_owned, e
+                    ));
                 }
             }
-        }))?;
+        }
+    }))
+}
 
-        Ok(inner)
-    }
+/// 监听配置变化，解析新配置后调用 reload_config 更新对象
+///
+/// 任意一步失败时保留旧配置并调用 `on_error`
+fn watch_and_reload<S, T, Config, P>(
+    source: &S,
+    key: &str,
+    format: Option<&str>,
+    handle: ConfigHandle<T>,
+    parse: P,
+    on_error: ReloadErrorHandler,
+) -> Result<()>
+where
+    S: ConfigSource + ?Sized,
+    T: ConfigReloader<Config> + Send + Sync + 'static,
+    Config: Send + Sync + 'static,
+    P: Fn(&str, ConfigValue) -> Result<Config> + Send + Sync + 'static,
+{
+    let key_owned = key.to_string();
+    source.watch(key, format, Box::new(move |change| {
+        let result = match change {
+            ConfigChange::Updated(config_value) => parse(&key_owned, config_value)
+                .and_then(|new_config| handle.apply(|instance| instance.reload_config(new_config))),
+            ConfigChange::Error(msg) => Err(anyhow!("监听配置失败: {}", msg)),
+            // 配置删除时保留当前对象
+            ConfigChange::Deleted => return,
+        };
+
+        match result {
+            // 保留旧配置，记录错误供 last_reload_error 查询
+            Err(e) => on_error(&key_owned, &handle.record_error(e)),
+            Ok(()) => {
+                let _ = crate::log::get_default()
+                    .info_sync(format!("[RELOAD] config updated - key={}", key_owned));
+            }
+        }
+    }))
 }
 
 // 为所有实现了 ConfigSource 的类型自动实现 Configurable
@@ -223,7 +415,7 @@ impl<S: ConfigSource + ?Sized> Configurable for S {}
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cfg::{ConfigReloader, FileSource, FileSourceConfig, register_trait};
+    use crate::cfg::{ConfigReloader, FileSource, FileSourceConfig, ValidationError, register_trait};
     use anyhow::anyhow;
     use serial_test::serial;
     use serde::Deserialize;
@@ -261,6 +453,7 @@ mod tests {
         let source = Box::new(FileSource::new(FileSourceConfig {
             base_path: "config/test".to_string(),
             logger: None,
+            interpolate: false,
         })) as Box<dyn ConfigSource>;
 
         // 虽然是 dyn ConfigSource，但可以调用 create 方法
@@ -275,6 +468,7 @@ mod tests {
         let source = FileSource::new(FileSourceConfig {
             base_path: "config".to_string(),
             logger: None,
+            interpolate: false,
         });
 
         // FileSource 实现了 ConfigSource，所以自动获得 Configurable 方法
@@ -320,6 +514,7 @@ mod tests {
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
 
         let service: TestService2 = source.create::<TestService2, TestServiceConfig>("create_test.json", None)?;
@@ -340,6 +535,7 @@ mod tests {
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
 
         let result: Result<TestService2> = source.create::<TestService2, TestServiceConfig>("invalid_test.json", None);
@@ -361,6 +557,7 @@ mod tests {
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
 
         // 创建并监听
@@ -368,7 +565,7 @@ mod tests {
 
         // 验证初始配置
         {
-            let guard = service.read().unwrap();
+            let guard = service.current();
             assert_eq!(guard.config.name, "initial");
             assert_eq!(guard.config.count, 1);
         }
@@ -387,7 +584,7 @@ mod tests {
 
         // 验证配置已更新
         {
-            let guard = service.read().unwrap();
+            let guard = service.current();
             assert_eq!(guard.config.name, "updated");
             assert_eq!(guard.config.count, 2);
         }
@@ -436,13 +633,14 @@ mod tests {
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
 
         let service = source.create_with_watch::<FailingService, FailingConfig>("failing_test.json", None)?;
 
         // 验证初始配置
         {
-            let guard = service.read().unwrap();
+            let guard = service.current();
             assert_eq!(guard.config.value, "valid");
         }
 
@@ -455,7 +653,7 @@ mod tests {
 
         // 验证旧配置被保留
         {
-            let guard = service.read().unwrap();
+            let guard = service.current();
             assert_eq!(guard.config.value, "valid");
         }
 
@@ -520,6 +718,7 @@ mod tests {
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
 
         // 创建并监听
@@ -551,6 +750,52 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[serial]
+    fn test_configurable_create_trait_with_watch_swap() -> Result<()> {
+        register_trait::<TraitServiceA, dyn TestTrait, TraitServiceConfig>("trait-a")?;
+
+        let temp_dir = TempDir::new()?;
+        let config_path = temp_dir.path().join("trait_swap.json");
+        fs::write(
+            &config_path,
+            r#"{"type": "trait-a", "options": {"name": "initial"}}"#,
+        )?;
+
+        let source = FileSource::new(FileSourceConfig {
+            base_path: temp_dir.path().to_string_lossy().to_string(),
+            logger: None,
+            interpolate: false,
+        });
+
+        let service: Arc<ArcSwap<Box<dyn TestTrait>>> =
+            source.create_trait_with_watch_swap::<dyn TestTrait, TraitServiceConfig>("trait_swap.json", None)?;
+
+        // 持有旧快照
+        let snapshot = service.load_full();
+        assert_eq!(snapshot.get_name(), "initial");
+
+        thread::sleep(Duration::from_millis(200));
+
+        fs::write(
+            &config_path,
+            r#"{"type": "trait-a", "options": {"name": "updated"}}"#,
+        )?;
+
+        thread::sleep(Duration::from_millis(500));
+
+        // 新读取获得新对象，旧快照保持不变
+        assert_eq!(service.load().get_name(), "updated");
+        assert_eq!(snapshot.get_name(), "initial");
+
+        // 无效配置不替换当前对象
+        fs::write(&config_path, r#"{"type": "unknown", "options": {}}"#)?;
+        thread::sleep(Duration::from_millis(500));
+        assert_eq!(service.load().get_name(), "updated");
+
+        Ok(())
+    }
+
     #[test]
     fn test_configurable_create_with_explicit_format() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -566,6 +811,7 @@ count: 100"#,
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
 
         // 使用显式格式创建对象
@@ -593,6 +839,7 @@ count = 1"#,
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
 
         // 使用显式格式创建并监听
@@ -600,7 +847,7 @@ count = 1"#,
 
         // 验证初始配置
         {
-            let guard = service.read().unwrap();
+            let guard = service.current();
             assert_eq!(guard.config.name, "initial");
             assert_eq!(guard.config.count, 1);
         }
@@ -618,11 +865,116 @@ count = 2"#,
 
         // 验证配置已更新
         {
-            let guard = service.read().unwrap();
+            let guard = service.current();
             assert_eq!(guard.config.name, "updated");
             assert_eq!(guard.config.count, 2);
         }
 
         Ok(())
     }
+
+    // ========== 配置校验测试 ==========
+
+    #[derive(Debug, Clone, Deserialize, Validate)]
+    struct ValidatedConfig {
+        #[garde(length(min = 1))]
+        name: String,
+        #[garde(range(min = 1, max = 100))]
+        count: usize,
+    }
+
+    struct ValidatedService {
+        config: ValidatedConfig,
+    }
+
+    impl From<ValidatedConfig> for ValidatedService {
+        fn from(config: ValidatedConfig) -> Self {
+            Self { config }
+        }
+    }
+
+    impl ConfigReloader<ValidatedConfig> for ValidatedService {
+        fn reload_config(&mut self, config: ValidatedConfig) -> Result<()> {
+            self.config = config;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_configurable_create_validated() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        fs::write(temp_dir.path().join("valid.json"), r#"{"name": "svc", "count": 10}"#)?;
+        fs::write(temp_dir.path().join("invalid.json"), r#"{"name": "", "count": 1000}"#)?;
+
+        let source = FileSource::new(FileSourceConfig {
+            base_path: temp_dir.path().to_string_lossy().to_string(),
+            logger: None,
+            interpolate: false,
+        });
+
+        let service = source.create_validated::<ValidatedService, ValidatedConfig>("valid.json", None)?;
+        assert_eq!(service.config.count, 10);
+
+        let err = source
+            .create_validated::<ValidatedService, ValidatedConfig>("invalid.json", None)
+            .err()
+            .unwrap();
+        let validation = err.downcast_ref::<ValidationError>().unwrap();
+        assert_eq!(validation.key, "invalid.json");
+        assert_eq!(validation.errors.len(), 2);
+        let count = validation.errors.iter().find(|e| e.path == "count").unwrap();
+        assert_eq!(count.value, Some(serde_json::json!(1000)));
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_configurable_create_with_watch_validated() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let config_path = temp_dir.path().join("validated_watch.json");
+        fs::write(&config_path, r#"{"name": "initial", "count": 1}"#)?;
+
+        let source = FileSource::new(FileSourceConfig {
+            base_path: temp_dir.path().to_string_lossy().to_string(),
+            logger: None,
+            interpolate: false,
+        });
+
+        let errors = Arc::new(RwLock::new(Vec::new()));
+        let errors_clone = errors.clone();
+        let service = source.create_with_watch_validated::<ValidatedService, ValidatedConfig>(
+            "validated_watch.json",
+            None,
+            Arc::new(move |key, e| {
+                errors_clone.write().unwrap().push((key.to_string(), e.to_string()));
+            }),
+        )?;
+
+        thread::sleep(Duration::from_millis(200));
+
+        // 写入校验失败的配置，保留旧配置并触发回调
+        fs::write(&config_path, r#"{"name": "invalid", "count": 0}"#)?;
+        thread::sleep(Duration::from_millis(500));
+
+        assert_eq!(service.current().config.name, "initial");
+        assert_eq!(service.version(), 0);
+        assert!(service.last_reload_error().unwrap().to_string().contains("count"));
+        {
+            let errors = errors.read().unwrap();
+            assert!(!errors.is_empty());
+            assert_eq!(errors[0].0, "validated_watch.json");
+            assert!(errors[0].1.contains("count"));
+        }
+
+        // 写入合法配置后正常更新
+        fs::write(&config_path, r#"{"name": "updated", "count": 2}"#)?;
+        thread::sleep(Duration::from_millis(500));
+
+        assert_eq!(service.current().config.name, "updated");
+        assert_eq!(service.version(), 1);
+        assert!(service.last_reload_error().is_none());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,506 @@
+//! 环境变量配置源
+//!
+//! 支持从环境变量加载配置，按前缀和分隔符将环境变量映射为嵌套的配置树
+//! 支持轮询监听环境变量变化
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use serde_json::{Map, Value as JsonValue};
+use smart_default::SmartDefault;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use super::merged_source::merge_json;
+use super::serde_duration::{serde_as, HumanDur};
+use super::source::{ConfigChange, ConfigSource, ConfigValue};
+use crate::log::{Logger, LoggerConfig};
+use crate::{impl_box_from, impl_from};
+
+/// 环境变量配置源的配置
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, Serialize, SmartDefault, JsonSchema)]
+#[serde(default)]
+pub struct EnvSourceConfig {
+    /// 环境变量前缀，如 "APP"，为空表示不使用前缀
+    #[default = "APP"]
+    pub prefix: String,
+    /// 层级分隔符，默认为 "__"
+    #[default = "__"]
+    pub separator: String,
+    /// 是否将 "true"/"false" 和数字字符串转换为对应的类型（仅转换能原样还原的值）
+    #[default = true]
+    pub coerce_types: bool,
+    /// 是否将以 `{` 或 `[` 开头的值解析为 JSON
+    #[default = true]
+    pub parse_json: bool,
+    /// 监听时检查环境变量变化的间隔
+    #[serde_as(as = "HumanDur")]
+    #[default(Duration::from_secs(5))]
+    pub poll_interval: Duration,
+    /// Logger 配置（可选，不配置则使用全局默认 logger）
+    pub logger: Option<LoggerConfig>,
+}
+
+/// 环境变量配置源
+///
+/// 将 `{prefix}{separator}{KEY}{separator}{FIELD}...` 形式的环境变量映射为 key 对应的配置树
+///
+/// # 映射规则
+/// - key 转为大写，`.` 和 `-` 替换为 `_`，如 `database` -> `DATABASE`
+/// - 字段名转为小写，多级分隔符表示嵌套，如 `APP__DATABASE__POOL__MAX` -> `{"pool": {"max": ...}}`
+/// - `APP__DATABASE` 本身可以是一个 JSON 值，作为整个配置的基础，字段变量覆盖其中的同名字段
+/// - 开启 `coerce_types` 时，`true`/`false` 转为布尔值，整数和浮点数转为数字；
+///   只转换能原样还原的值，`007`、`1e3` 等保持字符串
+///
+/// # 示例
+/// ```no_run
+/// use rustx::cfg::{ConfigSource, EnvSource, EnvSourceConfig};
+///
+/// // APP__DATABASE__HOST=localhost APP__DATABASE__PORT=3306
+/// let source = EnvSource::new(EnvSourceConfig::default()).unwrap();
+///
+/// // 加载得到 {"host": "localhost", "port": 3306}
+/// let config = source.load("database", None).unwrap();
+/// ```
+pub struct EnvSource {
+    /// 环境变量到配置树的映射规则
+    mapping: EnvMapping,
+    /// 监听轮询间隔
+    poll_interval: Duration,
+    /// Source drop 时通知监听线程退出
+    stopped: Arc<AtomicBool>,
+    /// Logger 实例
+    logger: Arc<Logger>,
+}
+
+impl EnvSource {
+    /// 创建环境变量配置源
+    ///
+    /// # 参数
+    /// - `config`: 环境变量配置源配置
+    pub fn new(config: EnvSourceConfig) -> Result<Self> {
+        if config.separator.is_empty() {
+            return Err(anyhow!("separator 不能为空"));
+        }
+
+        // 解析或创建 logger
+        let logger = match config.logger {
+            Some(logger_config) => Logger::resolve(logger_config)?,
+            None => crate::log::get_default(),
+        };
+
+        logger.info_sync(format!(
+            "[INIT] env_source created - prefix={} separator={}",
+            config.prefix, config.separator
+        ))?;
+
+        Ok(Self {
+            mapping: EnvMapping {
+                prefix: config.prefix.to_uppercase(),
+                separator: config.separator,
+                coerce_types: config.coerce_types,
+                parse_json: config.parse_json,
+            },
+            poll_interval: config.poll_interval,
+            stopped: Arc::new(AtomicBool::new(false)),
+            logger,
+        })
+    }
+}
+
+/// 环境变量映射规则
+#[derive(Debug, Clone)]
+struct EnvMapping {
+    prefix: String,
+    separator: String,
+    coerce_types: bool,
+    parse_json: bool,
+}
+
+impl EnvMapping {
+    /// 计算 key 对应的环境变量名前缀
+    fn var_prefix(&self, key: &str) -> String {
+        let key = key.to_uppercase().replace(['.', '-'], "_");
+        if self.prefix.is_empty() {
+            key
+        } else {
+            format!("{}{}{}", self.prefix, self.separator, key)
+        }
+    }
+
+    /// 将环境变量值转换为 JSON 值
+    fn parse_value(&self, value: &str) -> JsonValue {
+        let trimmed = value.trim();
+
+        if self.parse_json && (trimmed.starts_with('{') || trimmed.starts_with('[')) {
+            if let Ok(v) = serde_json::from_str(trimmed) {
+                return v;
+            }
+        }
+
+        if self.coerce_types {
+            let coerced = match value {
+                "true" => Some(JsonValue::Bool(true)),
+                "false" => Some(JsonValue::Bool(false)),
+                _ => value
+                    .parse::<i64>()
+                    .ok()
+                    .map(JsonValue::from)
+                    .or_else(|| value.parse::<f64>().ok().map(JsonValue::from)),
+            };
+            // 只有转换后的值能原样还原时才转换，`007`、`1e3`、`+1` 等保持字符串
+            let lossless = |v: &JsonValue| serde_json::to_string(v).is_ok_and(|s| s == value);
+            if let Some(coerced) = coerced.filter(lossless) {
+                return coerced;
+            }
+        }
+
+        JsonValue::String(value.to_string())
+    }
+
+    /// 从指定的环境变量集合中构建 key 对应的配置树
+    fn build_value<I>(&self, key: &str, vars: I) -> Option<JsonValue>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let var_prefix = self.var_prefix(key);
+        let field_prefix = format!("{}{}", var_prefix, self.separator);
+
+        let mut base = None;
+        let mut fields = Vec::new();
+        for (name, value) in vars {
+            let upper = name.to_uppercase();
+            if upper == var_prefix {
+                base = Some(self.parse_value(&value));
+            } else if let Some(rest) = upper.strip_prefix(&field_prefix) {
+                let path: Vec<String> = rest
+                    .split(self.separator.as_str())
+                    .map(|s| s.to_lowercase())
+                    .collect();
+                if path.iter().all(|s| !s.is_empty()) {
+                    fields.push((path, self.parse_value(&value)));
+                }
+            }
+        }
+
+        if base.is_none() && fields.is_empty() {
+            return None;
+        }
+
+        // 字段按路径排序，保证结果稳定
+        fields.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut root = base.unwrap_or_else(|| JsonValue::Object(Map::new()));
+        for (path, value) in fields {
+            let mut overlay = value;
+            for segment in path.iter().rev() {
+                let mut map = Map::new();
+                map.insert(segment.clone(), overlay);
+                overlay = JsonValue::Object(map);
+            }
+            if !root.is_object() {
+                root = JsonValue::Object(Map::new());
+            }
+            merge_json(&mut root, &overlay);
+        }
+
+        Some(root)
+    }
+}
+
+impl Drop for EnvSource {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+}
+
+impl_from!(EnvSourceConfig => EnvSource, expect: "创建 EnvSource 失败");
+impl_box_from!(EnvSource => dyn ConfigSource);
+
+impl ConfigSource for EnvSource {
+    /// 加载 key 对应的配置，`format` 参数对环境变量无意义，会被忽略
+    fn load(&self, key: &str, _format: Option<&str>) -> Result<ConfigValue> {
+        match self.mapping.build_value(key, std::env::vars()) {
+            Some(value) => {
+                // 记录加载成功
+                let _ = self.logger.debug_sync(format!(
+                    "[LOAD] env success - key={} prefix={}",
+                    key,
+                    self.mapping.var_prefix(key)
+                ));
+                Ok(ConfigValue::new(value))
+            }
+            None => Err(anyhow!(
+                "环境变量配置不存在: key={}, prefix={}",
+                key,
+                self.mapping.var_prefix(key)
+            )),
+        }
+    }
+
+    fn watch(
+        &self,
+        key: &str,
+        _format: Option<&str>,
+        handler: Box<dyn Fn(ConfigChange) + Send + Sync + 'static>,
+    ) -> Result<()> {
+        let mapping = self.mapping.clone();
+        let mut current = mapping.build_value(key, std::env::vars());
+        let stopped = self.stopped.clone();
+        let logger = self.logger.clone();
+        let poll_interval = self.poll_interval;
+        let key_owned = key.to_string();
+
+        // 记录注册监听器
+        self.logger.info_sync(format!(
+            "[WATCH] listener registered - key={} prefix={}",
+            key,
+            self.mapping.var_prefix(key)
+        ))?;
+
+        thread::spawn(move || {
+            while !stopped.load(Ordering::SeqCst) {
+                thread::sleep(poll_interval);
+                if stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                let latest = mapping.build_value(&key_owned, std::env::vars());
+                if latest == current {
+                    continue;
+                }
+
+                match &latest {
+                    Some(value) => {
+                        // 记录环境变量更新
+                        let _ = logger.info_sync(format!("[CHANGE] env updated - key={}", key_owned));
+                        handler(ConfigChange::Updated(ConfigValue::new(value.clone())));
+                    }
+                    None => {
+                        // 记录环境变量删除
+                        let _ = logger.warn_sync(format!("[CHANGE] env deleted - key={}", key_owned));
+                        handler(ConfigChange::Deleted);
+                    }
+                }
+                current = latest;
+            }
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use std::sync::RwLock;
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_env_source_config_defaults() {
+        let config: EnvSourceConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.prefix, "APP");
+        assert_eq!(config.separator, "__");
+        assert!(config.coerce_types);
+        assert!(config.parse_json);
+        assert_eq!(config.poll_interval, Duration::from_secs(5));
+
+        let config: EnvSourceConfig =
+            serde_json::from_str(r#"{"prefix": "SVC", "poll_interval": "100ms"}"#).unwrap();
+        assert_eq!(config.prefix, "SVC");
+        assert_eq!(config.poll_interval, Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_env_source_empty_separator() {
+        let result = EnvSource::new(EnvSourceConfig {
+            separator: "".to_string(),
+            ..Default::default()
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_env_source_build_nested_value() -> Result<()> {
+        let source = EnvSource::new(EnvSourceConfig::default())?;
+        let value = source
+            .mapping
+            .build_value(
+                "database",
+                vars(&[
+                    ("APP__DATABASE__HOST", "localhost"),
+                    ("APP__DATABASE__PORT", "3306"),
+                    ("APP__DATABASE__RATIO", "0.5"),
+                    ("APP__DATABASE__ENABLED", "true"),
+                    ("APP__DATABASE__POOL__MAX", "100"),
+                    ("APP__CACHE__HOST", "redis"),
+                    ("OTHER__DATABASE__HOST", "ignored"),
+                ]),
+            )
+            .unwrap();
+
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "host": "localhost",
+                "port": 3306,
+                "ratio": 0.5,
+                "enabled": true,
+                "pool": {"max": 100}
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_env_source_coerce_lossless() -> Result<()> {
+        let source = EnvSource::new(EnvSourceConfig::default())?;
+        let value = source
+            .mapping
+            .build_value(
+                "app",
+                vars(&[
+                    ("APP__APP__ZIP", "007"),
+                    ("APP__APP__ZERO", "00"),
+                    ("APP__APP__EXP", "1e3"),
+                    ("APP__APP__FLOAT", "1.0"),
+                    ("APP__APP__NEGATIVE", "-12"),
+                    ("APP__APP__PLUS", "+1"),
+                    ("APP__APP__UPPER", "TRUE"),
+                    ("APP__APP__NAN", "NaN"),
+                ]),
+            )
+            .unwrap();
+
+        // 带前导零、指数形式等转换后不能原样还原的值保持字符串
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "zip": "007",
+                "zero": "00",
+                "exp": "1e3",
+                "float": 1.0,
+                "negative": -12,
+                "plus": "+1",
+                "upper": "TRUE",
+                "nan": "NaN"
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_env_source_json_value() -> Result<()> {
+        let source = EnvSource::new(EnvSourceConfig::default())?;
+        let value = source
+            .mapping
+            .build_value(
+                "database",
+                vars(&[
+                    ("APP__DATABASE", r#"{"host": "localhost", "port": 3306}"#),
+                    ("APP__DATABASE__PORT", "3307"),
+                    ("APP__DATABASE__TAGS", r#"["a", "b"]"#),
+                ]),
+            )
+            .unwrap();
+
+        assert_eq!(
+            value,
+            serde_json::json!({"host": "localhost", "port": 3307, "tags": ["a", "b"]})
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_env_source_without_coercion() -> Result<()> {
+        let source = EnvSource::new(EnvSourceConfig {
+            prefix: "".to_string(),
+            separator: "_".to_string(),
+            coerce_types: false,
+            parse_json: false,
+            ..Default::default()
+        })?;
+        let value = source
+            .mapping
+            .build_value(
+                "redis",
+                vars(&[("REDIS_PORT", "6379"), ("REDIS_ENDPOINTS", r#"["a"]"#)]),
+            )
+            .unwrap();
+
+        assert_eq!(
+            value,
+            serde_json::json!({"port": "6379", "endpoints": r#"["a"]"#})
+        );
+        assert!(source.mapping.build_value("mysql", vars(&[("REDIS_PORT", "6379")])).is_none());
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_env_source_load() -> Result<()> {
+        std::env::set_var("RUSTX_ENV_LOAD__SERVER__HOST", "0.0.0.0");
+        std::env::set_var("RUSTX_ENV_LOAD__SERVER__PORT", "8080");
+
+        let source = EnvSource::new(EnvSourceConfig {
+            prefix: "rustx_env_load".to_string(),
+            ..Default::default()
+        })?;
+        let config = source.load("server", None)?;
+        assert_eq!(config.as_value()["host"], "0.0.0.0");
+        assert_eq!(config.as_value()["port"], 8080);
+
+        assert!(source.load("nonexistent", None).is_err());
+
+        std::env::remove_var("RUSTX_ENV_LOAD__SERVER__HOST");
+        std::env::remove_var("RUSTX_ENV_LOAD__SERVER__PORT");
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_env_source_watch() -> Result<()> {
+        std::env::set_var("RUSTX_ENV_WATCH__APP__NAME", "initial");
+
+        let source = EnvSource::new(EnvSourceConfig {
+            prefix: "RUSTX_ENV_WATCH".to_string(),
+            poll_interval: Duration::from_millis(50),
+            ..Default::default()
+        })?;
+
+        let changes = Arc::new(RwLock::new(Vec::new()));
+        let changes_clone = changes.clone();
+        source.watch(
+            "app",
+            None,
+            Box::new(move |change| {
+                changes_clone.write().unwrap().push(change);
+            }),
+        )?;
+
+        std::env::set_var("RUSTX_ENV_WATCH__APP__NAME", "updated");
+        thread::sleep(Duration::from_millis(300));
+
+        std::env::remove_var("RUSTX_ENV_WATCH__APP__NAME");
+        thread::sleep(Duration::from_millis(300));
+
+        let changes_vec = changes.read().unwrap();
+        let has_update = changes_vec.iter().any(
+            |c| matches!(c, ConfigChange::Updated(config) if config.as_value()["name"] == "updated"),
+        );
+        assert!(has_update, "应该收到配置更新通知");
+        let has_delete = changes_vec.iter().any(|c| matches!(c, ConfigChange::Deleted));
+        assert!(has_delete, "应该收到配置删除通知");
+
+        Ok(())
+    }
+}
@@ -5,6 +5,7 @@
 
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use serde_json::Value as JsonValue;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -14,12 +15,15 @@ use crate::{impl_box_from, impl_from};
 use crate::log::{Logger, LoggerConfig};
 
 /// 文件配置源的配置
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct FileSourceConfig {
     /// 配置文件所在目录
     pub base_path: String,
     /// Logger 配置（可选，不配置则使用全局默认 logger）
     pub logger: Option<LoggerConfig>,
+    /// 是否解析字符串值中的 `${ENV_VAR:-default}` 和 `${file:/path/to/secret}` 占位符
+    #[serde(default)]
+    pub interpolate: bool,
 }
 
 /// 文件配置源
@@ -34,6 +38,7 @@ pub struct FileSourceConfig {
 /// let source = FileSource::new(FileSourceConfig {
 ///     base_path: "config".to_string(),
 ///     logger: None,
+///     interpolate: false,
 /// });
 ///
 /// // 加载 config/database.json（自动根据扩展名推断格式）
@@ -44,6 +49,8 @@ pub struct FileSourceConfig {
 /// ```
 pub struct FileSource {
     base_path: PathBuf,
+    /// 是否解析占位符
+    interpolate: bool,
     /// Logger 实例
     logger: Arc<Logger>,
 }
@@ -69,6 +76,7 @@ impl FileSource {
 
         Self {
             base_path: config.base_path.into(),
+            interpolate: config.interpolate,
             logger,
         }
     }
@@ -135,6 +143,83 @@ impl FileSource {
             _ => Err(anyhow!("不支持的配置格式: {}", format)),
         }
     }
+
+    /// 递归解析配置中所有字符串值的占位符
+    ///
+    /// 支持的占位符：
+    /// - `${ENV_VAR}`：环境变量，未定义时返回错误
+    /// - `${ENV_VAR:-default}`：环境变量，未定义时使用默认值
+    /// - `${file:/path/to/secret}`：文件内容（去除末尾换行），常用于挂载的密钥文件
+    /// - `$${`：转义为字面的 `${`
+    ///
+    /// 只处理包含 `${` 的字符串，不带花括号的 `$name` 原样保留，对象的 key 不做处理
+    fn interpolate_value(value: JsonValue) -> Result<JsonValue> {
+        match value {
+            JsonValue::String(s) if s.contains("${") => Ok(JsonValue::String(Self::expand(&s)?)),
+            JsonValue::Array(arr) => Ok(JsonValue::Array(
+                arr.into_iter()
+                    .map(Self::interpolate_value)
+                    .collect::<Result<_>>()?,
+            )),
+            JsonValue::Object(obj) => Ok(JsonValue::Object(
+                obj.into_iter()
+                    .map(|(k, v)| Ok((k, Self::interpolate_value(v)?)))
+                    .collect::<Result<_>>()?,
+            )),
+            other => Ok(other),
+        }
+    }
+
+    /// 展开字符串中的 `${...}` 占位符
+    fn expand(s: &str) -> Result<String> {
+        let mut expanded = String::with_capacity(s.len());
+        let mut rest = s;
+        while let Some(start) = rest.find("${") {
+            // `$${` 转义为字面的 `${`
+            if rest[..start].ends_with('$') {
+                expanded.push_str(&rest[..start - 1]);
+                expanded.push_str("${");
+                rest = &rest[start + 2..];
+                continue;
+            }
+
+            expanded.push_str(&rest[..start]);
+            let body = &rest[start + 2..];
+            let end = body
+                .find('}')
+                .ok_or_else(|| anyhow!("解析占位符失败: 缺少 '}}': {}", &rest[start..]))?;
+            expanded.push_str(&Self::resolve_placeholder(&body[..end])?);
+            rest = &body[end + 1..];
+        }
+        expanded.push_str(rest);
+        Ok(expanded)
+    }
+
+    /// 解析单个占位符，`name:-default` 在 name 未定义或为空时使用默认值
+    fn resolve_placeholder(placeholder: &str) -> Result<String> {
+        let (name, default) = match placeholder.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (placeholder, None),
+        };
+
+        match (Self::lookup_placeholder(name), default) {
+            (Ok(value), Some(default)) if value.is_empty() => Ok(default.to_string()),
+            (Ok(value), _) => Ok(value),
+            (Err(_), Some(default)) => Ok(default.to_string()),
+            (Err(e), None) => Err(anyhow!("解析占位符失败: {}: {}", name, e)),
+        }
+    }
+
+    /// 查找占位符对应的值
+    fn lookup_placeholder(name: &str) -> std::result::Result<String, String> {
+        if let Some(path) = name.strip_prefix("file:") {
+            return std::fs::read_to_string(path)
+                .map(|content| content.trim_end_matches(['\r', '\n']).to_string())
+                .map_err(|e| format!("读取文件失败: {}", e));
+        }
+
+        std::env::var(name).map_err(|_| "环境变量未定义".to_string())
+    }
 }
 
 impl ConfigSource for FileSource {
@@ -158,16 +243,8 @@ impl ConfigSource for FileSource {
             anyhow!("读取配置文件失败: {}, path: {:?}", e, path)
         })?;
 
-        match Self::parse_config_with_format(&content, &fmt) {
-            Ok(value) => {
-                // 记录加载成功
-                let _ = self.logger.debug_sync(&format!(
-                    "[LOAD] file success - path={} format={}",
-                    path.display(),
-                    fmt
-                ));
-                Ok(ConfigValue::new(value))
-            }
+        let value = match Self::parse_config_with_format(&content, &fmt) {
+            Ok(value) => value,
             Err(e) => {
                 // 记录解析失败
                 let _ = self.logger.error_sync(&format!(
@@ -176,9 +253,31 @@ impl ConfigSource for FileSource {
                     fmt,
                     e
                 ));
-                Err(anyhow!("解析 {} 格式配置文件失败: {}, path: {:?}", fmt, e, path))
+                return Err(anyhow!("解析 {} 格式配置文件失败: {}, path: {:?}", fmt, e, path));
             }
-        }
+        };
+
+        let value = if self.interpolate {
+            Self::interpolate_value(value).map_err(|e| {
+                // 记录占位符解析失败
+                let _ = self.logger.error_sync(format!(
+                    "[ERROR] file interpolate_failed - path={} error={}",
+                    path.display(),
+                    e
+                ));
+                anyhow!("{}, path: {:?}", e, path)
+            })?
+        } else {
+            value
+        };
+
+        // 记录加载成功
+        let _ = self.logger.debug_sync(format!(
+            "[LOAD] file success - path={} format={}",
+            path.display(),
+            fmt
+        ));
+        Ok(ConfigValue::new(value))
     }
 
     fn watch(
@@ -190,6 +289,7 @@ impl ConfigSource for FileSource {
         let (file_path, fmt) = self.find_config_file(key, format)?;
         let file_path_clone = file_path.clone();
         let fmt_clone = fmt.clone();
+        let interpolate = self.interpolate;
         let handler = Arc::new(handler);
         let logger = self.logger.clone();
 
@@ -208,6 +308,25 @@ impl ConfigSource for FileSource {
                 crate::fs::FileEvent::Modified(_) | crate::fs::FileEvent::Created(_) => {
                     match std::fs::read_to_string(&file_path_clone) {
                         Ok(content) => match Self::parse_config_with_format(&content, &fmt_clone) {
+                            Ok(value) if interpolate => match Self::interpolate_value(value) {
+                                Ok(value) => {
+                                    // 记录文件更新
+                                    let _ = logger.info_sync(format!(
+                                        "[CHANGE] file updated - path={}",
+                                        file_path_clone.display()
+                                    ));
+                                    handler(ConfigChange::Updated(ConfigValue::new(value)))
+                                }
+                                Err(e) => {
+                                    // 记录占位符解析失败
+                                    let _ = logger.error_sync(format!(
+                                        "[ERROR] file interpolate_failed - path={} error={}",
+                                        file_path_clone.display(),
+                                        e
+                                    ));
+                                    handler(ConfigChange::Error(e.to_string()))
+                                }
+                            },
                             Ok(value) => {
                                 // 记录文件更新
                                 let _ = logger.info_sync(&format!(
@@ -302,6 +421,7 @@ mod tests {
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
         let config = source.load("test.json", None)?;
 
@@ -327,6 +447,7 @@ port: 3306
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
         let config = source.load("test.yaml", None)?;
 
@@ -352,6 +473,7 @@ port = 3306
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
         let config = source.load("test.toml", None)?;
 
@@ -367,6 +489,7 @@ port = 3306
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
 
         let result = source.load("nonexistent.json", None);
@@ -386,6 +509,7 @@ port = 3306
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
 
         // 使用 Arc<RwLock> 存储变更通知
@@ -435,6 +559,7 @@ port = 3306
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
 
         let changes = Arc::new(RwLock::new(Vec::new()));
@@ -476,6 +601,7 @@ port = 3306
             let source = FileSource::new(FileSourceConfig {
                 base_path: temp_dir.path().to_string_lossy().to_string(),
                 logger: None,
+                interpolate: false,
             });
             source.watch("cleanup_test.json", None, Box::new(|_| {}))?;
 
@@ -505,6 +631,7 @@ port: 3306
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
 
         // 使用自动推断（None）应该失败，因为 .config 扩展名不支持
@@ -535,6 +662,7 @@ port = 3306
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
 
         // 指定 TOML 格式
@@ -555,6 +683,7 @@ port = 3306
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
 
         // 测试大小写不敏感
@@ -577,6 +706,7 @@ port = 3306
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
 
         // 不支持的扩展名
@@ -596,6 +726,7 @@ port = 3306
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
 
         // 显式指定不支持的格式
@@ -616,6 +747,7 @@ port = 3306
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
 
         let changes = Arc::new(RwLock::new(Vec::new()));
@@ -666,6 +798,7 @@ value: 42
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
 
         // 自动推断格式（从 .yml 扩展名）
@@ -694,6 +827,7 @@ value: 42
         let source = FileSource::new(FileSourceConfig {
             base_path: temp_dir.path().to_string_lossy().to_string(),
             logger: None,
+            interpolate: false,
         });
 
         // 自动推断格式（从 .json5 扩展名）
@@ -703,4 +837,159 @@ value: 42
 
         Ok(())
     }
+
+    #[test]
+    #[serial]
+    fn test_file_source_load_with_interpolate() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let secret_path = temp_dir.path().join("redis_password");
+        fs::write(&secret_path, "s3cr3t\n")?;
+        fs::write(
+            temp_dir.path().join("redis.json"),
+            format!(
+                r#"{{
+                    "endpoint": "${{RUSTX_TEST_REDIS_HOST}}:${{RUSTX_TEST_REDIS_PORT:-6379}}",
+                    "password": "${{file:{}}}",
+                    "tags": ["${{RUSTX_TEST_REDIS_HOST}}", "plain"],
+                    "db": 0
+                }}"#,
+                secret_path.display()
+            ),
+        )?;
+        std::env::set_var("RUSTX_TEST_REDIS_HOST", "redis.local");
+
+        let source = FileSource::new(FileSourceConfig {
+            base_path: temp_dir.path().to_string_lossy().to_string(),
+            logger: None,
+            interpolate: true,
+        });
+        let config = source.load("redis.json", None)?;
+
+        assert_eq!(config.as_value()["endpoint"], "redis.local:6379");
+        assert_eq!(config.as_value()["password"], "s3cr3t");
+        assert_eq!(config.as_value()["tags"][0], "redis.local");
+        assert_eq!(config.as_value()["tags"][1], "plain");
+        assert_eq!(config.as_value()["db"], 0);
+
+        std::env::remove_var("RUSTX_TEST_REDIS_HOST");
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_file_source_interpolate_bare_dollar_and_escape() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        fs::write(
+            temp_dir.path().join("escape.json"),
+            r#"{
+                "password": "p$ss${RUSTX_TEST_ESCAPE_HOST}",
+                "price": "$100",
+                "template": "$${RUSTX_TEST_ESCAPE_HOST} is ${RUSTX_TEST_ESCAPE_HOST}"
+            }"#,
+        )?;
+        std::env::set_var("RUSTX_TEST_ESCAPE_HOST", "redis.local");
+
+        let source = FileSource::new(FileSourceConfig {
+            base_path: temp_dir.path().to_string_lossy().to_string(),
+            logger: None,
+            interpolate: true,
+        });
+        let config = source.load("escape.json", None)?;
+
+        // 不带花括号的 $ 原样保留，$${ 转义为字面的 ${
+        assert_eq!(config.as_value()["password"], "p$ssredis.local");
+        assert_eq!(config.as_value()["price"], "$100");
+        assert_eq!(
+            config.as_value()["template"],
+            "${RUSTX_TEST_ESCAPE_HOST} is redis.local"
+        );
+
+        std::env::remove_var("RUSTX_TEST_ESCAPE_HOST");
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_source_load_interpolate_errors() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        fs::write(
+            temp_dir.path().join("missing_env.json"),
+            r#"{"password": "${RUSTX_TEST_UNDEFINED_VAR}"}"#,
+        )?;
+        fs::write(
+            temp_dir.path().join("missing_file.json"),
+            r#"{"password": "${file:/nonexistent/rustx/secret}"}"#,
+        )?;
+
+        let source = FileSource::new(FileSourceConfig {
+            base_path: temp_dir.path().to_string_lossy().to_string(),
+            logger: None,
+            interpolate: true,
+        });
+        assert!(source.load("missing_env.json", None).is_err());
+        assert!(source.load("missing_file.json", None).is_err());
+
+        // 缺少右花括号
+        fs::write(temp_dir.path().join("unclosed.json"), r#"{"host": "${RUSTX_TEST_HOST"}"#)?;
+        assert!(source.load("unclosed.json", None).is_err());
+
+        // 未开启 interpolate 时保留原始字符串
+        let source = FileSource::new(FileSourceConfig {
+            base_path: temp_dir.path().to_string_lossy().to_string(),
+            logger: None,
+            interpolate: false,
+        });
+        let config = source.load("missing_env.json", None)?;
+        assert_eq!(config.as_value()["password"], "${RUSTX_TEST_UNDEFINED_VAR}");
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_file_source_watch_with_interpolate() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let secret_path = temp_dir.path().join("token");
+        let config_path = temp_dir.path().join("watch_secret.json");
+        fs::write(&secret_path, "token-v1")?;
+        fs::write(
+            &config_path,
+            format!(r#"{{"version": 1, "token": "${{file:{}}}"}}"#, secret_path.display()),
+        )?;
+
+        let source = FileSource::new(FileSourceConfig {
+            base_path: temp_dir.path().to_string_lossy().to_string(),
+            logger: None,
+            interpolate: true,
+        });
+
+        let changes = Arc::new(RwLock::new(Vec::new()));
+        let changes_clone = changes.clone();
+        source.watch(
+            "watch_secret.json",
+            None,
+            Box::new(move |change| {
+                changes_clone.write().unwrap().push(change);
+            }),
+        )?;
+
+        thread::sleep(Duration::from_millis(200));
+
+        // 修改配置文件时重新解析占位符
+        fs::write(&secret_path, "token-v2")?;
+        fs::write(
+            &config_path,
+            format!(r#"{{"version": 2, "token": "${{file:{}}}"}}"#, secret_path.display()),
+        )?;
+
+        thread::sleep(Duration::from_millis(500));
+
+        let changes_vec = changes.read().unwrap();
+        let has_update = changes_vec.iter().any(|c| {
+            matches!(c, ConfigChange::Updated(config)
+                if config.as_value()["version"] == 2 && config.as_value()["token"] == "token-v2")
+        });
+        assert!(has_update, "应该收到解析占位符后的配置更新通知");
+
+        Ok(())
+    }
 }
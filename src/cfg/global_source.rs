@@ -6,10 +6,12 @@ use anyhow::Result;
 use once_cell::sync::Lazy;
 use std::sync::{Arc, RwLock};
 
+use super::snapshot::{ConfigSnapshot, Redactor};
+use super::tracked_source::TrackedSource;
 use super::{create_trait_from_type_options, source::ConfigChange, ConfigSource, ConfigValue, TypeOptions};
 
-/// 全局 ConfigSource 单例
-static GLOBAL_SOURCE: Lazy<Arc<RwLock<Option<Box<dyn ConfigSource>>>>> =
+/// 全局 ConfigSource 单例，使用 TrackedSource 记录加载和监听过的配置
+static GLOBAL_SOURCE: Lazy<Arc<RwLock<Option<TrackedSource>>>> =
     Lazy::new(|| Arc::new(RwLock::new(None)));
 
 /// 初始化全局 ConfigSource
@@ -44,7 +46,7 @@ pub fn init(options: TypeOptions) -> Result<()> {
     let mut global = GLOBAL_SOURCE
         .write()
         .map_err(|e| anyhow::anyhow!("Failed to acquire write lock: {}", e))?;
-    *global = Some(source);
+    *global = Some(TrackedSource::new(source));
     Ok(())
 }
 
@@ -126,6 +128,58 @@ pub fn watch(
     }
 }
 
+/// 列出通过全局配置源加载或监听过的所有 key
+///
+/// # 返回
+/// - 按字典序排列的 key 列表
+/// - 未初始化全局配置源时返回错误
+pub fn loaded_keys() -> Result<Vec<String>> {
+    let global = GLOBAL_SOURCE
+        .read()
+        .map_err(|e| anyhow::anyhow!("Failed to acquire read lock: {}", e))?;
+    match global.as_ref() {
+        Some(source) => Ok(source.keys()),
+        None => Err(anyhow::anyhow!(
+            "Global source not initialized. Call init() first."
+        )),
+    }
+}
+
+/// 导出全局配置源当前生效的配置
+///
+/// 使用默认脱敏规则，字段名包含 `password`、`secret`、`access_key` 的值会被替换
+///
+/// # 示例
+/// ```ignore
+/// use rustx::cfg::dump;
+///
+/// // 启动时输出当前配置
+/// println!("{}", dump()?.to_json_pretty());
+///
+/// // 对比两次快照
+/// let before = dump()?;
+/// // ...
+/// for diff in before.diff(&dump()?) {
+///     println!("{}", diff);
+/// }
+/// ```
+pub fn dump() -> Result<ConfigSnapshot> {
+    dump_with(&Redactor::default())
+}
+
+/// 使用指定的脱敏规则导出全局配置源当前生效的配置
+pub fn dump_with(redactor: &Redactor) -> Result<ConfigSnapshot> {
+    let global = GLOBAL_SOURCE
+        .read()
+        .map_err(|e| anyhow::anyhow!("Failed to acquire read lock: {}", e))?;
+    match global.as_ref() {
+        Some(source) => Ok(source.snapshot(redactor)),
+        None => Err(anyhow::anyhow!(
+            "Global source not initialized. Call init() first."
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -248,4 +302,31 @@ value: 100
         assert!(result.is_err());
     }
 
+    #[test]
+    #[serial]
+    fn test_dump_global_source() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        setup_global_source(&temp_dir)?;
+        fs::write(
+            temp_dir.path().join("secret.json"),
+            r#"{"user": "admin", "password": "123"}"#,
+        )?;
+
+        load("test.json", None)?;
+        load("secret.json", None)?;
+
+        assert_eq!(loaded_keys()?, vec!["secret.json", "test.json"]);
+
+        let snapshot = dump()?;
+        assert_eq!(snapshot.values["test.json"]["value"], 42);
+        assert_eq!(snapshot.values["secret.json"]["user"], "admin");
+        assert_eq!(snapshot.values["secret.json"]["password"], "******");
+
+        let snapshot = dump_with(&Redactor::new(["user"]))?;
+        assert_eq!(snapshot.values["secret.json"]["user"], "******");
+        assert_eq!(snapshot.values["secret.json"]["password"], "123");
+
+        Ok(())
+    }
+
 }
@@ -0,0 +1,685 @@
+//! 配置组
+//!
+//! 将多个配置键作为一个整体加载和热更新：一次重载中所有对象的 reload_config 全部成功才生效，
+//! 任意一个失败时回滚已应用的对象。通过 `ConfigGroupHandle::read` 读取时不会看到重载中的中间状态
+
+use anyhow::{anyhow, Result};
+use serde::de::DeserializeOwned;
+use serde_json::Value as JsonValue;
+use std::any::Any;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard};
+use std::thread;
+use std::time::Duration;
+
+use super::configurable::ReloadErrorHandler;
+use super::handle::ConfigHandle;
+use super::reloader::ConfigReloader;
+use super::source::{ConfigChange, ConfigSource, ConfigValue};
+
+/// 配置组成员
+trait GroupMember: Send + Sync {
+    /// 解析配置，返回待应用的配置
+    fn prepare(&self, value: &ConfigValue) -> Result<Box<dyn Any + Send>>;
+    /// 应用配置，并保留旧配置用于回滚
+    fn apply(&self, config: Box<dyn Any + Send>) -> Result<()>;
+    /// 回滚到应用前的配置
+    fn rollback(&self) -> Result<()>;
+    /// 提交本次重载
+    fn commit(&self);
+    /// 记录重载错误
+    fn set_error(&self, error: Arc<anyhow::Error>);
+    /// 成员的 `ConfigHandle<T>`，用于按类型取回
+    fn handle(&self) -> &dyn Any;
+}
+
+/// 配置组成员的实现
+struct Member<T, Config> {
+    handle: ConfigHandle<T>,
+    /// 当前已应用的配置
+    applied: Mutex<Config>,
+    /// 本次重载前的配置，提交或回滚后清空
+    previous: Mutex<Option<Config>>,
+}
+
+impl<T, Config> GroupMember for Member<T, Config>
+where
+    T: ConfigReloader<Config> + Send + Sync + 'static,
+    Config: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    fn prepare(&self, value: &ConfigValue) -> Result<Box<dyn Any + Send>> {
+        Ok(Box::new(value.as_type::<Config>()?))
+    }
+
+    fn apply(&self, config: Box<dyn Any + Send>) -> Result<()> {
+        let config = *config
+            .downcast::<Config>()
+            .map_err(|_| anyhow!("配置类型不匹配"))?;
+
+        self.handle.write().reload_config(config.clone())?;
+
+        let old = std::mem::replace(&mut *self.applied.lock().unwrap(), config);
+        *self.previous.lock().unwrap() = Some(old);
+        Ok(())
+    }
+
+    fn rollback(&self) -> Result<()> {
+        if let Some(previous) = self.previous.lock().unwrap().take() {
+            self.handle.write().reload_config(previous.clone())?;
+            *self.applied.lock().unwrap() = previous;
+        }
+        Ok(())
+    }
+
+    fn commit(&self) {
+        self.previous.lock().unwrap().take();
+        self.handle.commit();
+    }
+
+    fn set_error(&self, error: Arc<anyhow::Error>) {
+        self.handle.set_error(error);
+    }
+
+    fn handle(&self) -> &dyn Any {
+        &self.handle
+    }
+}
+
+/// 成员构造函数，根据初始配置创建成员
+type MemberBuilder = Box<dyn Fn(ConfigValue) -> Result<Arc<dyn GroupMember>> + Send + Sync>;
+
+/// 成员描述
+struct MemberSpec {
+    key: String,
+    format: Option<String>,
+    build: MemberBuilder,
+}
+
+/// 配置组定义
+///
+/// 通过 `add` 添加配置键和对应的对象类型，再交给 `Configurable::create_group_with_watch` 创建
+///
+/// # 示例
+/// ```ignore
+/// let group = source.create_group_with_watch(
+///     ConfigGroup::new()
+///         .add::<DatabaseService, DatabaseConfig>("database.json", None)
+///         .add::<CacheService, CacheConfig>("cache.json", None),
+/// )?;
+///
+/// let database = group.get::<DatabaseService>("database.json")?;
+/// let cache = group.get::<CacheService>("cache.json")?;
+/// ```
+pub struct ConfigGroup {
+    members: Vec<MemberSpec>,
+    debounce: Duration,
+    on_error: ReloadErrorHandler,
+}
+
+impl Default for ConfigGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigGroup {
+    /// 创建空的配置组
+    ///
+    /// 默认合并 100ms 内的变化后再重载，重载失败时输出到默认 logger
+    pub fn new() -> Self {
+        Self {
+            members: Vec::new(),
+            debounce: Duration::from_millis(100),
+            on_error: Arc::new(|key, e| {
+                let _ = crate::log::get_default().warn_sync(format!(
+                    "[ERROR] group reload_failed - key={} error={}",
+                    key, e
+                ));
+            }),
+        }
+    }
+
+    /// 添加配置键
+    ///
+    /// # 类型参数
+    /// - `T`: 目标类型，必须实现 From<Config> 和 ConfigReloader<Config>
+    /// - `Config`: 配置类型
+    ///
+    /// # 参数
+    /// - `key`: 配置键
+    /// - `format`: 配置格式，None 表示自动推断
+    pub fn add<T, Config>(mut self, key: &str, format: Option<&str>) -> Self
+    where
+        T: From<Config> + ConfigReloader<Config> + Send + Sync + 'static,
+        Config: DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        self.members.push(MemberSpec {
+            key: key.to_string(),
+            format: format.map(|s| s.to_string()),
+            build: Box::new(|value| {
+                let config: Config = value.into_type()?;
+                Ok(Arc::new(Member {
+                    handle: ConfigHandle::new(T::from(config.clone())),
+                    applied: Mutex::new(config),
+                    previous: Mutex::new(None),
+                }) as Arc<dyn GroupMember>)
+            }),
+        });
+        self
+    }
+
+    /// 设置合并变化的时间窗口
+    ///
+    /// 同一次发布中多个键的变化通常先后到达，窗口内的变化会合并为一次重载
+    pub fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// 设置重载失败回调，参数为导致失败的配置键和错误
+    pub fn on_error(mut self, on_error: ReloadErrorHandler) -> Self {
+        self.on_error = on_error;
+        self
+    }
+}
+
+/// 配置组运行时状态
+struct GroupState {
+    keys: Vec<String>,
+    members: Vec<Arc<dyn GroupMember>>,
+    /// 每个键最新收到的配置
+    latest: Mutex<Vec<ConfigValue>>,
+    /// 每个键已应用的配置，用于判断哪些成员需要重载
+    applied: Mutex<Vec<JsonValue>>,
+    /// 变化计数，用于合并时间窗口内的变化
+    generation: AtomicU64,
+    /// 组锁，重载期间（应用、回滚和提交）持有写锁，`ConfigGroupHandle::read` 持有读锁
+    lock: RwLock<()>,
+    debounce: Duration,
+    on_error: ReloadErrorHandler,
+}
+
+impl GroupState {
+    /// 收到某个键的变化，等待时间窗口结束后重载
+    fn schedule(self: &Arc<Self>, index: usize, value: ConfigValue) {
+        self.latest.lock().unwrap()[index] = value;
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+
+        let state = self.clone();
+        thread::spawn(move || {
+            thread::sleep(state.debounce);
+            // 窗口内有新的变化时交给最后一次变化处理
+            if state.generation.load(Ordering::SeqCst) == generation {
+                state.reload();
+            }
+        });
+    }
+
+    /// 以事务方式重载所有变化的成员
+    fn reload(&self) {
+        // 持有 applied 锁期间完成整个事务，保证重载串行执行
+        let mut applied = self.applied.lock().unwrap();
+        let latest = self.latest.lock().unwrap().clone();
+
+        let group = self.lock.write().unwrap_or_else(|e| e.into_inner());
+        let result = self.apply_all(&latest, &applied);
+        if let Ok(changed) = &result {
+            for &i in changed {
+                self.members[i].commit();
+                applied[i] = latest[i].as_value().clone();
+            }
+        }
+        drop(group);
+
+        match result {
+            Ok(changed) if changed.is_empty() => {}
+            Ok(changed) => {
+                let keys: Vec<&str> = changed.iter().map(|&i| self.keys[i].as_str()).collect();
+                let _ = crate::log::get_default()
+                    .info_sync(format!("[RELOAD] group updated - keys={}", keys.join(",")));
+            }
+            Err((index, e)) => self.report_error(index, e),
+        }
+    }
+
+    /// 解析并应用所有变化的成员，返回已应用成员的下标
+    ///
+    /// 任意成员解析失败时不应用任何成员；应用失败时逆序回滚已应用的成员
+    fn apply_all(
+        &self,
+        latest: &[ConfigValue],
+        applied: &[JsonValue],
+    ) -> std::result::Result<Vec<usize>, (usize, anyhow::Error)> {
+        let mut prepared = Vec::new();
+        for (i, member) in self.members.iter().enumerate() {
+            if latest[i].as_value() != &applied[i] {
+                let config = member.prepare(&latest[i]).map_err(|e| (i, e))?;
+                prepared.push((i, config));
+            }
+        }
+
+        let mut done: Vec<usize> = Vec::new();
+        for (i, config) in prepared {
+            if let Err(e) = self.members[i].apply(config) {
+                for &j in done.iter().rev() {
+                    if let Err(rollback_err) = self.members[j].rollback() {
+                        let _ = crate::log::get_default().warn_sync(format!(
+                            "[ERROR] group rollback_failed - key={} error={}",
+                            self.keys[j], rollback_err
+                        ));
+                    }
+                }
+                return Err((i, e));
+            }
+            done.push(i);
+        }
+
+        Ok(done)
+    }
+
+    /// 记录错误到所有成员并调用回调
+    fn report_error(&self, index: usize, error: anyhow::Error) {
+        let error = Arc::new(error);
+        for member in &self.members {
+            member.set_error(error.clone());
+        }
+        (self.on_error)(&self.keys[index], &error);
+    }
+
+    /// 配置键对应成员的句柄
+    fn handle<T: Send + Sync + 'static>(&self, key: &str) -> Result<&ConfigHandle<T>> {
+        let index = self
+            .keys
+            .iter()
+            .position(|k| k == key)
+            .ok_or_else(|| anyhow!("配置组中不存在 key: {}", key))?;
+
+        self.members[index]
+            .handle()
+            .downcast_ref::<ConfigHandle<T>>()
+            .ok_or_else(|| {
+                anyhow!(
+                    "配置组 key [{}] 的类型不是 {}",
+                    key,
+                    std::any::type_name::<T>()
+                )
+            })
+    }
+}
+
+/// 配置组句柄
+///
+/// 由 `Configurable::create_group_with_watch` 返回，通过 `get` 按配置键取回各个对象的 `ConfigHandle<T>`
+///
+/// 组内对象的版本号只在整个组重载成功时递增，重载失败时所有成员的 `last_reload_error()` 都会返回该错误
+#[derive(Clone)]
+pub struct ConfigGroupHandle {
+    state: Arc<GroupState>,
+}
+
+impl ConfigGroupHandle {
+    /// 获取配置键对应对象的句柄
+    ///
+    /// 配置键不在组内或类型不匹配时返回错误
+    pub fn get<T: Send + Sync + 'static>(&self, key: &str) -> Result<ConfigHandle<T>> {
+        self.state.handle(key).cloned()
+    }
+
+    /// 获取组的读锁，持有期间组不会重载，读取的所有成员来自同一次成功的重载
+    ///
+    /// 通过 `get` 取得的句柄各自读取，重载过程中可能读到部分成员已应用、随后被回滚的配置
+    ///
+    /// # 示例
+    /// ```ignore
+    /// let guard = group.read();
+    /// let database = guard.current::<DatabaseService>("database.json")?;
+    /// let cache = guard.current::<CacheService>("cache.json")?;
+    /// ```
+    pub fn read(&self) -> ConfigGroupReadGuard<'_> {
+        ConfigGroupReadGuard {
+            state: &self.state,
+            _guard: self.state.lock.read().unwrap_or_else(|e| e.into_inner()),
+        }
+    }
+
+    /// 组内所有配置键
+    pub fn keys(&self) -> &[String] {
+        &self.state.keys
+    }
+}
+
+/// 配置组读锁，由 `ConfigGroupHandle::read` 返回，释放前组不会重载
+pub struct ConfigGroupReadGuard<'a> {
+    state: &'a GroupState,
+    _guard: RwLockReadGuard<'a, ()>,
+}
+
+impl ConfigGroupReadGuard<'_> {
+    /// 获取配置键对应的当前对象
+    ///
+    /// 配置键不在组内或类型不匹配时返回错误
+    pub fn current<T: Send + Sync + 'static>(&self, key: &str) -> Result<RwLockReadGuard<'_, T>> {
+        Ok(self.state.handle::<T>(key)?.current())
+    }
+}
+
+/// 加载配置组内的所有配置并监听变化
+pub(crate) fn watch_group<S>(source: &S, group: ConfigGroup) -> Result<ConfigGroupHandle>
+where
+    S: ConfigSource + ?Sized,
+{
+    if group.members.is_empty() {
+        return Err(anyhow!("配置组不能为空"));
+    }
+
+    // 加载所有初始配置，任意一个失败则整体失败
+    let mut keys = Vec::new();
+    let mut members = Vec::new();
+    let mut values = Vec::new();
+    for spec in &group.members {
+        if keys.contains(&spec.key) {
+            return Err(anyhow!("配置组中存在重复的 key: {}", spec.key));
+        }
+        let value = source.load(&spec.key, spec.format.as_deref())?;
+        members.push((spec.build)(value.clone())?);
+        keys.push(spec.key.clone());
+        values.push(value);
+    }
+
+    let state = Arc::new(GroupState {
+        keys,
+        members,
+        applied: Mutex::new(values.iter().map(|v| v.as_value().clone()).collect()),
+        latest: Mutex::new(values),
+        generation: AtomicU64::new(0),
+        lock: RwLock::new(()),
+        debounce: group.debounce,
+        on_error: group.on_error,
+    });
+
+    for (index, spec) in group.members.iter().enumerate() {
+        let state_clone = state.clone();
+        source.watch(&spec.key, spec.format.as_deref(), Box::new(move |change| match change {
+            ConfigChange::Updated(value) => state_clone.schedule(index, value),
+            ConfigChange::Error(msg) => {
+                state_clone.report_error(index, anyhow!("监听配置失败: {}", msg))
+            }
+            // 配置删除时保留当前对象
+            ConfigChange::Deleted => {}
+        }))?;
+    }
+
+    Ok(ConfigGroupHandle { state })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+    use std::sync::atomic::AtomicBool;
+
+    type Handler = Box<dyn Fn(ConfigChange) + Send + Sync>;
+
+    /// 内存配置源，通过 `set` 手动触发变化
+    #[derive(Default)]
+    struct MemorySource {
+        values: RwLock<HashMap<String, JsonValue>>,
+        handlers: RwLock<HashMap<String, Vec<Handler>>>,
+    }
+
+    impl MemorySource {
+        fn set(&self, key: &str, value: JsonValue) {
+            self.values.write().unwrap().insert(key.to_string(), value.clone());
+            if let Some(handlers) = self.handlers.read().unwrap().get(key) {
+                for handler in handlers {
+                    handler(ConfigChange::Updated(ConfigValue::new(value.clone())));
+                }
+            }
+        }
+    }
+
+    impl ConfigSource for MemorySource {
+        fn load(&self, key: &str, _format: Option<&str>) -> Result<ConfigValue> {
+            self.values
+                .read()
+                .unwrap()
+                .get(key)
+                .cloned()
+                .map(ConfigValue::new)
+                .ok_or_else(|| anyhow!("not found: {}", key))
+        }
+
+        fn watch(
+            &self,
+            key: &str,
+            _format: Option<&str>,
+            handler: Box<dyn Fn(ConfigChange) + Send + Sync + 'static>,
+        ) -> Result<()> {
+            self.handlers
+                .write()
+                .unwrap()
+                .entry(key.to_string())
+                .or_default()
+                .push(handler);
+            Ok(())
+        }
+    }
+
+    #[derive(Debug, Clone, Deserialize)]
+    struct ServiceConfig {
+        name: String,
+        #[serde(default)]
+        reject: bool,
+        /// 重载前等待的毫秒数，用于观察重载中的状态
+        #[serde(default)]
+        delay_ms: u64,
+    }
+
+    struct Service {
+        name: String,
+        reloads: usize,
+    }
+
+    impl From<ServiceConfig> for Service {
+        fn from(config: ServiceConfig) -> Self {
+            Self {
+                name: config.name,
+                reloads: 0,
+            }
+        }
+    }
+
+    impl ConfigReloader<ServiceConfig> for Service {
+        fn reload_config(&mut self, config: ServiceConfig) -> Result<()> {
+            thread::sleep(Duration::from_millis(config.delay_ms));
+            if config.reject {
+                return Err(anyhow!("rejected {}", config.name));
+            }
+            self.name = config.name;
+            self.reloads += 1;
+            Ok(())
+        }
+    }
+
+    struct Other;
+
+    impl From<ServiceConfig> for Other {
+        fn from(_: ServiceConfig) -> Self {
+            Other
+        }
+    }
+
+    impl ConfigReloader<ServiceConfig> for Other {
+        fn reload_config(&mut self, _: ServiceConfig) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn create_group(source: &MemorySource) -> ConfigGroupHandle {
+        source.set("database", serde_json::json!({"name": "db-v1"}));
+        source.set("cache", serde_json::json!({"name": "cache-v1"}));
+
+        watch_group(
+            source,
+            ConfigGroup::new()
+                .add::<Service, ServiceConfig>("database", None)
+                .add::<Service, ServiceConfig>("cache", None)
+                .debounce(Duration::from_millis(50)),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_config_group_reload_together() {
+        let source = MemorySource::default();
+        let group = create_group(&source);
+        let database = group.get::<Service>("database").unwrap();
+        let cache = group.get::<Service>("cache").unwrap();
+
+        source.set("database", serde_json::json!({"name": "db-v2"}));
+        source.set("cache", serde_json::json!({"name": "cache-v2"}));
+
+        // 时间窗口内不重载
+        assert_eq!(database.current().name, "db-v1");
+
+        thread::sleep(Duration::from_millis(200));
+        assert_eq!(database.current().name, "db-v2");
+        assert_eq!(cache.current().name, "cache-v2");
+        assert_eq!(database.version(), 1);
+        assert_eq!(cache.version(), 1);
+    }
+
+    #[test]
+    fn test_config_group_rollback() {
+        let source = MemorySource::default();
+        let group = create_group(&source);
+        let database = group.get::<Service>("database").unwrap();
+        let cache = group.get::<Service>("cache").unwrap();
+
+        // cache 重载失败，已应用的 database 回滚
+        source.set("database", serde_json::json!({"name": "db-v2"}));
+        source.set("cache", serde_json::json!({"name": "cache-v2", "reject": true}));
+        thread::sleep(Duration::from_millis(200));
+
+        assert_eq!(database.current().name, "db-v1");
+        assert_eq!(database.current().reloads, 2);
+        assert_eq!(cache.current().name, "cache-v1");
+        assert_eq!(database.version(), 0);
+        assert!(database.last_reload_error().unwrap().to_string().contains("cache-v2"));
+        assert!(cache.last_reload_error().is_some());
+
+        // 修复后整体生效
+        source.set("cache", serde_json::json!({"name": "cache-v3"}));
+        thread::sleep(Duration::from_millis(200));
+
+        assert_eq!(database.current().name, "db-v2");
+        assert_eq!(cache.current().name, "cache-v3");
+        assert_eq!(database.version(), 1);
+        assert!(database.last_reload_error().is_none());
+    }
+
+    #[test]
+    fn test_config_group_read_consistent() {
+        let source = MemorySource::default();
+        let group = create_group(&source);
+
+        // database 先应用，cache 等待后失败，database 随后回滚
+        source.set("database", serde_json::json!({"name": "db-v2"}));
+        source.set(
+            "cache",
+            serde_json::json!({"name": "cache-v2", "reject": true, "delay_ms": 200}),
+        );
+
+        let done = Arc::new(AtomicBool::new(false));
+        let done_clone = done.clone();
+        let reader = thread::spawn({
+            let group = group.clone();
+            move || {
+                let mut reads = 0;
+                while !done_clone.load(Ordering::SeqCst) {
+                    {
+                        let guard = group.read();
+                        let database = guard.current::<Service>("database").unwrap();
+                        let cache = guard.current::<Service>("cache").unwrap();
+                        assert_eq!(database.name, "db-v1");
+                        assert_eq!(cache.name, "cache-v1");
+                    }
+                    reads += 1;
+                    thread::sleep(Duration::from_millis(1));
+                }
+                reads
+            }
+        });
+
+        thread::sleep(Duration::from_millis(400));
+        done.store(true, Ordering::SeqCst);
+        assert!(reader.join().unwrap() > 0);
+
+        // 回滚后 database 重载过两次（应用和回滚），通过组读取仍是 v1
+        let database = group.get::<Service>("database").unwrap();
+        assert_eq!(database.current().reloads, 2);
+        assert!(group.read().current::<Other>("database").is_err());
+    }
+
+    #[test]
+    fn test_config_group_parse_error() {
+        let source = MemorySource::default();
+        let errors = Arc::new(RwLock::new(Vec::new()));
+        let errors_clone = errors.clone();
+
+        source.set("database", serde_json::json!({"name": "db-v1"}));
+        source.set("cache", serde_json::json!({"name": "cache-v1"}));
+        let group = watch_group(
+            &source,
+            ConfigGroup::new()
+                .add::<Service, ServiceConfig>("database", None)
+                .add::<Service, ServiceConfig>("cache", None)
+                .debounce(Duration::from_millis(50))
+                .on_error(Arc::new(move |key, _| {
+                    errors_clone.write().unwrap().push(key.to_string());
+                })),
+        )
+        .unwrap();
+        let database = group.get::<Service>("database").unwrap();
+
+        // 解析失败时不调用任何 reload_config
+        source.set("database", serde_json::json!({"name": "db-v2"}));
+        source.set("cache", serde_json::json!({"name": 1}));
+        thread::sleep(Duration::from_millis(200));
+
+        assert_eq!(database.current().name, "db-v1");
+        assert_eq!(database.current().reloads, 0);
+        assert_eq!(*errors.read().unwrap(), vec!["cache".to_string()]);
+    }
+
+    #[test]
+    fn test_config_group_get() {
+        let source = MemorySource::default();
+        let group = create_group(&source);
+
+        assert_eq!(group.keys(), &["database".to_string(), "cache".to_string()]);
+        assert!(group.get::<Service>("missing").is_err());
+        assert!(group.get::<Other>("database").is_err());
+    }
+
+    #[test]
+    fn test_config_group_invalid() {
+        let source = MemorySource::default();
+        source.set("database", serde_json::json!({"name": "db"}));
+
+        assert!(watch_group(&source, ConfigGroup::new()).is_err());
+        assert!(watch_group(
+            &source,
+            ConfigGroup::new()
+                .add::<Service, ServiceConfig>("database", None)
+                .add::<Service, ServiceConfig>("database", None),
+        )
+        .is_err());
+        assert!(watch_group(
+            &source,
+            ConfigGroup::new().add::<Service, ServiceConfig>("missing", None),
+        )
+        .is_err());
+    }
+}
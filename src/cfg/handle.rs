@@ -0,0 +1,192 @@
+//! 热更新对象句柄
+//!
+//! 包装 `create_with_watch` 创建的对象，提供版本号、最近一次重载错误和变更订阅
+
+use anyhow::Result;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use tokio::sync::watch;
+
+/// 句柄共享状态
+struct HandleState {
+    /// 成功应用的配置版本，初始配置为 0
+    version: AtomicU64,
+    /// 最近一次重载的错误，成功重载后清空
+    last_error: RwLock<Option<Arc<anyhow::Error>>>,
+    /// 版本变更通知
+    notifier: watch::Sender<u64>,
+}
+
+/// 热更新对象句柄
+///
+/// 由 `Configurable::create_with_watch` 返回，可在线程间克隆共享
+///
+/// # 示例
+/// ```ignore
+/// let service = source.create_with_watch::<DatabaseService, DatabaseConfig>("database.json", None)?;
+///
+/// // 读取当前对象
+/// println!("{}", service.current().connection_info());
+///
+/// // 异步等待配置变化
+/// let mut rx = service.subscribe();
+/// while rx.changed().await.is_ok() {
+///     println!("配置已更新到版本 {}", *rx.borrow());
+/// }
+/// ```
+pub struct ConfigHandle<T> {
+    inner: Arc<RwLock<T>>,
+    state: Arc<HandleState>,
+}
+
+impl<T> Clone for ConfigHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T> ConfigHandle<T> {
+    /// 使用初始对象创建句柄
+    pub fn new(instance: T) -> Self {
+        let (notifier, _) = watch::channel(0);
+        Self {
+            inner: Arc::new(RwLock::new(instance)),
+            state: Arc::new(HandleState {
+                version: AtomicU64::new(0),
+                last_error: RwLock::new(None),
+                notifier,
+            }),
+        }
+    }
+
+    /// 获取当前对象的读锁
+    ///
+    /// 锁被毒化时仍返回内部对象（重载失败不会修改对象状态）
+    pub fn current(&self) -> RwLockReadGuard<'_, T> {
+        self.inner.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 当前配置版本，初始为 0，每次成功重载加 1
+    pub fn version(&self) -> u64 {
+        self.state.version.load(Ordering::SeqCst)
+    }
+
+    /// 最近一次重载的错误
+    ///
+    /// - 最近一次重载被拒绝（解析、校验或 reload_config 失败）时返回错误
+    /// - 从未重载失败或最近一次重载成功时返回 None
+    pub fn last_reload_error(&self) -> Option<Arc<anyhow::Error>> {
+        self.state
+            .last_error
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// 订阅配置变更，接收成功重载后的版本号
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
+        self.state.notifier.subscribe()
+    }
+
+    /// 获取内部的 Arc<RwLock<T>>
+    pub fn as_lock(&self) -> &Arc<RwLock<T>> {
+        &self.inner
+    }
+
+    /// 在写锁中更新对象，成功后递增版本、清空错误并通知订阅者
+    pub(crate) fn apply<F>(&self, update: F) -> Result<()>
+    where
+        F: FnOnce(&mut T) -> Result<()>,
+    {
+        update(&mut self.write())?;
+        self.commit();
+        Ok(())
+    }
+
+    /// 获取当前对象的写锁，不修改版本
+    pub(crate) fn write(&self) -> RwLockWriteGuard<'_, T> {
+        self.inner.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 提交一次成功的重载：递增版本、清空错误并通知订阅者
+    pub(crate) fn commit(&self) {
+        let version = self.state.version.fetch_add(1, Ordering::SeqCst) + 1;
+        *self.state.last_error.write().unwrap_or_else(|e| e.into_inner()) = None;
+        self.state.notifier.send_replace(version);
+    }
+
+    /// 记录重载错误，不修改对象和版本
+    pub(crate) fn record_error(&self, error: anyhow::Error) -> Arc<anyhow::Error> {
+        let error = Arc::new(error);
+        self.set_error(error.clone());
+        error
+    }
+
+    /// 设置最近一次重载错误，用于多个句柄共享同一个错误
+    pub(crate) fn set_error(&self, error: Arc<anyhow::Error>) {
+        *self.state.last_error.write().unwrap_or_else(|e| e.into_inner()) = Some(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    #[test]
+    fn test_config_handle_apply() {
+        let handle = ConfigHandle::new(1);
+        assert_eq!(*handle.current(), 1);
+        assert_eq!(handle.version(), 0);
+        assert!(handle.last_reload_error().is_none());
+
+        handle
+            .apply(|v| {
+                *v = 2;
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(*handle.current(), 2);
+        assert_eq!(handle.version(), 1);
+
+        // 失败时保留旧值和版本
+        let err = handle.apply(|_| Err(anyhow!("rejected"))).unwrap_err();
+        assert_eq!(*handle.current(), 2);
+        assert_eq!(handle.version(), 1);
+        handle.record_error(err);
+        assert_eq!(handle.last_reload_error().unwrap().to_string(), "rejected");
+
+        // 成功后清空错误
+        handle
+            .apply(|v| {
+                *v = 3;
+                Ok(())
+            })
+            .unwrap();
+        assert!(handle.last_reload_error().is_none());
+        assert_eq!(handle.version(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_config_handle_subscribe() {
+        let handle = ConfigHandle::new(String::from("a"));
+        let mut rx = handle.subscribe();
+
+        let writer = handle.clone();
+        tokio::spawn(async move {
+            writer
+                .apply(|v| {
+                    *v = String::from("b");
+                    Ok(())
+                })
+                .unwrap();
+        });
+
+        rx.changed().await.unwrap();
+        assert_eq!(*rx.borrow(), 1);
+        assert_eq!(*handle.current(), "b");
+    }
+}
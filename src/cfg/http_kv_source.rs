@@ -0,0 +1,930 @@
+//! HTTP KV 配置中心源
+//!
+//! 支持从 Consul KV 和 etcd v3（JSON gateway）加载配置
+//! Consul 使用阻塞查询（blocking query），etcd 使用 watch 流监听配置变化
+
+use anyhow::{anyhow, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use smart_default::SmartDefault;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use super::serde_duration::{serde_as, HumanDur};
+use super::source::{ConfigChange, ConfigSource, ConfigValue};
+use crate::log::{Logger, LoggerConfig};
+use crate::{impl_box_from, impl_from};
+
+/// HTTP KV 配置源的配置
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, Serialize, SmartDefault, JsonSchema)]
+#[serde(default)]
+pub struct HttpKvSourceConfig {
+    /// 后端类型，支持 "consul" 和 "etcd"
+    #[default = "consul"]
+    pub backend: String,
+    /// 服务器地址，如 "http://localhost:8500"（Consul）或 "http://localhost:2379"（etcd）
+    pub server_url: String,
+    /// key 前缀，加载时拼接在 key 之前，如 "config/my-app/"
+    pub prefix: String,
+    /// ACL token（可选），Consul 通过 `X-Consul-Token` 传递，etcd 通过 `Authorization` 传递
+    pub token: Option<String>,
+    /// 长轮询等待时间，Consul 阻塞查询的 wait 参数，etcd watch 流的重连周期
+    #[serde_as(as = "HumanDur")]
+    #[default(Duration::from_secs(60))]
+    pub wait_time: Duration,
+    /// 请求失败后的重试间隔
+    #[serde_as(as = "HumanDur")]
+    #[default(Duration::from_secs(5))]
+    pub retry_interval: Duration,
+    /// Logger 配置（可选，不配置则使用全局默认 logger）
+    pub logger: Option<LoggerConfig>,
+}
+
+/// KV 后端类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Backend {
+    Consul,
+    Etcd,
+}
+
+impl Backend {
+    fn parse(backend: &str) -> Result<Self> {
+        match backend.to_lowercase().as_str() {
+            "consul" => Ok(Backend::Consul),
+            "etcd" => Ok(Backend::Etcd),
+            _ => Err(anyhow!("不支持的 KV 后端类型: {}", backend)),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Backend::Consul => "consul",
+            Backend::Etcd => "etcd",
+        }
+    }
+}
+
+/// 一次读取的结果
+#[derive(Debug, Clone, PartialEq)]
+struct KvEntry {
+    /// 原始值，None 表示 key 不存在
+    value: Option<String>,
+    /// 版本号（Consul 的 X-Consul-Index / etcd 的 revision）
+    index: u64,
+}
+
+/// etcd 响应头
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct EtcdHeader {
+    revision: String,
+}
+
+/// etcd KV
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct EtcdKv {
+    value: String,
+    mod_revision: String,
+}
+
+/// etcd range 响应
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct EtcdRangeResponse {
+    header: EtcdHeader,
+    kvs: Vec<EtcdKv>,
+}
+
+/// etcd watch 事件
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct EtcdEvent {
+    /// PUT 事件时 etcd 省略该字段，删除时为 "DELETE"
+    #[serde(rename = "type")]
+    event_type: String,
+    kv: EtcdKv,
+}
+
+/// etcd watch 响应
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct EtcdWatchResult {
+    header: EtcdHeader,
+    events: Vec<EtcdEvent>,
+    canceled: bool,
+}
+
+/// etcd watch 流中的一条消息
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct EtcdWatchMessage {
+    result: Option<EtcdWatchResult>,
+    error: Option<serde_json::Value>,
+}
+
+/// 配置变化回调
+type Handler = Box<dyn Fn(ConfigChange) + Send + Sync + 'static>;
+
+/// 单个 key 的监听状态
+struct KeyState {
+    /// 该 key 的 (handler, format) 列表
+    handlers: Vec<(Handler, Option<String>)>,
+    /// 当前原始值，用于对比变化，None 表示 key 不存在
+    current_value: Option<String>,
+    /// 当前版本号
+    index: u64,
+}
+
+/// KV 后端 HTTP 客户端
+#[derive(Clone)]
+struct KvClient {
+    backend: Backend,
+    server_url: String,
+    prefix: String,
+    token: Option<String>,
+    wait_time: Duration,
+    client: reqwest::blocking::Client,
+}
+
+impl KvClient {
+    /// 拼接前缀后的完整 key
+    fn full_key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+
+    /// 创建请求并附加 ACL token
+    fn request(&self, method: reqwest::Method, url: &str) -> reqwest::blocking::RequestBuilder {
+        let request = self.client.request(method, url);
+        match (&self.token, self.backend) {
+            (Some(token), Backend::Consul) => request.header("X-Consul-Token", token),
+            (Some(token), Backend::Etcd) => request.header("Authorization", token),
+            (None, _) => request,
+        }
+    }
+
+    /// 读取 key 的当前值
+    ///
+    /// Consul 传入 `index` 时发起阻塞查询，直到 key 变化或等待超时才返回
+    fn get(&self, key: &str, index: Option<u64>) -> Result<KvEntry> {
+        match self.backend {
+            Backend::Consul => self.consul_get(key, index),
+            Backend::Etcd => self.etcd_range(key),
+        }
+    }
+
+    fn consul_get(&self, key: &str, index: Option<u64>) -> Result<KvEntry> {
+        let url = format!("{}/v1/kv/{}", self.server_url, self.full_key(key));
+
+        let mut request = self.request(reqwest::Method::GET, &url).query(&[("raw", "")]);
+        if let Some(index) = index {
+            // Consul 会在 wait 基础上增加最多 wait/16 的随机抖动
+            request = request
+                .query(&[
+                    ("index", index.to_string()),
+                    ("wait", format!("{}s", self.wait_time.as_secs().max(1))),
+                ])
+                .timeout(self.wait_time + self.wait_time / 16 + Duration::from_secs(5));
+        }
+
+        let resp = request
+            .send()
+            .map_err(|e| anyhow!("请求 Consul 失败: {}", e))?;
+
+        let index = resp
+            .headers()
+            .get("X-Consul-Index")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(0);
+
+        match resp.status().as_u16() {
+            200 => Ok(KvEntry {
+                value: Some(resp.text().map_err(|e| anyhow!("读取 Consul 响应失败: {}", e))?),
+                index,
+            }),
+            404 => Ok(KvEntry { value: None, index }),
+            status => Err(anyhow!("Consul 返回错误状态: {}", status)),
+        }
+    }
+
+    fn etcd_range(&self, key: &str) -> Result<KvEntry> {
+        let url = format!("{}/v3/kv/range", self.server_url);
+        let body = serde_json::json!({ "key": BASE64.encode(self.full_key(key)) });
+
+        let resp = self
+            .request(reqwest::Method::POST, &url)
+            .json(&body)
+            .send()
+            .map_err(|e| anyhow!("请求 etcd 失败: {}", e))?;
+
+        if !resp.status().is_success() {
+            return Err(anyhow!("etcd 返回错误状态: {}", resp.status()));
+        }
+
+        let range: EtcdRangeResponse = resp
+            .json()
+            .map_err(|e| anyhow!("解析 etcd 响应失败: {}", e))?;
+
+        let value = match range.kvs.first() {
+            Some(kv) => Some(decode_etcd_value(&kv.value)?),
+            None => None,
+        };
+
+        Ok(KvEntry {
+            value,
+            index: parse_revision(&range.header.revision),
+        })
+    }
+
+    /// 从 `start_revision` 开始监听 etcd key 的变化，每个事件调用一次 `on_entry`
+    ///
+    /// watch 流在 wait_time 后由客户端断开，调用方需循环调用
+    /// watch 被取消（如版本已被压缩）时重新读取当前值
+    fn etcd_watch<F>(&self, key: &str, start_revision: u64, mut on_entry: F) -> Result<()>
+    where
+        F: FnMut(KvEntry),
+    {
+        let url = format!("{}/v3/watch", self.server_url);
+        let body = serde_json::json!({
+            "create_request": {
+                "key": BASE64.encode(self.full_key(key)),
+                "start_revision": start_revision.to_string(),
+            }
+        });
+
+        let resp = self
+            .request(reqwest::Method::POST, &url)
+            .json(&body)
+            .timeout(self.wait_time)
+            .send()
+            .map_err(|e| anyhow!("请求 etcd 失败: {}", e))?;
+
+        if !resp.status().is_success() {
+            return Err(anyhow!("etcd 返回错误状态: {}", resp.status()));
+        }
+
+        for line in BufReader::new(resp).lines() {
+            // 读取超时表示本轮 watch 结束
+            let Ok(line) = line else { break };
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let message: EtcdWatchMessage = serde_json::from_str(&line)
+                .map_err(|e| anyhow!("解析 etcd watch 响应失败: {}", e))?;
+            if let Some(error) = message.error {
+                return Err(anyhow!("etcd watch 失败: {}", error));
+            }
+            let Some(result) = message.result else { continue };
+
+            if result.canceled {
+                on_entry(self.etcd_range(key)?);
+                break;
+            }
+
+            for event in result.events {
+                let value = if event.event_type == "DELETE" {
+                    None
+                } else {
+                    Some(decode_etcd_value(&event.kv.value)?)
+                };
+                let index = match parse_revision(&event.kv.mod_revision) {
+                    0 => parse_revision(&result.header.revision),
+                    revision => revision,
+                };
+                on_entry(KvEntry { value, index });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// 解析 etcd 的 int64 字符串
+fn parse_revision(revision: &str) -> u64 {
+    revision.parse().unwrap_or(0)
+}
+
+/// 解码 etcd base64 编码的值
+fn decode_etcd_value(value: &str) -> Result<String> {
+    let bytes = BASE64
+        .decode(value)
+        .map_err(|e| anyhow!("解码 etcd 值失败: {}", e))?;
+    String::from_utf8(bytes).map_err(|e| anyhow!("etcd 值不是有效的 UTF-8: {}", e))
+}
+
+/// HTTP KV 配置中心源
+///
+/// 支持从 Consul KV 或 etcd v3 JSON gateway 加载配置并监听变化
+///
+/// # Key 格式
+/// - key 拼接 `prefix` 后作为后端中的完整 key，例如 prefix 为 `config/app/`，key 为 `database`，
+///   则读取 `config/app/database`
+/// - 值为配置字符串，按 `format` 参数解析，默认为 JSON
+///
+/// # 监听行为说明
+/// - `watch` 方法仅监听配置**变化**，不会在启动时立即触发回调
+/// - 每个 key 启动一个长轮询线程：Consul 使用带 `index` 的阻塞查询，etcd 使用 `/v3/watch` 流
+/// - 新值与当前值相同时不触发回调；key 被删除时触发 `ConfigChange::Deleted`
+///
+/// # 示例
+/// ```no_run
+/// use rustx::cfg::{ConfigSource, HttpKvSource, HttpKvSourceConfig};
+///
+/// let source = HttpKvSource::new(HttpKvSourceConfig {
+///     backend: "consul".to_string(),
+///     server_url: "http://localhost:8500".to_string(),
+///     prefix: "config/my-app/".to_string(),
+///     token: Some("acl-token".to_string()),
+///     ..Default::default()
+/// }).unwrap();
+///
+/// // 加载初始配置
+/// let config = source.load("database", Some("json")).unwrap();
+///
+/// // 监听后续变化
+/// source.watch("database", Some("json"), Box::new(|change| {
+///     // 仅在配置发生变化时才会触发
+/// })).unwrap();
+/// ```
+pub struct HttpKvSource {
+    /// HTTP 客户端
+    client: KvClient,
+    /// 存储每个 key 的监听状态
+    keys: Arc<Mutex<HashMap<String, Arc<Mutex<KeyState>>>>>,
+    /// 请求失败后的重试间隔
+    retry_interval: Duration,
+    /// Source drop 时通知监听线程退出
+    stopped: Arc<AtomicBool>,
+    /// Logger 实例
+    logger: Arc<Logger>,
+}
+
+impl HttpKvSource {
+    /// 创建 HTTP KV 配置源
+    ///
+    /// # 参数
+    /// - `config`: HTTP KV 配置源配置
+    pub fn new(config: HttpKvSourceConfig) -> Result<Self> {
+        let backend = Backend::parse(&config.backend)?;
+        let server_url = config.server_url.trim_end_matches('/').to_string();
+        if server_url.is_empty() {
+            return Err(anyhow!("server_url 不能为空"));
+        }
+
+        // 解析或创建 logger
+        let logger = match config.logger {
+            Some(logger_config) => Logger::resolve(logger_config)?,
+            None => crate::log::get_default(),
+        };
+
+        logger.info_sync(format!(
+            "[INIT] http_kv_source created - backend={} server={} prefix={}",
+            backend.name(),
+            server_url,
+            config.prefix
+        ))?;
+
+        Ok(Self {
+            client: KvClient {
+                backend,
+                server_url,
+                prefix: config.prefix,
+                token: config.token,
+                wait_time: config.wait_time,
+                client: reqwest::blocking::Client::builder()
+                    .timeout(Duration::from_secs(30))
+                    .build()?,
+            },
+            keys: Arc::new(Mutex::new(HashMap::new())),
+            retry_interval: config.retry_interval,
+            stopped: Arc::new(AtomicBool::new(false)),
+            logger,
+        })
+    }
+
+    /// 注册 handler，key 首次被监听时启动长轮询线程
+    fn register_handler(
+        &self,
+        key: &str,
+        handler: Handler,
+        format: Option<String>,
+    ) {
+        if let Some(state) = self.keys.lock().unwrap().get(key) {
+            state.lock().unwrap().handlers.push((handler, format));
+            return;
+        }
+
+        // 记录当前值和版本号，用于后续对比变化；请求可能阻塞较久，不持有 keys 锁
+        let initial = match self.client.get(key, None) {
+            Ok(entry) => entry,
+            Err(e) => {
+                let _ = self.logger.error_sync(format!(
+                    "[ERROR] watch initial_load_failed - key={} error={}",
+                    key, e
+                ));
+                KvEntry { value: None, index: 0 }
+            }
+        };
+
+        // 请求期间其他线程可能已经开始监听同一个 key
+        let state = match self.keys.lock().unwrap().entry(key.to_string()) {
+            Entry::Occupied(entry) => {
+                entry.get().lock().unwrap().handlers.push((handler, format));
+                return;
+            }
+            Entry::Vacant(entry) => {
+                let state = Arc::new(Mutex::new(KeyState {
+                    handlers: vec![(handler, format)],
+                    current_value: initial.value,
+                    index: initial.index,
+                }));
+                entry.insert(state.clone());
+                state
+            }
+        };
+
+        self.start_watch_thread_for_key(key.to_string(), state);
+    }
+
+    /// 为指定的 key 启动长轮询监听线程
+    fn start_watch_thread_for_key(&self, key: String, state: Arc<Mutex<KeyState>>) {
+        let client = self.client.clone();
+        let retry_interval = self.retry_interval;
+        let stopped = self.stopped.clone();
+        let logger = self.logger.clone();
+
+        thread::spawn(move || {
+            let _ = logger.info_sync(format!("[POLL] poll_thread started - key={}", key));
+
+            while !stopped.load(Ordering::SeqCst) {
+                let index = state.lock().unwrap().index;
+
+                let result = match client.backend {
+                    Backend::Consul => client
+                        .get(&key, Some(index))
+                        .map(|entry| Self::handle_entry(&key, &state, entry, &logger)),
+                    Backend::Etcd => client.etcd_watch(&key, index + 1, |entry| {
+                        Self::handle_entry(&key, &state, entry, &logger)
+                    }),
+                };
+
+                if let Err(e) = result {
+                    let _ = logger.error_sync(format!(
+                        "[ERROR] poll request_failed - key={} error={}",
+                        key, e
+                    ));
+                    // 网络错误或服务端错误，等待后重试
+                    thread::sleep(retry_interval);
+                }
+
+                // 短暂休眠，避免紧密循环
+                thread::sleep(Duration::from_millis(100));
+            }
+
+            let _ = logger.info_sync(format!("[POLL] poll_thread stopped - key={}", key));
+        });
+    }
+
+    /// 对比新值与当前值，分发变化给所有 handler
+    fn handle_entry(key: &str, state: &Arc<Mutex<KeyState>>, entry: KvEntry, logger: &Arc<Logger>) {
+        let mut state_guard = state.lock().unwrap();
+
+        // Consul 的 index 回退时需要从 0 重新开始阻塞查询
+        state_guard.index = if entry.index < state_guard.index && entry.index != 0 {
+            0
+        } else {
+            entry.index.max(state_guard.index)
+        };
+
+        if state_guard.current_value == entry.value {
+            return;
+        }
+        state_guard.current_value = entry.value.clone();
+
+        match entry.value {
+            None => {
+                let _ = logger.warn_sync(format!("[CHANGE] config deleted - key={}", key));
+                for (handler, _) in state_guard.handlers.iter() {
+                    handler(ConfigChange::Deleted);
+                }
+            }
+            Some(content) => {
+                let _ = logger.info_sync(format!("[CHANGE] config updated - key={}", key));
+                for (handler, format) in state_guard.handlers.iter() {
+                    match Self::parse_config_with_format(&content, format.as_deref()) {
+                        Ok(value) => handler(ConfigChange::Updated(ConfigValue::new(value))),
+                        Err(e) => {
+                            let format_name = Self::format_name(format.as_deref());
+                            let _ = logger.error_sync(format!(
+                                "[ERROR] config parse_failed - key={} format={} error={}",
+                                key, format_name, e
+                            ));
+                            handler(ConfigChange::Error(format!(
+                                "解析 {} 格式配置失败: {}",
+                                format_name, e
+                            )));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// 根据指定格式解析配置字符串
+    fn parse_config_with_format(content: &str, format: Option<&str>) -> Result<serde_json::Value> {
+        let format = format.unwrap_or("json");
+        match format.to_lowercase().as_str() {
+            "json" => Ok(serde_json::from_str(content)?),
+            "json5" => Ok(json5::from_str(content)?),
+            "yaml" => Ok(serde_yaml::from_str(content)?),
+            "toml" => Ok(toml::from_str(content)?),
+            _ => Err(anyhow!("不支持的配置格式: {}", format)),
+        }
+    }
+
+    /// 格式名称（用于错误提示）
+    fn format_name(format: Option<&str>) -> String {
+        format
+            .map(|s| s.to_uppercase())
+            .unwrap_or_else(|| "JSON".to_string())
+    }
+}
+
+impl Drop for HttpKvSource {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+}
+
+impl_from!(HttpKvSourceConfig => HttpKvSource, expect: "创建 HttpKvSource 失败");
+impl_box_from!(HttpKvSource => dyn ConfigSource);
+
+impl ConfigSource for HttpKvSource {
+    fn load(&self, key: &str, format: Option<&str>) -> Result<ConfigValue> {
+        let entry = self.client.get(key, None)?;
+        let content = entry
+            .value
+            .ok_or_else(|| anyhow!("配置 key 不存在: {}", key))?;
+
+        let value = match Self::parse_config_with_format(&content, format) {
+            Ok(v) => v,
+            Err(e) => {
+                let format_name = Self::format_name(format);
+                self.logger.error_sync(format!(
+                    "[ERROR] config parse_failed - key={} format={} error={}",
+                    key, format_name, e
+                ))?;
+                return Err(anyhow!("解析 {} 格式配置失败: {}", format_name, e));
+            }
+        };
+
+        self.logger.debug_sync(format!(
+            "[LOAD] config success - key={} index={} format={:?}",
+            key, entry.index, format
+        ))?;
+
+        Ok(ConfigValue::new(value))
+    }
+
+    fn watch(
+        &self,
+        key: &str,
+        format: Option<&str>,
+        handler: Box<dyn Fn(ConfigChange) + Send + Sync + 'static>,
+    ) -> Result<()> {
+        self.register_handler(key, handler, format.map(|s| s.to_string()));
+
+        self.logger.info_sync(format!(
+            "[WATCH] listener registered - key={} format={:?}",
+            key, format
+        ))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+    use std::sync::RwLock;
+    use std::time::Instant;
+
+    fn consul_source(server_url: &str, token: Option<&str>) -> HttpKvSource {
+        HttpKvSource::new(HttpKvSourceConfig {
+            backend: "consul".to_string(),
+            server_url: server_url.to_string(),
+            prefix: "config/app/".to_string(),
+            token: token.map(|s| s.to_string()),
+            retry_interval: Duration::from_millis(100),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn etcd_source(server_url: &str) -> HttpKvSource {
+        HttpKvSource::new(HttpKvSourceConfig {
+            backend: "etcd".to_string(),
+            server_url: server_url.to_string(),
+            prefix: "config/app/".to_string(),
+            retry_interval: Duration::from_millis(100),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn collect_changes(source: &HttpKvSource, key: &str) -> Arc<RwLock<Vec<ConfigChange>>> {
+        let changes = Arc::new(RwLock::new(Vec::new()));
+        let changes_clone = changes.clone();
+        source
+            .watch(key, None, Box::new(move |change| {
+                changes_clone.write().unwrap().push(change);
+            }))
+            .unwrap();
+        changes
+    }
+
+    // 等待收到的变更满足条件，最多等待 5 秒
+    fn wait_for_changes(
+        changes: &Arc<RwLock<Vec<ConfigChange>>>,
+        done: impl Fn(&[ConfigChange]) -> bool,
+    ) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !done(&changes.read().unwrap()) && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_http_kv_source_config_defaults() {
+        let config: HttpKvSourceConfig =
+            serde_json::from_str(r#"{"server_url": "http://localhost:8500", "wait_time": "10s"}"#)
+                .unwrap();
+
+        assert_eq!(config.backend, "consul");
+        assert_eq!(config.wait_time, Duration::from_secs(10));
+        assert_eq!(config.retry_interval, Duration::from_secs(5));
+        assert!(config.token.is_none());
+    }
+
+    #[test]
+    fn test_http_kv_source_invalid_backend() {
+        let result = HttpKvSource::new(HttpKvSourceConfig {
+            backend: "zookeeper".to_string(),
+            server_url: "http://localhost:2181".to_string(),
+            ..Default::default()
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_http_kv_source_consul_load() -> Result<()> {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("GET", "/v1/kv/config/app/database")
+            .match_query(Matcher::UrlEncoded("raw".into(), "".into()))
+            .match_header("X-Consul-Token", "secret")
+            .with_status(200)
+            .with_header("X-Consul-Index", "10")
+            .with_body(r#"{"host": "localhost", "port": 3306}"#)
+            .create();
+
+        let source = consul_source(&server.url(), Some("secret"));
+        let config = source.load("database", None)?;
+
+        mock.assert();
+        assert_eq!(config.as_value()["host"], "localhost");
+        assert_eq!(config.as_value()["port"], 3306);
+        Ok(())
+    }
+
+    #[test]
+    fn test_http_kv_source_consul_load_yaml_and_not_found() {
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", "/v1/kv/config/app/database")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_body("host: localhost\nport: 3306")
+            .create();
+        server
+            .mock("GET", "/v1/kv/config/app/missing")
+            .match_query(Matcher::Any)
+            .with_status(404)
+            .create();
+
+        let source = consul_source(&server.url(), None);
+        let config = source.load("database", Some("yaml")).unwrap();
+        assert_eq!(config.as_value()["port"], 3306);
+
+        let err = source.load("missing", None).unwrap_err();
+        assert!(err.to_string().contains("不存在"));
+
+        let err = source.load("database", Some("json")).unwrap_err();
+        assert!(err.to_string().contains("JSON"));
+    }
+
+    #[test]
+    fn test_http_kv_source_consul_watch() {
+        let mut server = mockito::Server::new();
+
+        // 初始读取
+        server
+            .mock("GET", "/v1/kv/config/app/database")
+            .match_query(Matcher::Exact("raw=".into()))
+            .with_header("X-Consul-Index", "10")
+            .with_body(r#"{"host": "a"}"#)
+            .expect(1)
+            .create();
+        // 阻塞查询返回新值
+        server
+            .mock("GET", "/v1/kv/config/app/database")
+            .match_query(Matcher::UrlEncoded("index".into(), "10".into()))
+            .with_header("X-Consul-Index", "11")
+            .with_body(r#"{"host": "b"}"#)
+            .create();
+        // 相同值不触发回调
+        server
+            .mock("GET", "/v1/kv/config/app/database")
+            .match_query(Matcher::UrlEncoded("index".into(), "11".into()))
+            .with_header("X-Consul-Index", "12")
+            .with_body(r#"{"host": "b"}"#)
+            .expect(1)
+            .create();
+        // key 被删除
+        server
+            .mock("GET", "/v1/kv/config/app/database")
+            .match_query(Matcher::UrlEncoded("index".into(), "12".into()))
+            .with_status(404)
+            .with_header("X-Consul-Index", "13")
+            .create();
+        server
+            .mock("GET", "/v1/kv/config/app/database")
+            .match_query(Matcher::UrlEncoded("index".into(), "13".into()))
+            .with_status(404)
+            .with_header("X-Consul-Index", "13")
+            .create();
+
+        let source = consul_source(&server.url(), None);
+        let changes = collect_changes(&source, "database");
+
+        wait_for_changes(&changes, |changes| {
+            changes.iter().any(|c| matches!(c, ConfigChange::Deleted))
+        });
+
+        let changes = changes.read().unwrap();
+        let updates: Vec<_> = changes
+            .iter()
+            .filter_map(|c| match c {
+                ConfigChange::Updated(v) => Some(v.as_value()["host"].clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(updates, vec![serde_json::json!("b")]);
+        assert!(matches!(changes.last(), Some(ConfigChange::Deleted)));
+        assert_eq!(
+            changes.iter().filter(|c| matches!(c, ConfigChange::Deleted)).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_http_kv_source_etcd_load() -> Result<()> {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/v3/kv/range")
+            .match_body(Matcher::Json(serde_json::json!({
+                "key": BASE64.encode("config/app/database")
+            })))
+            .with_body(
+                serde_json::json!({
+                    "header": {"revision": "5"},
+                    "kvs": [{
+                        "key": BASE64.encode("config/app/database"),
+                        "value": BASE64.encode(r#"{"host": "localhost"}"#),
+                        "mod_revision": "5"
+                    }],
+                    "count": "1"
+                })
+                .to_string(),
+            )
+            .create();
+        server
+            .mock("POST", "/v3/kv/range")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "key": BASE64.encode("config/app/missing")
+            })))
+            .with_body(r#"{"header": {"revision": "5"}}"#)
+            .create();
+
+        let source = etcd_source(&server.url());
+        let config = source.load("database", None)?;
+
+        mock.assert();
+        assert_eq!(config.as_value()["host"], "localhost");
+        assert!(source.load("missing", None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_http_kv_source_etcd_watch() {
+        let mut server = mockito::Server::new();
+        let key = BASE64.encode("config/app/database");
+
+        server
+            .mock("POST", "/v3/kv/range")
+            .with_body(
+                serde_json::json!({
+                    "header": {"revision": "5"},
+                    "kvs": [{"value": BASE64.encode(r#"{"host": "a"}"#), "mod_revision": "5"}]
+                })
+                .to_string(),
+            )
+            .create();
+        // 从 revision 6 开始监听，收到一次更新和一次删除
+        server
+            .mock("POST", "/v3/watch")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "create_request": {"key": key, "start_revision": "6"}
+            })))
+            .with_body(
+                [
+                    serde_json::json!({"result": {"header": {"revision": "5"}, "created": true}}),
+                    serde_json::json!({"result": {"header": {"revision": "7"}, "events": [
+                        {"kv": {"value": BASE64.encode(r#"{"host": "b"}"#), "mod_revision": "7"}}
+                    ]}}),
+                    serde_json::json!({"result": {"header": {"revision": "8"}, "events": [
+                        {"type": "DELETE", "kv": {"mod_revision": "8"}}
+                    ]}}),
+                ]
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            )
+            .create();
+        // 后续监听没有新事件
+        server
+            .mock("POST", "/v3/watch")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "create_request": {"start_revision": "9"}
+            })))
+            .with_body(r#"{"result": {"header": {"revision": "8"}, "created": true}}"#)
+            .create();
+
+        let source = etcd_source(&server.url());
+        let changes = collect_changes(&source, "database");
+
+        wait_for_changes(&changes, |changes| changes.len() >= 2);
+
+        let changes = changes.read().unwrap();
+        assert_eq!(changes.len(), 2);
+        match &changes[0] {
+            ConfigChange::Updated(v) => assert_eq!(v.as_value()["host"], "b"),
+            other => panic!("expected Updated, got {:?}", other),
+        }
+        assert!(matches!(changes[1], ConfigChange::Deleted));
+    }
+
+    #[test]
+    fn test_http_kv_source_watch_parse_error() {
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", "/v1/kv/config/app/database")
+            .match_query(Matcher::Exact("raw=".into()))
+            .with_header("X-Consul-Index", "1")
+            .with_body(r#"{"host": "a"}"#)
+            .create();
+        server
+            .mock("GET", "/v1/kv/config/app/database")
+            .match_query(Matcher::UrlEncoded("index".into(), "1".into()))
+            .with_header("X-Consul-Index", "2")
+            .with_body("not json")
+            .create();
+        server
+            .mock("GET", "/v1/kv/config/app/database")
+            .match_query(Matcher::UrlEncoded("index".into(), "2".into()))
+            .with_header("X-Consul-Index", "2")
+            .with_body("not json")
+            .create();
+
+        let source = consul_source(&server.url(), None);
+        let changes = collect_changes(&source, "database");
+
+        wait_for_changes(&changes, |changes| !changes.is_empty());
+
+        let changes = changes.read().unwrap();
+        assert_eq!(changes.len(), 1);
+        assert!(matches!(&changes[0], ConfigChange::Error(msg) if msg.contains("JSON")));
+    }
+}
@@ -0,0 +1,185 @@
+//! 命名实例容器
+//!
+//! 预先创建并注册命名实例，任意 TypeOptions 可以通过 `{"$instance": "name"}` 共享同一个实例
+
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use super::registry::create_shared_trait_from_type_options;
+use super::type_options::TypeOptions;
+
+// 命名实例容器：为每个 Trait 类型维护一组命名实例
+// 外层 HashMap 的 key 是 Trait 的 TypeId，内层 HashMap 的 key 是实例名称
+// 值实际存放的是 Arc<Trait>
+type InstanceMap = HashMap<String, Arc<dyn Any + Send + Sync>>;
+
+static INSTANCE_REGISTRY: Lazy<RwLock<HashMap<TypeId, InstanceMap>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// 注册命名实例
+///
+/// 注册后任意 TypeOptions 可以通过 `{"$instance": "name"}` 引用该实例，
+/// `create_shared_trait_from_type_options` 返回同一个 `Arc`。同名实例会被覆盖
+///
+/// # 示例
+/// ```ignore
+/// let redis: Arc<dyn SyncStore<String, String>> = Arc::from(create_trait_from_type_options(&opts)?);
+/// register_instance::<dyn SyncStore<String, String>>("shared-redis", redis)?;
+///
+/// // 配置中引用：{"store": {"$instance": "shared-redis"}}
+/// ```
+pub fn register_instance<Trait>(name: &str, instance: Arc<Trait>) -> Result<()>
+where
+    Trait: ?Sized + Send + Sync + 'static,
+{
+    let mut registry = INSTANCE_REGISTRY
+        .write()
+        .map_err(|_| anyhow!("Failed to acquire write lock"))?;
+    registry
+        .entry(TypeId::of::<Trait>())
+        .or_insert_with(HashMap::new)
+        .insert(name.to_string(), Arc::new(instance));
+    Ok(())
+}
+
+/// 根据 TypeOptions 创建实例并以指定名称注册，返回创建的实例
+///
+/// # 示例
+/// ```ignore
+/// let opts = TypeOptions::from_json(r#"{"type": "RedisStore", "options": {...}}"#)?;
+/// create_instance::<dyn SyncStore<String, String>>("shared-redis", &opts)?;
+/// ```
+pub fn create_instance<Trait>(name: &str, type_options: &TypeOptions) -> Result<Arc<Trait>>
+where
+    Trait: ?Sized + Send + Sync + 'static,
+{
+    let instance = create_shared_trait_from_type_options::<Trait>(type_options)?;
+    register_instance::<Trait>(name, instance.clone())?;
+    Ok(instance)
+}
+
+/// 获取命名实例
+pub fn get_instance<Trait>(name: &str) -> Option<Arc<Trait>>
+where
+    Trait: ?Sized + Send + Sync + 'static,
+{
+    let registry = INSTANCE_REGISTRY.read().unwrap_or_else(|e| e.into_inner());
+    registry
+        .get(&TypeId::of::<Trait>())
+        .and_then(|instances| instances.get(name))
+        .and_then(|instance| instance.downcast_ref::<Arc<Trait>>())
+        .cloned()
+}
+
+/// 移除命名实例，已经持有该实例的组件不受影响
+pub fn remove_instance<Trait>(name: &str) -> Option<Arc<Trait>>
+where
+    Trait: ?Sized + Send + Sync + 'static,
+{
+    let mut registry = INSTANCE_REGISTRY.write().unwrap_or_else(|e| e.into_inner());
+    registry
+        .get_mut(&TypeId::of::<Trait>())
+        .and_then(|instances| instances.remove(name))
+        .and_then(|instance| instance.downcast_ref::<Arc<Trait>>().cloned())
+}
+
+/// 列出指定 Trait 已注册的所有实例名称（按字典序）
+pub fn registered_instances<Trait>() -> Vec<String>
+where
+    Trait: ?Sized + 'static,
+{
+    let registry = INSTANCE_REGISTRY.read().unwrap_or_else(|e| e.into_inner());
+    let mut names: Vec<String> = registry
+        .get(&TypeId::of::<Trait>())
+        .map(|instances| instances.keys().cloned().collect())
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cfg::register_trait;
+    use serde::Deserialize;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    trait Counter: Send + Sync {
+        fn incr(&self) -> usize;
+    }
+
+    #[derive(Debug, Clone, Deserialize)]
+    struct CounterConfig {
+        start: usize,
+    }
+
+    struct AtomicCounter(AtomicUsize);
+
+    impl From<CounterConfig> for AtomicCounter {
+        fn from(config: CounterConfig) -> Self {
+            Self(AtomicUsize::new(config.start))
+        }
+    }
+
+    impl Counter for AtomicCounter {
+        fn incr(&self) -> usize {
+            self.0.fetch_add(1, Ordering::SeqCst) + 1
+        }
+    }
+
+    impl From<Box<AtomicCounter>> for Box<dyn Counter> {
+        fn from(c: Box<AtomicCounter>) -> Self {
+            c
+        }
+    }
+
+    #[test]
+    fn test_instance_shared_by_reference() -> Result<()> {
+        register_trait::<AtomicCounter, dyn Counter, CounterConfig>("AtomicCounter")?;
+
+        let opts = TypeOptions::from_json(r#"{"type": "AtomicCounter", "options": {"start": 10}}"#)?;
+        let created = create_instance::<dyn Counter>("shared-counter", &opts)?;
+
+        // 通过 $instance 引用得到同一个实例
+        let reference = TypeOptions::from_json(r#"{"$instance": "shared-counter"}"#)?;
+        let a = create_shared_trait_from_type_options::<dyn Counter>(&reference)?;
+        let b = create_shared_trait_from_type_options::<dyn Counter>(&reference)?;
+        assert!(Arc::ptr_eq(&a, &created));
+        assert_eq!(a.incr(), 11);
+        assert_eq!(b.incr(), 12);
+
+        // 普通形式每次创建新实例
+        let c = create_shared_trait_from_type_options::<dyn Counter>(&opts)?;
+        assert_eq!(c.incr(), 11);
+
+        assert!(registered_instances::<dyn Counter>().contains(&"shared-counter".to_string()));
+        assert!(get_instance::<dyn Counter>("shared-counter").is_some());
+
+        let removed = remove_instance::<dyn Counter>("shared-counter").unwrap();
+        assert!(Arc::ptr_eq(&removed, &created));
+        assert!(get_instance::<dyn Counter>("shared-counter").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_instance_not_found() {
+        let reference = TypeOptions::instance("missing-counter");
+        let err = create_shared_trait_from_type_options::<dyn Counter>(&reference)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("missing-counter"));
+        assert!(err.contains("Counter"));
+
+        // Box 形式无法共享实例
+        let err = crate::cfg::create_trait_from_type_options::<dyn Counter>(&reference)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("create_shared_trait_from_type_options"));
+    }
+}
@@ -0,0 +1,486 @@
+//! 合并配置源
+//!
+//! 将多个配置源按优先级组合，对同一个 key 的配置做深度合并
+//! 任意底层配置源变化时重新合并并通知监听者
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use serde_json::Value as JsonValue;
+use smart_default::SmartDefault;
+use std::sync::{Arc, Mutex};
+
+use super::registry::create_trait_from_type_options;
+use super::source::{ConfigChange, ConfigSource, ConfigValue};
+use super::type_options::TypeOptions;
+use crate::log::{Logger, LoggerConfig};
+use crate::{impl_box_from, impl_from};
+
+/// 合并配置源的配置
+#[derive(Debug, Clone, Deserialize, Serialize, SmartDefault, JsonSchema)]
+#[serde(default)]
+pub struct MergedSourceConfig {
+    /// 底层配置源列表，按优先级从低到高排列，后面的配置覆盖前面的配置
+    pub sources: Vec<TypeOptions>,
+    /// Logger 配置（可选，不配置则使用全局默认 logger）
+    pub logger: Option<LoggerConfig>,
+}
+
+/// 合并配置源
+///
+/// 对同一个 key，依次从每个底层配置源加载配置并深度合并：
+/// - 对象按字段递归合并，后面的配置源覆盖前面的配置源
+/// - 数组、字符串、数字等非对象值整体覆盖
+/// - 加载失败（如 key 不存在）的配置源会被跳过，全部失败时返回错误
+///
+/// 底层配置源需要已经注册到 registry（参考 `register_sources`）
+///
+/// # 示例
+/// ```no_run
+/// use rustx::cfg::{register_sources, ConfigSource, MergedSource, MergedSourceConfig, TypeOptions};
+///
+/// register_sources().unwrap();
+///
+/// // 默认配置来自文件，覆盖配置来自 Apollo
+/// let source = MergedSource::new(MergedSourceConfig {
+///     sources: vec![
+///         TypeOptions::from_json(r#"{"type": "FileSource", "options": {"base_path": "config"}}"#).unwrap(),
+///         TypeOptions::from_json(r#"{
+///             "type": "ApolloSource",
+///             "options": {"server_url": "http://localhost:8080", "app_id": "my-app"}
+///         }"#).unwrap(),
+///     ],
+///     logger: None,
+/// }).unwrap();
+///
+/// // 加载 config/application/database.json 并与 Apollo application 命名空间下的 database.json 合并
+/// let config = source.load("application/database.json", None).unwrap();
+/// ```
+pub struct MergedSource {
+    /// 底层配置源，按优先级从低到高排列
+    sources: Vec<Box<dyn ConfigSource>>,
+    /// Logger 实例
+    logger: Arc<Logger>,
+}
+
+impl MergedSource {
+    /// 创建合并配置源
+    ///
+    /// # 参数
+    /// - `config`: 合并配置源配置
+    pub fn new(config: MergedSourceConfig) -> Result<Self> {
+        if config.sources.is_empty() {
+            return Err(anyhow!("sources 不能为空"));
+        }
+
+        // 解析或创建 logger
+        let logger = match config.logger {
+            Some(logger_config) => Logger::resolve(logger_config)?,
+            None => crate::log::get_default(),
+        };
+
+        let sources = config
+            .sources
+            .iter()
+            .map(create_trait_from_type_options::<dyn ConfigSource>)
+            .collect::<Result<Vec<_>>>()?;
+
+        logger.info_sync(format!(
+            "[INIT] merged_source created - sources={}",
+            config
+                .sources
+                .iter()
+                .map(|s| s.type_name.as_str())
+                .collect::<Vec<_>>()
+                .join(",")
+        ))?;
+
+        Ok(Self { sources, logger })
+    }
+
+    /// 按顺序合并各配置源的值，跳过不存在的值
+    fn merge_values(values: &[Option<JsonValue>]) -> Option<JsonValue> {
+        values.iter().flatten().fold(None, |acc, value| match acc {
+            None => Some(value.clone()),
+            Some(mut merged) => {
+                merge_json(&mut merged, value);
+                Some(merged)
+            }
+        })
+    }
+}
+
+impl_from!(MergedSourceConfig => MergedSource, expect: "创建 MergedSource 失败");
+impl_box_from!(MergedSource => dyn ConfigSource);
+
+/// 将 `overlay` 深度合并到 `base` 中
+///
+/// 两边都是对象时按字段递归合并，否则使用 `overlay` 整体覆盖 `base`
+pub(crate) fn merge_json(base: &mut JsonValue, overlay: &JsonValue) {
+    match (base, overlay) {
+        (JsonValue::Object(base_obj), JsonValue::Object(overlay_obj)) => {
+            for (key, overlay_value) in overlay_obj {
+                match base_obj.get_mut(key) {
+                    Some(base_value) => merge_json(base_value, overlay_value),
+                    None => {
+                        base_obj.insert(key.clone(), overlay_value.clone());
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay.clone(),
+    }
+}
+
+impl ConfigSource for MergedSource {
+    fn load(&self, key: &str, format: Option<&str>) -> Result<ConfigValue> {
+        let mut values = Vec::with_capacity(self.sources.len());
+        let mut last_err = None;
+
+        for (index, source) in self.sources.iter().enumerate() {
+            match source.load(key, format) {
+                Ok(value) => values.push(Some(value.into_value())),
+                Err(e) => {
+                    // 记录跳过的配置源
+                    let _ = self.logger.debug_sync(format!(
+                        "[LOAD] merged source_skipped - key={} index={} error={}",
+                        key, index, e
+                    ));
+                    values.push(None);
+                    last_err = Some(e);
+                }
+            }
+        }
+
+        match Self::merge_values(&values) {
+            Some(merged) => Ok(ConfigValue::new(merged)),
+            None => Err(anyhow!(
+                "所有配置源加载失败: key={}, error={}",
+                key,
+                last_err.map(|e| e.to_string()).unwrap_or_default()
+            )),
+        }
+    }
+
+    fn watch(
+        &self,
+        key: &str,
+        format: Option<&str>,
+        handler: Box<dyn Fn(ConfigChange) + Send + Sync + 'static>,
+    ) -> Result<()> {
+        // 记录每个配置源的当前值，任意一个变化后重新合并
+        let values: Vec<Option<JsonValue>> = self
+            .sources
+            .iter()
+            .map(|source| source.load(key, format).ok().map(ConfigValue::into_value))
+            .collect();
+        let values = Arc::new(Mutex::new(values));
+        let handler: Arc<dyn Fn(ConfigChange) + Send + Sync> = Arc::from(handler);
+
+        let mut watched = 0;
+        let mut last_err = None;
+        for (index, source) in self.sources.iter().enumerate() {
+            let values = values.clone();
+            let handler = handler.clone();
+            let logger = self.logger.clone();
+            let key_owned = key.to_string();
+
+            let result = source.watch(
+                key,
+                format,
+                Box::new(move |change| {
+                    // 持有锁期间调用 handler，多个配置源并发变化时按合并的顺序通知，
+                    // 不会用较早的合并结果覆盖较新的结果
+                    let mut values = values.lock().unwrap();
+                    match change {
+                        ConfigChange::Updated(value) => values[index] = Some(value.into_value()),
+                        ConfigChange::Deleted => values[index] = None,
+                        ConfigChange::Error(msg) => {
+                            handler(ConfigChange::Error(msg));
+                            return;
+                        }
+                    }
+                    let merged = Self::merge_values(&values);
+
+                    // 记录合并结果变化
+                    let _ = logger.info_sync(format!(
+                        "[CHANGE] merged updated - key={} index={}",
+                        key_owned, index
+                    ));
+                    match merged {
+                        Some(value) => handler(ConfigChange::Updated(ConfigValue::new(value))),
+                        None => handler(ConfigChange::Deleted),
+                    }
+                }),
+            );
+
+            match result {
+                Ok(()) => watched += 1,
+                Err(e) => {
+                    // 记录监听失败的配置源
+                    let _ = self.logger.warn_sync(format!(
+                        "[WATCH] merged source_skipped - key={} index={} error={}",
+                        key, index, e
+                    ));
+                    last_err = Some(e);
+                }
+            }
+        }
+
+        if watched == 0 {
+            return Err(anyhow!(
+                "所有配置源监听失败: key={}, error={}",
+                key,
+                last_err.map(|e| e.to_string()).unwrap_or_default()
+            ));
+        }
+
+        // 记录注册监听器
+        self.logger.info_sync(format!(
+            "[WATCH] listener registered - key={} sources={}",
+            key, watched
+        ))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cfg::register_sources;
+    use serial_test::serial;
+    use std::fs;
+    use std::sync::RwLock;
+    use std::thread;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn file_source_options(dir: &TempDir) -> TypeOptions {
+        TypeOptions {
+            type_name: "FileSource".to_string(),
+            options: serde_json::json!({
+                "base_path": dir.path().to_string_lossy(),
+            }),
+        }
+    }
+
+    fn new_merged_source(dirs: &[&TempDir]) -> Result<MergedSource> {
+        register_sources()?;
+        MergedSource::new(MergedSourceConfig {
+            sources: dirs.iter().map(|dir| file_source_options(dir)).collect(),
+            logger: None,
+        })
+    }
+
+    #[test]
+    fn test_merge_json() {
+        let mut base = serde_json::json!({
+            "host": "localhost",
+            "port": 3306,
+            "pool": {"min": 1, "max": 10},
+            "tags": ["a", "b"]
+        });
+        merge_json(
+            &mut base,
+            &serde_json::json!({
+                "host": "db.prod",
+                "pool": {"max": 100},
+                "tags": ["c"]
+            }),
+        );
+
+        assert_eq!(
+            base,
+            serde_json::json!({
+                "host": "db.prod",
+                "port": 3306,
+                "pool": {"min": 1, "max": 100},
+                "tags": ["c"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_merged_source_empty_sources() {
+        let result = MergedSource::new(MergedSourceConfig::default());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_merged_source_load() -> Result<()> {
+        let defaults = TempDir::new()?;
+        let overrides = TempDir::new()?;
+        fs::write(
+            defaults.path().join("database.json"),
+            r#"{"host": "localhost", "port": 3306, "pool": {"min": 1, "max": 10}}"#,
+        )?;
+        fs::write(
+            overrides.path().join("database.json"),
+            r#"{"host": "db.prod", "pool": {"max": 100}}"#,
+        )?;
+
+        let source = new_merged_source(&[&defaults, &overrides])?;
+        let config = source.load("database.json", None)?;
+
+        assert_eq!(config.as_value()["host"], "db.prod");
+        assert_eq!(config.as_value()["port"], 3306);
+        assert_eq!(config.as_value()["pool"]["min"], 1);
+        assert_eq!(config.as_value()["pool"]["max"], 100);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merged_source_load_skip_missing() -> Result<()> {
+        let defaults = TempDir::new()?;
+        let overrides = TempDir::new()?;
+        fs::write(defaults.path().join("cache.json"), r#"{"ttl": 60}"#)?;
+
+        let source = new_merged_source(&[&defaults, &overrides])?;
+        let config = source.load("cache.json", None)?;
+        assert_eq!(config.as_value()["ttl"], 60);
+
+        // 所有配置源都不存在时返回错误
+        assert!(source.load("nonexistent.json", None).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merged_source_via_type_options() -> Result<()> {
+        let defaults = TempDir::new()?;
+        fs::write(defaults.path().join("app.json"), r#"{"name": "app"}"#)?;
+
+        register_sources()?;
+        let opts = TypeOptions {
+            type_name: "MergedSource".to_string(),
+            options: serde_json::json!({
+                "sources": [file_source_options(&defaults)]
+            }),
+        };
+        let source: Box<dyn ConfigSource> = create_trait_from_type_options(&opts)?;
+        assert_eq!(source.load("app.json", None)?.as_value()["name"], "app");
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_merged_source_watch() -> Result<()> {
+        let defaults = TempDir::new()?;
+        let overrides = TempDir::new()?;
+        fs::write(
+            defaults.path().join("server.json"),
+            r#"{"host": "0.0.0.0", "port": 8080}"#,
+        )?;
+        fs::write(overrides.path().join("server.json"), r#"{"port": 9090}"#)?;
+
+        let source = new_merged_source(&[&defaults, &overrides])?;
+
+        let changes = Arc::new(RwLock::new(Vec::new()));
+        let changes_clone = changes.clone();
+        source.watch(
+            "server.json",
+            None,
+            Box::new(move |change| {
+                changes_clone.write().unwrap().push(change);
+            }),
+        )?;
+
+        thread::sleep(Duration::from_millis(200));
+
+        // 修改低优先级配置，合并结果仍保留高优先级的覆盖值
+        fs::write(
+            defaults.path().join("server.json"),
+            r#"{"host": "127.0.0.1", "port": 8080}"#,
+        )?;
+
+        thread::sleep(Duration::from_millis(500));
+
+        let changes_vec = changes.read().unwrap();
+        let has_update = changes_vec.iter().any(|c| {
+            matches!(c, ConfigChange::Updated(config)
+                if config.as_value()["host"] == "127.0.0.1" && config.as_value()["port"] == 9090)
+        });
+        assert!(has_update, "应该收到合并后的配置更新通知");
+
+        Ok(())
+    }
+
+    type Handler = Arc<dyn Fn(ConfigChange) + Send + Sync>;
+
+    /// 手动触发变化的配置源
+    #[derive(Clone, Default)]
+    struct ManualSource {
+        handler: Arc<Mutex<Option<Handler>>>,
+    }
+
+    impl ManualSource {
+        fn update(&self, value: JsonValue) {
+            let handler = self.handler.lock().unwrap().clone().unwrap();
+            handler(ConfigChange::Updated(ConfigValue::new(value)));
+        }
+    }
+
+    impl ConfigSource for ManualSource {
+        fn load(&self, _key: &str, _format: Option<&str>) -> Result<ConfigValue> {
+            Ok(ConfigValue::new(serde_json::json!({})))
+        }
+
+        fn watch(
+            &self,
+            _key: &str,
+            _format: Option<&str>,
+            handler: Box<dyn Fn(ConfigChange) + Send + Sync + 'static>,
+        ) -> Result<()> {
+            *self.handler.lock().unwrap() = Some(Arc::from(handler));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_merged_source_watch_concurrent() -> Result<()> {
+        let sources = [ManualSource::default(), ManualSource::default()];
+        let source = MergedSource {
+            sources: sources
+                .iter()
+                .map(|s| Box::new(s.clone()) as Box<dyn ConfigSource>)
+                .collect(),
+            logger: crate::log::get_default(),
+        };
+
+        let latest = Arc::new(Mutex::new(JsonValue::Null));
+        let latest_clone = latest.clone();
+        source.watch(
+            "server.json",
+            None,
+            Box::new(move |change| {
+                if let ConfigChange::Updated(value) = change {
+                    thread::yield_now();
+                    *latest_clone.lock().unwrap() = value.into_value();
+                }
+            }),
+        )?;
+
+        // 两个配置源并发变化，最后通知的必须是最新的合并结果
+        let threads: Vec<_> = sources
+            .iter()
+            .zip(["a", "b"])
+            .map(|(s, field)| {
+                let s = s.clone();
+                thread::spawn(move || {
+                    for i in 0..200 {
+                        s.update(serde_json::json!({ field: i }));
+                    }
+                })
+            })
+            .collect();
+        for t in threads {
+            t.join().unwrap();
+        }
+
+        assert_eq!(*latest.lock().unwrap(), serde_json::json!({"a": 199, "b": 199}));
+        Ok(())
+    }
+}
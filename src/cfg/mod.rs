@@ -5,23 +5,53 @@
 // 模块声明
 pub mod apollo_source;
 pub mod configurable;
+pub mod env_source;
 pub mod file_source;
 pub mod global_source;
+pub mod group;
+pub mod handle;
+pub mod http_kv_source;
+pub mod instance;
 pub mod macros;
+pub mod merged_source;
 pub mod register;
 pub mod registry;
 pub mod reloader;
+pub mod schema;
 pub mod serde_duration;
+pub mod snapshot;
 pub mod source;
+pub mod tracked_source;
 pub mod type_options;
+pub mod validation;
 
 // 重新导出公共 API
 pub use apollo_source::{ApolloSource, ApolloSourceConfig};
-pub use configurable::Configurable;
+pub use configurable::{Configurable, ReloadErrorHandler};
+pub use env_source::{EnvSource, EnvSourceConfig};
 pub use file_source::{FileSource, FileSourceConfig};
-pub use global_source::{init, load, watch};
+pub use global_source::{dump, dump_with, init, load, loaded_keys, watch};
+pub use group::{ConfigGroup, ConfigGroupHandle, ConfigGroupReadGuard};
+pub use handle::ConfigHandle;
+pub use http_kv_source::{HttpKvSource, HttpKvSourceConfig};
+pub use merged_source::{MergedSource, MergedSourceConfig};
 pub use register::register_sources;
-pub use registry::{create_trait_from_type_options, register_trait};
+pub use instance::{
+    create_instance, get_instance, register_instance, registered_instances, remove_instance,
+};
+pub use registry::{
+    create_shared_trait_from_type_options, create_trait_from_type_options,
+    create_trait_from_type_options_async, duplicate_policy, register_trait, register_trait_async,
+    register_trait_fallible, register_trait_with_policy, registered_types, set_duplicate_policy,
+    set_strict_mode, strict_mode, DuplicatePolicy,
+};
 pub use reloader::ConfigReloader;
+pub use schema::{
+    config_schema, register_schema, register_trait_fallible_with_schema, register_trait_with_schema,
+    type_options_schema,
+};
+pub use snapshot::{ConfigDiff, ConfigSnapshot, Redactor};
 pub use source::{ConfigChange, ConfigSource, ConfigValue};
-pub use type_options::TypeOptions;
+pub use tracked_source::TrackedSource;
+pub use type_options::{TypeOptions, INSTANCE_TYPE_NAME};
+pub use validation::{FieldError, ValidationError};
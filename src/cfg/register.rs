@@ -4,9 +4,12 @@
 
 use anyhow::Result;
 
-use crate::cfg::register_trait;
+use crate::cfg::{register_trait_fallible_with_schema, register_trait_with_schema};
 
-use super::{ApolloSource, ApolloSourceConfig, ConfigSource, FileSource, FileSourceConfig};
+use super::{
+    ApolloSource, ApolloSourceConfig, ConfigSource, EnvSource, EnvSourceConfig, FileSource,
+    FileSourceConfig, HttpKvSource, HttpKvSourceConfig, MergedSource, MergedSourceConfig,
+};
 
 /// 注册所有基础 ConfigSource 实现
 ///
@@ -15,6 +18,9 @@ use super::{ApolloSource, ApolloSourceConfig, ConfigSource, FileSource, FileSour
 /// # 注册的类型
 /// - `FileSource` - 文件配置源
 /// - `ApolloSource` - Apollo 配置中心源
+/// - `EnvSource` - 环境变量配置源
+/// - `HttpKvSource` - Consul / etcd KV 配置源
+/// - `MergedSource` - 合并多个配置源的组合源
 ///
 /// # 示例
 /// ```ignore
@@ -34,8 +40,14 @@ use super::{ApolloSource, ApolloSourceConfig, ConfigSource, FileSource, FileSour
 /// let source: Box<dyn ConfigSource> = create_trait_from_type_options(&opts)?;
 /// ```
 pub fn register_sources() -> Result<()> {
-    register_trait::<FileSource, dyn ConfigSource, FileSourceConfig>("FileSource")?;
-    register_trait::<ApolloSource, dyn ConfigSource, ApolloSourceConfig>("ApolloSource")?;
+    register_trait_with_schema::<FileSource, dyn ConfigSource, FileSourceConfig>("FileSource")?;
+    register_trait_fallible_with_schema::<ApolloSource, dyn ConfigSource, ApolloSourceConfig, _>(
+        "ApolloSource",
+        ApolloSource::new,
+    )?;
+    register_trait_with_schema::<EnvSource, dyn ConfigSource, EnvSourceConfig>("EnvSource")?;
+    register_trait_with_schema::<HttpKvSource, dyn ConfigSource, HttpKvSourceConfig>("HttpKvSource")?;
+    register_trait_with_schema::<MergedSource, dyn ConfigSource, MergedSourceConfig>("MergedSource")?;
 
     Ok(())
 }
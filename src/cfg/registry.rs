@@ -1,13 +1,17 @@
 // 类型注册表和工厂函数
 
 use anyhow::{anyhow, Result};
+use futures::future::BoxFuture;
 use once_cell::sync::Lazy;
 use serde::de::DeserializeOwned;
 use serde_json::Value as JsonValue;
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::RwLock;
 
+use super::instance::{get_instance, registered_instances};
 use super::type_options::TypeOptions;
 
 use std::sync::Arc;
@@ -17,15 +21,125 @@ use std::sync::Arc;
 // 使用 Arc 以便在查找后快速克隆（只是增加引用计数）
 type TraitConstructor = Arc<dyn Fn(JsonValue) -> Result<Box<dyn Any + Send + Sync>> + Send + Sync>;
 
+// 异步构造函数类型，返回的 Future 产出 Box<dyn Any>（实际包含 Box<dyn Trait>）
+type AsyncTraitConstructor =
+    Arc<dyn Fn(JsonValue) -> BoxFuture<'static, Result<Box<dyn Any + Send + Sync>>> + Send + Sync>;
+
+// 注册表中的构造函数，同步和异步构造函数共用同一个类型名称空间
+#[derive(Clone)]
+enum Constructor {
+    Sync(TraitConstructor),
+    Async(AsyncTraitConstructor),
+}
+
 // Trait 注册表：为每个 Trait 类型维护一个独立的注册表
 // 外层 HashMap 的 key 是 Trait 的 TypeId，内层 HashMap 的 key 是类型名称
-static TRAIT_REGISTRY: Lazy<RwLock<HashMap<TypeId, HashMap<String, TraitConstructor>>>> =
+static TRAIT_REGISTRY: Lazy<RwLock<HashMap<TypeId, HashMap<String, Constructor>>>> =
     Lazy::new(|| RwLock::new(HashMap::new()));
 
+// register_trait 使用的重复注册策略
+static DUPLICATE_POLICY: Lazy<RwLock<DuplicatePolicy>> =
+    Lazy::new(|| RwLock::new(DuplicatePolicy::default()));
+
+/// 同一 Trait 下重复注册同名类型时的处理策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicatePolicy {
+    /// 覆盖已有的注册（默认）
+    #[default]
+    Overwrite,
+    /// 覆盖已有的注册并输出警告日志
+    Warn,
+    /// 保留已有的注册并返回错误
+    Error,
+}
+
+/// 设置 `register_trait` 使用的重复注册策略
+///
+/// 默认为 `DuplicatePolicy::Overwrite`，各模块的 `register_*` 函数可以重复调用
+pub fn set_duplicate_policy(policy: DuplicatePolicy) {
+    *DUPLICATE_POLICY.write().unwrap_or_else(|e| e.into_inner()) = policy;
+}
+
+/// 获取当前的重复注册策略
+pub fn duplicate_policy() -> DuplicatePolicy {
+    *DUPLICATE_POLICY.read().unwrap_or_else(|e| e.into_inner())
+}
+
+// 构造组件时是否拒绝 options 中的未知字段
+static STRICT_MODE: AtomicBool = AtomicBool::new(false);
+
+/// 设置严格模式
+///
+/// 配置结构体普遍使用 `#[serde(default)]`，拼写错误的字段（如 `max_file`）默认会被静默忽略。
+/// 启用严格模式后，构造组件时 `options` 中存在未定义的字段会返回错误，错误信息包含字段的完整路径
+///
+/// # 示例
+/// ```ignore
+/// set_strict_mode(true);
+/// // Unknown fields in options for type 'RollingFileAppender' of trait 'dyn LogAppender': [max_file]
+/// let appender: Result<Box<dyn LogAppender>> = create_trait_from_type_options(&type_options);
+/// ```
+pub fn set_strict_mode(strict: bool) {
+    STRICT_MODE.store(strict, Ordering::SeqCst);
+}
+
+/// 是否启用严格模式
+pub fn strict_mode() -> bool {
+    STRICT_MODE.load(Ordering::SeqCst)
+}
+
+// 将 options 解析为配置结构体，严格模式下拒绝未知字段
+fn deserialize_options<Config: DeserializeOwned>(
+    type_name: &str,
+    trait_name: &str,
+    value: JsonValue,
+    strict: bool,
+) -> Result<Config> {
+    if !strict {
+        return Ok(serde_json::from_value(value)?);
+    }
+
+    let mut unknown = Vec::new();
+    let config = serde_ignored::deserialize(value, |path| unknown.push(format_path(&path)))?;
+    if !unknown.is_empty() {
+        return Err(anyhow!(
+            "Unknown fields in options for type '{}' of trait '{}': [{}]",
+            type_name,
+            trait_name,
+            unknown.join(", ")
+        ));
+    }
+    Ok(config)
+}
+
+// 格式化字段路径，如 `aop.retry.max_times`、`sources.0.type`，忽略 Option 等包装层
+fn format_path(path: &serde_ignored::Path) -> String {
+    use serde_ignored::Path;
+
+    match path {
+        Path::Root => String::new(),
+        Path::Seq { parent, index } => join_path(format_path(parent), &index.to_string()),
+        Path::Map { parent, key } => join_path(format_path(parent), key),
+        Path::Some { parent } | Path::NewtypeStruct { parent } | Path::NewtypeVariant { parent } => {
+            format_path(parent)
+        }
+    }
+}
+
+fn join_path(parent: String, segment: &str) -> String {
+    if parent.is_empty() {
+        segment.to_string()
+    } else {
+        format!("{}.{}", parent, segment)
+    }
+}
+
 /// 为实现特定 Trait 的类型注册构造函数
 ///
 /// 这个方法允许你注册多个实现同一 Trait 的不同类型，并在运行时根据配置创建 trait object
 ///
+/// 重复注册同名类型时按 `set_duplicate_policy` 设置的策略处理
+///
 /// # 类型参数
 /// - `T`: 具体实现类型
 /// - `Trait`: 目标 trait（使用 `dyn Trait` 形式）
@@ -51,34 +165,194 @@ where
     T: From<Config>,
     Box<T>: Into<Box<Trait>>,
 {
-    let type_name = type_name.to_string();
-    let trait_id = TypeId::of::<Trait>();
+    register_trait_with_policy::<T, Trait, Config>(type_name, duplicate_policy())
+}
+
+/// 使用指定的重复注册策略注册构造函数
+///
+/// # 示例
+/// ```ignore
+/// // 插件注册时避免覆盖内置实现
+/// register_trait_with_policy::<MyCache, dyn Cache, MyCacheConfig>("redis", DuplicatePolicy::Error)?;
+/// ```
+pub fn register_trait_with_policy<T, Trait, Config>(
+    type_name: &str,
+    policy: DuplicatePolicy,
+) -> Result<()>
+where
+    T: Send + Sync + 'static,
+    Trait: ?Sized + Send + Sync + 'static,
+    Config: DeserializeOwned + Clone + Send + Sync + 'static,
+    T: From<Config>,
+    Box<T>: Into<Box<Trait>>,
+{
+    let constructor = sync_constructor::<T, Trait, Config, _>(type_name, |config| Ok(T::from(config)));
+    insert_constructor::<Trait>(type_name, Constructor::Sync(constructor), policy)
+}
+
+/// 注册可能失败的同步构造函数
+///
+/// 构造过程可能因配置错误、文件被占用等原因失败时使用，构造函数返回 `Result`，
+/// 失败时 `create_trait_from_type_options` 返回错误而不是 panic
+///
+/// 与 `register_trait` 共用同一个类型名称空间，重复注册同名类型时按 `set_duplicate_policy` 设置的策略处理
+///
+/// # 示例
+/// ```ignore
+/// register_trait_fallible::<DiskStore<K, V>, dyn SyncStore<K, V>, DiskStoreConfig, _>(
+///     "DiskStore",
+///     |config| Ok(DiskStore::new(config)?),
+/// )?;
+/// ```
+pub fn register_trait_fallible<T, Trait, Config, F>(type_name: &str, constructor: F) -> Result<()>
+where
+    T: Send + Sync + 'static,
+    Trait: ?Sized + Send + Sync + 'static,
+    Config: DeserializeOwned + 'static,
+    F: Fn(Config) -> Result<T> + Send + Sync + 'static,
+    Box<T>: Into<Box<Trait>>,
+{
+    let constructor = sync_constructor::<T, Trait, Config, F>(type_name, constructor);
+    insert_constructor::<Trait>(type_name, Constructor::Sync(constructor), duplicate_policy())
+}
 
-    let constructor: TraitConstructor = Arc::new(move |value| {
-        let config: Config = serde_json::from_value(value)?;
-        let instance = T::from(config);
-        let boxed_instance = Box::new(instance);
-        let trait_object: Box<Trait> = boxed_instance.into();
+// 包装同步构造函数：解析 options 后调用 constructor，并将结果装箱为 Box<dyn Trait>
+fn sync_constructor<T, Trait, Config, F>(type_name: &str, constructor: F) -> TraitConstructor
+where
+    T: Send + Sync + 'static,
+    Trait: ?Sized + Send + Sync + 'static,
+    Config: DeserializeOwned + 'static,
+    F: Fn(Config) -> Result<T> + Send + Sync + 'static,
+    Box<T>: Into<Box<Trait>>,
+{
+    let type_name_owned = type_name.to_string();
+    Arc::new(move |value| {
+        let config: Config = deserialize_options(
+            &type_name_owned,
+            std::any::type_name::<Trait>(),
+            value,
+            strict_mode(),
+        )?;
+        let instance = constructor(config)?;
+        let trait_object: Box<Trait> = Box::new(instance).into();
         // 将 Box<dyn Trait> 包装成 Box<dyn Any>
         Ok(Box::new(trait_object) as Box<dyn Any + Send + Sync>)
+    })
+}
+
+/// 注册异步构造函数
+///
+/// 构造过程需要 I/O（建立连接、鉴权、拉取初始配置）时使用，构造函数返回 `Result`，
+/// 配置错误以错误的形式返回而不是 panic。注册后通过 `create_trait_from_type_options_async` 创建
+///
+/// 与 `register_trait` 共用同一个类型名称空间，重复注册同名类型时按 `set_duplicate_policy` 设置的策略处理
+///
+/// # 示例
+/// ```ignore
+/// register_trait_async::<RedisCache, dyn Cache, RedisCacheConfig, _, _>("RedisCache", |config| async move {
+///     RedisCache::connect(config).await
+/// })?;
+///
+/// let cache: Box<dyn Cache> = create_trait_from_type_options_async(&type_options).await?;
+/// ```
+pub fn register_trait_async<T, Trait, Config, F, Fut>(type_name: &str, constructor: F) -> Result<()>
+where
+    T: Send + Sync + 'static,
+    Trait: ?Sized + Send + Sync + 'static,
+    Config: DeserializeOwned + Send + 'static,
+    F: Fn(Config) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<T>> + Send + 'static,
+    Box<T>: Into<Box<Trait>>,
+{
+    let type_name_owned = type_name.to_string();
+    let constructor: AsyncTraitConstructor = Arc::new(move |value| {
+        let config = deserialize_options::<Config>(
+            &type_name_owned,
+            std::any::type_name::<Trait>(),
+            value,
+            strict_mode(),
+        );
+        let future = config.map(&constructor);
+        Box::pin(async move {
+            let instance = future?.await?;
+            let trait_object: Box<Trait> = Box::new(instance).into();
+            Ok(Box::new(trait_object) as Box<dyn Any + Send + Sync>)
+        })
     });
 
-    let mut registry = TRAIT_REGISTRY
-        .write()
-        .map_err(|_| anyhow!("Failed to acquire write lock"))?;
-    registry
-        .entry(trait_id)
-        .or_insert_with(HashMap::new)
-        .insert(type_name, constructor);
+    insert_constructor::<Trait>(type_name, Constructor::Async(constructor), duplicate_policy())
+}
+
+// 按重复注册策略将构造函数写入注册表
+fn insert_constructor<Trait>(
+    type_name: &str,
+    constructor: Constructor,
+    policy: DuplicatePolicy,
+) -> Result<()>
+where
+    Trait: ?Sized + 'static,
+{
+    let trait_id = TypeId::of::<Trait>();
+    let trait_name = std::any::type_name::<Trait>();
+
+    let replaced = {
+        let mut registry = TRAIT_REGISTRY
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire write lock"))?;
+        let trait_registry = registry.entry(trait_id).or_insert_with(HashMap::new);
+
+        if policy == DuplicatePolicy::Error && trait_registry.contains_key(type_name) {
+            return Err(anyhow!(
+                "Type '{}' already registered for trait '{}'",
+                type_name,
+                trait_name
+            ));
+        }
+        trait_registry
+            .insert(type_name.to_string(), constructor)
+            .is_some()
+    };
+
+    // 在释放注册表锁后输出日志，logger 的创建本身也依赖注册表
+    if replaced && policy == DuplicatePolicy::Warn {
+        let _ = crate::log::get_default().warn_sync(format!(
+            "[REGISTRY] duplicate registration overwritten - trait={} type={}",
+            trait_name, type_name
+        ));
+    }
 
     Ok(())
 }
 
+/// 列出指定 Trait 已注册的所有类型名称（按字典序）
+///
+/// # 示例
+/// ```ignore
+/// register_sources()?;
+/// let types = registered_types::<dyn ConfigSource>();
+/// // ["ApolloSource", "EnvSource", "FileSource", ...]
+/// ```
+pub fn registered_types<Trait>() -> Vec<String>
+where
+    Trait: ?Sized + 'static,
+{
+    let registry = TRAIT_REGISTRY.read().unwrap_or_else(|e| e.into_inner());
+    let mut types: Vec<String> = registry
+        .get(&TypeId::of::<Trait>())
+        .map(|trait_registry| trait_registry.keys().cloned().collect())
+        .unwrap_or_default();
+    types.sort();
+    types
+}
+
 /// 根据 TypeOptions 创建 trait object
 ///
 /// # 类型参数
 /// - `Trait`: 目标 trait（使用 `dyn Trait` 形式）
 ///
+/// # 错误
+/// - 类型未注册时，错误信息包含 trait 名称和该 trait 下所有已注册的类型名称
+///
 /// # 示例
 /// ```ignore
 /// let type_options = TypeOptions::from_json(r#"{"type": "service-v1", "options": {...}}"#)?;
@@ -88,31 +362,90 @@ pub fn create_trait_from_type_options<Trait>(type_options: &TypeOptions) -> Resu
 where
     Trait: ?Sized + Send + Sync + 'static,
 {
+    // 步骤1: 查找构造函数（锁在此步骤后立即释放）
+    let constructor = match lookup_constructor::<Trait>(type_options)? {
+        Constructor::Sync(constructor) => constructor,
+        Constructor::Async(_) => {
+            return Err(anyhow!(
+                "Type '{}' registered with async constructor for trait '{}', use create_trait_from_type_options_async instead",
+                type_options.type_name,
+                std::any::type_name::<Trait>()
+            ))
+        }
+    };
+
+    // 步骤2: 在无锁状态下调用构造函数（可能包含耗时操作）
+    let any_box = constructor(type_options.options.clone())?;
+    downcast_trait_object::<Trait>(any_box)
+}
+
+/// 根据 TypeOptions 异步创建 trait object
+///
+/// 同时支持 `register_trait` 和 `register_trait_async` 注册的类型，构造失败时返回错误
+///
+/// # 示例
+/// ```ignore
+/// let type_options = TypeOptions::from_json(r#"{"type": "RedisCache", "options": {...}}"#)?;
+/// let cache: Box<dyn Cache> = create_trait_from_type_options_async(&type_options).await?;
+/// ```
+pub async fn create_trait_from_type_options_async<Trait>(type_options: &TypeOptions) -> Result<Box<Trait>>
+where
+    Trait: ?Sized + Send + Sync + 'static,
+{
+    let any_box = match lookup_constructor::<Trait>(type_options)? {
+        Constructor::Sync(constructor) => constructor(type_options.options.clone())?,
+        Constructor::Async(constructor) => constructor(type_options.options.clone()).await?,
+    };
+    downcast_trait_object::<Trait>(any_box)
+}
+
+// 在读锁中查找并克隆构造函数
+fn lookup_constructor<Trait>(type_options: &TypeOptions) -> Result<Constructor>
+where
+    Trait: ?Sized + 'static,
+{
+    if let Some(name) = type_options.instance_name() {
+        return Err(anyhow!(
+            "Instance reference '{}' for trait '{}' cannot be created as Box, use create_shared_trait_from_type_options instead",
+            name,
+            std::any::type_name::<Trait>()
+        ));
+    }
+
     let trait_id = TypeId::of::<Trait>();
 
-    // 步骤1: 在读锁中查找并克隆构造函数（锁在此步骤后立即释放）
     let constructor = {
         let registry_guard = TRAIT_REGISTRY.read()
             .map_err(|_| anyhow!("Failed to acquire read lock"))?;
 
-        let trait_registry = registry_guard
+        registry_guard
             .get(&trait_id)
-            .ok_or_else(|| anyhow!("No implementations registered for trait"))?;
-
-        trait_registry
-            .get(&type_options.type_name)
-            .ok_or_else(|| anyhow!(
-                "Type '{}' not registered for this trait",
-                type_options.type_name
-            ))?
-            .clone()
+            .and_then(|trait_registry| trait_registry.get(&type_options.type_name))
+            .cloned()
+            .ok_or_else(|| {
+                let mut available: Vec<&str> = registry_guard
+                    .get(&trait_id)
+                    .map(|trait_registry| trait_registry.keys().map(|k| k.as_str()).collect())
+                    .unwrap_or_default();
+                available.sort();
+                anyhow!(
+                    "Type '{}' not registered for trait '{}', available types: [{}]",
+                    type_options.type_name,
+                    std::any::type_name::<Trait>(),
+                    available.join(", ")
+                )
+            })?
         // registry_guard 在此自动释放
     };
 
-    // 步骤2: 在无锁状态下调用构造函数（可能包含耗时操作）
-    let any_box = constructor(type_options.options.clone())?;
+    Ok(constructor)
+}
 
-    // 从 Box<dyn Any> 中提取 Box<dyn Trait>
+// 从 Box<dyn Any> 中提取 Box<dyn Trait>
+fn downcast_trait_object<Trait>(any_box: Box<dyn Any + Send + Sync>) -> Result<Box<Trait>>
+where
+    Trait: ?Sized + 'static,
+{
     // 这里使用 downcast 将 Box<dyn Any> 转换回 Box<Box<dyn Trait>>
     any_box
         .downcast::<Box<Trait>>()
@@ -120,6 +453,36 @@ where
         .map_err(|_| anyhow!("Failed to downcast to target trait type"))
 }
 
+/// 根据 TypeOptions 创建可共享的 trait object
+///
+/// - `{"type": ..., "options": ...}`: 创建新实例
+/// - `{"$instance": "name"}`: 返回通过 `register_instance` / `create_instance` 注册的同一个实例
+///
+/// # 错误
+/// - 引用的实例未注册时，错误信息包含 trait 名称和该 trait 下所有已注册的实例名称
+///
+/// # 示例
+/// ```ignore
+/// let type_options = TypeOptions::from_json(r#"{"$instance": "shared-redis"}"#)?;
+/// let store: Arc<dyn SyncStore<String, String>> = create_shared_trait_from_type_options(&type_options)?;
+/// ```
+pub fn create_shared_trait_from_type_options<Trait>(type_options: &TypeOptions) -> Result<Arc<Trait>>
+where
+    Trait: ?Sized + Send + Sync + 'static,
+{
+    match type_options.instance_name() {
+        Some(name) => get_instance::<Trait>(name).ok_or_else(|| {
+            anyhow!(
+                "Instance '{}' not registered for trait '{}', available instances: [{}]",
+                name,
+                std::any::type_name::<Trait>(),
+                registered_instances::<Trait>().join(", ")
+            )
+        }),
+        None => create_trait_from_type_options::<Trait>(type_options).map(Arc::from),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -250,11 +613,117 @@ mod tests {
         if let Err(e) = result {
             let error_msg = e.to_string();
             assert!(error_msg.contains("not registered"));
+            assert!(error_msg.contains("TestTrait"));
+            assert!(error_msg.contains("test-impl-a"));
         }
 
         Ok(())
     }
 
+    #[test]
+    fn test_register_trait_unregistered_trait() {
+        trait EmptyTrait: Send + Sync {}
+
+        let type_options = TypeOptions {
+            type_name: "any".to_string(),
+            options: serde_json::json!({}),
+        };
+
+        let result: Result<Box<dyn EmptyTrait>> = create_trait_from_type_options(&type_options);
+        let error_msg = result.err().unwrap().to_string();
+        assert!(error_msg.contains("EmptyTrait"));
+        assert!(error_msg.contains("available types: []"));
+        assert!(registered_types::<dyn EmptyTrait>().is_empty());
+    }
+
+    #[test]
+    fn test_registered_types() -> Result<()> {
+        trait ListTrait: Send + Sync {}
+        impl ListTrait for ImplA {}
+        impl ListTrait for ImplB {}
+        impl From<Box<ImplA>> for Box<dyn ListTrait> {
+            fn from(v: Box<ImplA>) -> Self {
+                v
+            }
+        }
+        impl From<Box<ImplB>> for Box<dyn ListTrait> {
+            fn from(v: Box<ImplB>) -> Self {
+                v
+            }
+        }
+
+        register_trait::<ImplB, dyn ListTrait, ImplBConfig>("list-b")?;
+        register_trait::<ImplA, dyn ListTrait, ImplAConfig>("list-a")?;
+
+        assert_eq!(registered_types::<dyn ListTrait>(), vec!["list-a", "list-b"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_trait_duplicate_policy() -> Result<()> {
+        trait PolicyTrait: Send + Sync {
+            fn execute(&self) -> String;
+        }
+        impl PolicyTrait for ImplA {
+            fn execute(&self) -> String {
+                format!("ImplA: {}", self.message)
+            }
+        }
+        impl PolicyTrait for ImplB {
+            fn execute(&self) -> String {
+                format!("ImplB: {}", self.value)
+            }
+        }
+        impl From<Box<ImplA>> for Box<dyn PolicyTrait> {
+            fn from(v: Box<ImplA>) -> Self {
+                v
+            }
+        }
+        impl From<Box<ImplB>> for Box<dyn PolicyTrait> {
+            fn from(v: Box<ImplB>) -> Self {
+                v
+            }
+        }
+
+        register_trait_with_policy::<ImplA, dyn PolicyTrait, ImplAConfig>(
+            "dup",
+            DuplicatePolicy::Error,
+        )?;
+
+        // Error 策略保留已有注册
+        let err = register_trait_with_policy::<ImplB, dyn PolicyTrait, ImplBConfig>(
+            "dup",
+            DuplicatePolicy::Error,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("already registered"));
+        assert!(err.to_string().contains("PolicyTrait"));
+
+        let options = TypeOptions {
+            type_name: "dup".to_string(),
+            options: serde_json::json!({"message": "a", "value": 1}),
+        };
+        let obj: Box<dyn PolicyTrait> = create_trait_from_type_options(&options)?;
+        assert_eq!(obj.execute(), "ImplA: a");
+
+        // Warn / Overwrite 策略覆盖已有注册
+        register_trait_with_policy::<ImplB, dyn PolicyTrait, ImplBConfig>(
+            "dup",
+            DuplicatePolicy::Warn,
+        )?;
+        let obj: Box<dyn PolicyTrait> = create_trait_from_type_options(&options)?;
+        assert_eq!(obj.execute(), "ImplB: 1");
+
+        register_trait_with_policy::<ImplA, dyn PolicyTrait, ImplAConfig>(
+            "dup",
+            DuplicatePolicy::Overwrite,
+        )?;
+        let obj: Box<dyn PolicyTrait> = create_trait_from_type_options(&options)?;
+        assert_eq!(obj.execute(), "ImplA: a");
+
+        Ok(())
+    }
+
     #[test]
     fn test_register_trait_invalid_config() -> Result<()> {
         register_trait::<ImplA, dyn TestTrait, ImplAConfig>("test-impl-a2")?;
@@ -316,4 +785,151 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_register_trait_async() -> Result<()> {
+        // 异步构造函数：value 为负数时构造失败
+        register_trait_async::<ImplB, dyn TestTrait, ImplBConfig, _, _>(
+            "async-impl-b",
+            |config: ImplBConfig| async move {
+                tokio::task::yield_now().await;
+                if config.value < 0 {
+                    return Err(anyhow!("value must be non-negative, got {}", config.value));
+                }
+                Ok(ImplB::from(config))
+            },
+        )?;
+        register_trait::<ImplA, dyn TestTrait, ImplAConfig>("async-sync-impl-a")?;
+
+        let type_options = TypeOptions {
+            type_name: "async-impl-b".to_string(),
+            options: serde_json::json!({"value": 7}),
+        };
+        let trait_obj: Box<dyn TestTrait> =
+            create_trait_from_type_options_async(&type_options).await?;
+        assert_eq!(trait_obj.execute(), "ImplB: 7");
+        assert!(registered_types::<dyn TestTrait>().contains(&"async-impl-b".to_string()));
+
+        // 构造失败以错误返回
+        let invalid = TypeOptions {
+            type_name: "async-impl-b".to_string(),
+            options: serde_json::json!({"value": -1}),
+        };
+        let err = create_trait_from_type_options_async::<dyn TestTrait>(&invalid)
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("value must be non-negative"));
+
+        // 配置格式错误同样以错误返回
+        let malformed = TypeOptions {
+            type_name: "async-impl-b".to_string(),
+            options: serde_json::json!({"value": "x"}),
+        };
+        assert!(create_trait_from_type_options_async::<dyn TestTrait>(&malformed)
+            .await
+            .is_err());
+
+        // 同步接口无法创建异步注册的类型
+        let err = create_trait_from_type_options::<dyn TestTrait>(&type_options)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("create_trait_from_type_options_async"));
+
+        // 异步接口同样支持同步注册的类型
+        let sync_options = TypeOptions {
+            type_name: "async-sync-impl-a".to_string(),
+            options: serde_json::json!({"message": "hello"}),
+        };
+        let trait_obj: Box<dyn TestTrait> =
+            create_trait_from_type_options_async(&sync_options).await?;
+        assert_eq!(trait_obj.execute(), "ImplA: hello");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_trait_fallible() -> Result<()> {
+        register_trait_fallible::<ImplB, dyn TestTrait, ImplBConfig, _>(
+            "fallible-impl-b",
+            |config: ImplBConfig| {
+                if config.value < 0 {
+                    return Err(anyhow!("value must be non-negative, got {}", config.value));
+                }
+                Ok(ImplB::from(config))
+            },
+        )?;
+
+        let type_options = TypeOptions {
+            type_name: "fallible-impl-b".to_string(),
+            options: serde_json::json!({"value": 7}),
+        };
+        let trait_obj: Box<dyn TestTrait> = create_trait_from_type_options(&type_options)?;
+        assert_eq!(trait_obj.execute(), "ImplB: 7");
+
+        // 构造失败以错误返回，同步和异步接口都可以创建
+        let invalid = TypeOptions {
+            type_name: "fallible-impl-b".to_string(),
+            options: serde_json::json!({"value": -1}),
+        };
+        let err = create_trait_from_type_options::<dyn TestTrait>(&invalid)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("value must be non-negative"));
+        assert!(create_shared_trait_from_type_options::<dyn TestTrait>(&invalid).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_options_strict() -> Result<()> {
+        use crate::aop::tracing::GlobalTracingConfig;
+        use crate::log::appender::RollingFileAppenderConfig;
+
+        let options = serde_json::json!({"file_path": "app.log", "max_file": 3});
+
+        // 非严格模式下未知字段被忽略
+        let config: RollingFileAppenderConfig =
+            deserialize_options("RollingFileAppender", "dyn LogAppender", options.clone(), false)?;
+        assert_eq!(config.file_path, "app.log");
+
+        // 严格模式下返回错误，包含类型名称和字段路径
+        let err = deserialize_options::<RollingFileAppenderConfig>(
+            "RollingFileAppender",
+            "dyn LogAppender",
+            options,
+            true,
+        )
+        .err()
+        .unwrap()
+        .to_string();
+        assert_eq!(
+            err,
+            "Unknown fields in options for type 'RollingFileAppender' of trait 'dyn LogAppender': [max_file]"
+        );
+
+        // 嵌套字段报告完整路径
+        let options = serde_json::json!({
+            "service_nam": "svc",
+            "batch_processor": {"max_queue_siz": 10}
+        });
+        let err = deserialize_options::<GlobalTracingConfig>("tracing", "dyn Tracing", options, true)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("service_nam"), "{}", err);
+        assert!(err.contains("batch_processor.max_queue_siz"), "{}", err);
+
+        // 没有未知字段时正常解析
+        let config: RollingFileAppenderConfig = deserialize_options(
+            "RollingFileAppender",
+            "dyn LogAppender",
+            serde_json::json!({"file_path": "app.log", "max_files": 3}),
+            true,
+        )?;
+        assert_eq!(config.max_files, Some(3));
+
+        assert!(!strict_mode());
+        Ok(())
+    }
 }
@@ -0,0 +1,380 @@
+//! 组件配置的 JSON Schema
+//!
+//! 为注册的组件类型生成配置的 JSON Schema，以及按 `type` 区分的 TypeOptions `oneOf` Schema，
+//! 编辑器和 CI 可以据此在运行前校验配置文件
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use schemars::generate::SchemaSettings;
+use schemars::transform::{RecursiveTransform, Transform};
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value as JsonValue};
+use std::any::TypeId;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::RwLock;
+
+use super::registry::{register_trait, register_trait_fallible, registered_types};
+
+// 生成配置 Schema 的函数
+#[derive(Clone, Copy)]
+struct SchemaEntry {
+    // 生成独立的根 Schema
+    root: fn() -> Schema,
+    // 在共享的 SchemaGenerator 中生成子 Schema，定义写入 generator 的 $defs
+    subschema: fn(&mut SchemaGenerator) -> Schema,
+}
+
+// Schema 注册表：外层 HashMap 的 key 是 Trait 的 TypeId，内层 BTreeMap 的 key 是类型名称
+static SCHEMA_REGISTRY: Lazy<RwLock<HashMap<TypeId, BTreeMap<String, SchemaEntry>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+// 对象 Schema 不允许未声明的字段，拼错的字段名（如 max_file）在校验时报错；
+// 已经声明 additionalProperties（如 map 类型）或由多个 Schema 组合的不做修改
+fn deny_additional_properties(schema: &mut Schema) {
+    let Some(object) = schema.as_object_mut() else {
+        return;
+    };
+    let combined = ["allOf", "anyOf", "oneOf"]
+        .iter()
+        .any(|key| object.contains_key(*key));
+    if object.contains_key("properties") && !object.contains_key("additionalProperties") && !combined
+    {
+        object.insert("additionalProperties".to_string(), JsonValue::Bool(false));
+    }
+}
+
+fn schema_generator() -> SchemaGenerator {
+    SchemaSettings::default()
+        .with_transform(RecursiveTransform(deny_additional_properties))
+        .into_generator()
+}
+
+fn root_schema_for<Config: JsonSchema>() -> Schema {
+    schema_generator().into_root_schema_for::<Config>()
+}
+
+// 定义写入 $defs，由 take_definitions 处理；内联的 Schema 在这里处理
+fn subschema_for<Config: JsonSchema>(generator: &mut SchemaGenerator) -> Schema {
+    let mut schema = generator.subschema_for::<Config>();
+    RecursiveTransform(deny_additional_properties).transform(&mut schema);
+    schema
+}
+
+/// 注册类型的配置 Schema，同名类型的 Schema 会被覆盖
+///
+/// # 示例
+/// ```ignore
+/// register_schema::<dyn LogAppender, RollingFileAppenderConfig>("RollingFileAppender");
+/// ```
+pub fn register_schema<Trait, Config>(type_name: &str)
+where
+    Trait: ?Sized + 'static,
+    Config: JsonSchema,
+{
+    let entry = SchemaEntry {
+        root: root_schema_for::<Config>,
+        subschema: subschema_for::<Config>,
+    };
+    SCHEMA_REGISTRY
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(TypeId::of::<Trait>())
+        .or_default()
+        .insert(type_name.to_string(), entry);
+}
+
+/// 注册构造函数并同时注册配置 Schema
+///
+/// 等价于 `register_trait` 加 `register_schema`
+///
+/// # 示例
+/// ```ignore
+/// register_trait_with_schema::<RollingFileAppender, dyn LogAppender, RollingFileAppenderConfig>(
+///     "RollingFileAppender",
+/// )?;
+/// ```
+pub fn register_trait_with_schema<T, Trait, Config>(type_name: &str) -> Result<()>
+where
+    T: Send + Sync + 'static,
+    Trait: ?Sized + Send + Sync + 'static,
+    Config: DeserializeOwned + Clone + Send + Sync + JsonSchema + 'static,
+    T: From<Config>,
+    Box<T>: Into<Box<Trait>>,
+{
+    register_trait::<T, Trait, Config>(type_name)?;
+    register_schema::<Trait, Config>(type_name);
+    Ok(())
+}
+
+/// 注册可能失败的构造函数并同时注册配置 Schema
+///
+/// 等价于 `register_trait_fallible` 加 `register_schema`
+///
+/// # 示例
+/// ```ignore
+/// register_trait_fallible_with_schema::<RedisStore<K, V>, dyn Store<K, V>, RedisStoreConfig, _>(
+///     "RedisStore",
+///     |config| Ok(RedisStore::new(config)?),
+/// )?;
+/// ```
+pub fn register_trait_fallible_with_schema<T, Trait, Config, F>(
+    type_name: &str,
+    constructor: F,
+) -> Result<()>
+where
+    T: Send + Sync + 'static,
+    Trait: ?Sized + Send + Sync + 'static,
+    Config: DeserializeOwned + JsonSchema + 'static,
+    F: Fn(Config) -> Result<T> + Send + Sync + 'static,
+    Box<T>: Into<Box<Trait>>,
+{
+    register_trait_fallible::<T, Trait, Config, F>(type_name, constructor)?;
+    register_schema::<Trait, Config>(type_name);
+    Ok(())
+}
+
+/// 获取指定类型配置的 JSON Schema，未注册 Schema 时返回 None
+///
+/// # 示例
+/// ```ignore
+/// let schema = config_schema::<dyn LogAppender>("RollingFileAppender").unwrap();
+/// println!("{}", serde_json::to_string_pretty(&schema)?);
+/// ```
+pub fn config_schema<Trait>(type_name: &str) -> Option<JsonValue>
+where
+    Trait: ?Sized + 'static,
+{
+    let entry = SCHEMA_REGISTRY
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&TypeId::of::<Trait>())
+        .and_then(|entries| entries.get(type_name).copied())?;
+    Some((entry.root)().to_value())
+}
+
+/// 生成指定 Trait 的 TypeOptions JSON Schema
+///
+/// 每个已注册的类型对应 `oneOf` 中的一项，按 `type` 区分，`options` 使用该类型的配置 Schema；
+/// 通过 `register_trait` 注册但没有注册 Schema 的类型，`options` 不做限制。
+/// 另外包含 `{"$instance": "name"}` 引用命名实例的形式
+///
+/// # 示例
+/// ```ignore
+/// register_appenders()?;
+/// let schema = type_options_schema::<dyn LogAppender>();
+/// std::fs::write("appender.schema.json", serde_json::to_string_pretty(&schema)?)?;
+/// ```
+pub fn type_options_schema<Trait>() -> JsonValue
+where
+    Trait: ?Sized + 'static,
+{
+    let entries = SCHEMA_REGISTRY
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&TypeId::of::<Trait>())
+        .cloned()
+        .unwrap_or_default();
+
+    let mut type_names = registered_types::<Trait>();
+    type_names.extend(entries.keys().cloned());
+    type_names.sort();
+    type_names.dedup();
+
+    let mut generator = schema_generator();
+    let mut variants: Vec<JsonValue> = type_names
+        .iter()
+        .map(|type_name| {
+            let options = entries
+                .get(type_name)
+                .map(|entry| (entry.subschema)(&mut generator).to_value())
+                .unwrap_or_else(|| json!({}));
+            json!({
+                "title": type_name,
+                "type": "object",
+                "properties": {
+                    "type": {"const": type_name},
+                    "options": options
+                },
+                "required": ["type", "options"],
+                "additionalProperties": false
+            })
+        })
+        .collect();
+    variants.push(json!({
+        "title": "$instance",
+        "type": "object",
+        "properties": {
+            "$instance": {"type": "string"}
+        },
+        "required": ["$instance"],
+        "additionalProperties": false
+    }));
+
+    let mut schema = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": format!("TypeOptions<{}>", std::any::type_name::<Trait>()),
+        "oneOf": variants
+    });
+    let definitions = generator.take_definitions(true);
+    if !definitions.is_empty() {
+        schema["$defs"] = JsonValue::Object(definitions);
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log::appender::LogAppender;
+    use crate::log::register_appenders;
+
+    trait Plugin: Send + Sync {}
+
+    #[derive(Debug, Clone, serde::Deserialize, JsonSchema)]
+    struct PluginConfig {
+        #[allow(dead_code)]
+        name: String,
+    }
+
+    struct NamedPlugin;
+
+    impl From<PluginConfig> for NamedPlugin {
+        fn from(_: PluginConfig) -> Self {
+            Self
+        }
+    }
+
+    impl Plugin for NamedPlugin {}
+
+    impl From<Box<NamedPlugin>> for Box<dyn Plugin> {
+        fn from(p: Box<NamedPlugin>) -> Self {
+            p
+        }
+    }
+
+    #[test]
+    fn test_config_schema() -> Result<()> {
+        register_appenders()?;
+
+        let schema = config_schema::<dyn LogAppender>("RollingFileAppender").unwrap();
+        let properties = &schema["properties"];
+        assert!(properties.get("file_path").is_some());
+        assert!(properties.get("max_files").is_some());
+        assert_eq!(schema["additionalProperties"], json!(false));
+
+        // 字段名拼错的配置不能通过校验
+        let valid = json!({"file_path": "logs/app.log", "max_files": 3});
+        assert!(check_properties(&schema, &schema, &valid).is_ok());
+        let typo = json!({"file_path": "logs/app.log", "max_file": 3});
+        assert_eq!(
+            check_properties(&schema, &schema, &typo),
+            Err("max_file".to_string())
+        );
+
+        assert!(config_schema::<dyn LogAppender>("NotExist").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_type_options_schema_rejects_unknown_options() -> Result<()> {
+        register_appenders()?;
+
+        // 通过 $defs 引用的配置 Schema 同样不允许未声明的字段
+        let schema = type_options_schema::<dyn LogAppender>();
+        let valid = json!({
+            "type": "RollingFileAppender",
+            "options": {"file_path": "logs/app.log", "max_files": 3}
+        });
+        assert!(check_properties(&schema, &schema, &valid).is_ok());
+        let typo = json!({
+            "type": "RollingFileAppender",
+            "options": {"file_path": "logs/app.log", "max_file": 3}
+        });
+        assert!(check_properties(&schema, &schema, &typo).is_err());
+        Ok(())
+    }
+
+    // 只检查未声明的字段：解析 $ref，oneOf/anyOf 按 type 的 const 选择分支，
+    // 返回第一个不允许的字段名
+    fn check_properties(
+        root: &JsonValue,
+        schema: &JsonValue,
+        value: &JsonValue,
+    ) -> std::result::Result<(), String> {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name = reference.trim_start_matches("#/$defs/");
+            return check_properties(root, &root["$defs"][name], value);
+        }
+        for key in ["oneOf", "anyOf"] {
+            if let Some(variants) = schema[key].as_array() {
+                let variant = variants
+                    .iter()
+                    .find(|v| v["properties"]["type"]["const"] == value["type"])
+                    .ok_or_else(|| "type".to_string())?;
+                return check_properties(root, variant, value);
+            }
+        }
+        let Some(object) = value.as_object() else {
+            return Ok(());
+        };
+        for (key, field) in object {
+            match schema["properties"].get(key) {
+                Some(field_schema) => check_properties(root, field_schema, field)?,
+                None if schema["additionalProperties"] == json!(false) => return Err(key.clone()),
+                None => {}
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_type_options_schema() -> Result<()> {
+        register_trait_with_schema::<NamedPlugin, dyn Plugin, PluginConfig>("NamedPlugin")?;
+        register_trait::<NamedPlugin, dyn Plugin, PluginConfig>("UntypedPlugin")?;
+
+        let schema = type_options_schema::<dyn Plugin>();
+        let variants = schema["oneOf"].as_array().unwrap();
+        let titles: Vec<&str> = variants
+            .iter()
+            .map(|v| v["title"].as_str().unwrap())
+            .collect();
+        assert_eq!(titles, vec!["NamedPlugin", "UntypedPlugin", "$instance"]);
+
+        // 有 Schema 的类型通过 $defs 引用配置 Schema
+        assert_eq!(variants[0]["properties"]["type"]["const"], "NamedPlugin");
+        assert_eq!(
+            variants[0]["properties"]["options"]["$ref"],
+            "#/$defs/PluginConfig"
+        );
+        assert_eq!(
+            schema["$defs"]["PluginConfig"]["required"],
+            json!(["name"])
+        );
+
+        // 没有注册 Schema 的类型不限制 options
+        assert_eq!(variants[1]["properties"]["options"], json!({}));
+        Ok(())
+    }
+
+    #[test]
+    fn test_type_options_schema_nested() -> Result<()> {
+        register_appenders()?;
+
+        let schema = type_options_schema::<dyn LogAppender>();
+        let titles: Vec<&str> = schema["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v["title"].as_str().unwrap())
+            .collect();
+        assert!(titles.contains(&"ConsoleAppender"));
+        assert!(titles.contains(&"RollingFileAppender"));
+
+        // 嵌套的枚举类型生成在 $defs 中
+        let defs = schema["$defs"].as_object().unwrap();
+        assert!(defs.contains_key("RollingFileAppenderConfig"));
+        assert!(defs.contains_key("TimePolicy"));
+        Ok(())
+    }
+}
@@ -1,5 +1,8 @@
 use anyhow::{anyhow, Result};
+use schemars::{json_schema, Schema, SchemaGenerator};
 use serde::{Deserialize, Deserializer, Serializer};
+use serde_with::schemars_1::JsonSchemaAs;
+use std::borrow::Cow;
 use std::time::Duration;
 
 // 重新导出serde_with
@@ -29,6 +32,24 @@ impl<'de> DeserializeAs<'de, Duration> for HumanDur {
     }
 }
 
+impl JsonSchemaAs<Duration> for HumanDur {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        "HumanDur".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "pattern": "^([0-9.]+(ns|us|μs|ms|s|m|h|d))+$",
+            "examples": ["3s", "100ms", "1h30m"]
+        })
+    }
+}
+
 /// 解析时间字符串: "1h30m45s" -> Duration
 pub fn parse_duration(s: &str) -> Result<Duration> {
     let s = s.trim().to_lowercase();
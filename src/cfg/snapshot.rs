@@ -0,0 +1,243 @@
+//! 配置快照
+//!
+//! 导出当前生效的配置（敏感字段脱敏），并对比两个快照之间的差异
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+use smart_default::SmartDefault;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// 敏感字段脱敏规则
+///
+/// 字段名（不区分大小写）包含任意一个模式时，字段值被替换为 `mask`
+#[derive(Debug, Clone, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct Redactor {
+    /// 敏感字段名模式
+    #[default(vec!["password".to_string(), "secret".to_string(), "access_key".to_string()])]
+    pub patterns: Vec<String>,
+    /// 替换后的值
+    #[default = "******"]
+    pub mask: String,
+}
+
+impl Redactor {
+    /// 使用指定的模式创建脱敏规则
+    pub fn new<I, S>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            patterns: patterns.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// 字段名是否为敏感字段
+    pub fn is_sensitive(&self, field: &str) -> bool {
+        let field = field.to_lowercase();
+        self.patterns
+            .iter()
+            .any(|pattern| field.contains(&pattern.to_lowercase()))
+    }
+
+    /// 返回脱敏后的配置
+    pub fn redact(&self, value: &JsonValue) -> JsonValue {
+        match value {
+            JsonValue::Object(map) => JsonValue::Object(
+                map.iter()
+                    .map(|(k, v)| {
+                        let v = if self.is_sensitive(k) {
+                            JsonValue::String(self.mask.clone())
+                        } else {
+                            self.redact(v)
+                        };
+                        (k.clone(), v)
+                    })
+                    .collect::<Map<_, _>>(),
+            ),
+            JsonValue::Array(items) => {
+                JsonValue::Array(items.iter().map(|v| self.redact(v)).collect())
+            }
+            other => other.clone(),
+        }
+    }
+}
+
+/// 配置快照，key -> 当前生效的配置
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct ConfigSnapshot {
+    pub values: BTreeMap<String, JsonValue>,
+}
+
+impl ConfigSnapshot {
+    /// 对比两个快照，返回从 `self` 到 `other` 的所有字段变化
+    ///
+    /// 对象按字段递归对比，数组和标量整体对比
+    pub fn diff(&self, other: &ConfigSnapshot) -> Vec<ConfigDiff> {
+        let mut diffs = Vec::new();
+
+        for (key, old) in &self.values {
+            match other.values.get(key) {
+                Some(new) => diff_value(key, "", Some(old), Some(new), &mut diffs),
+                None => diff_value(key, "", Some(old), None, &mut diffs),
+            }
+        }
+        for (key, new) in &other.values {
+            if !self.values.contains_key(key) {
+                diff_value(key, "", None, Some(new), &mut diffs);
+            }
+        }
+
+        diffs
+    }
+
+    /// 序列化为格式化的 JSON 字符串
+    pub fn to_json_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self.values).unwrap_or_default()
+    }
+}
+
+impl fmt::Display for ConfigSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string(&self.values).unwrap_or_default())
+    }
+}
+
+/// 单个字段的配置变化
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ConfigDiff {
+    /// 配置键
+    pub key: String,
+    /// 字段路径，如 `pool.max`，整个配置变化时为空字符串
+    pub path: String,
+    /// 旧值，新增时为 None
+    pub old: Option<JsonValue>,
+    /// 新值，删除时为 None
+    pub new: Option<JsonValue>,
+}
+
+impl fmt::Display for ConfigDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let field = if self.path.is_empty() {
+            self.key.clone()
+        } else {
+            format!("{}.{}", self.key, self.path)
+        };
+        match (&self.old, &self.new) {
+            (Some(old), Some(new)) => write!(f, "~ {}: {} -> {}", field, old, new),
+            (None, Some(new)) => write!(f, "+ {}: {}", field, new),
+            (Some(old), None) => write!(f, "- {}: {}", field, old),
+            (None, None) => write!(f, "  {}", field),
+        }
+    }
+}
+
+/// 递归对比两个值
+fn diff_value(
+    key: &str,
+    path: &str,
+    old: Option<&JsonValue>,
+    new: Option<&JsonValue>,
+    diffs: &mut Vec<ConfigDiff>,
+) {
+    if let (Some(JsonValue::Object(old_map)), Some(JsonValue::Object(new_map))) = (old, new) {
+        let join = |field: &str| {
+            if path.is_empty() {
+                field.to_string()
+            } else {
+                format!("{}.{}", path, field)
+            }
+        };
+        for (field, old_value) in old_map {
+            diff_value(key, &join(field), Some(old_value), new_map.get(field), diffs);
+        }
+        for (field, new_value) in new_map {
+            if !old_map.contains_key(field) {
+                diff_value(key, &join(field), None, Some(new_value), diffs);
+            }
+        }
+        return;
+    }
+
+    if old != new {
+        diffs.push(ConfigDiff {
+            key: key.to_string(),
+            path: path.to_string(),
+            old: old.cloned(),
+            new: new.cloned(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_redactor_default() {
+        let redactor = Redactor::default();
+        let value = json!({
+            "host": "localhost",
+            "Password": "123",
+            "db_secret": "abc",
+            "oss": {"access_key_id": "ak", "endpoint": "oss.example.com"},
+            "users": [{"name": "a", "password": "p"}]
+        });
+
+        let redacted = redactor.redact(&value);
+        assert_eq!(redacted["host"], "localhost");
+        assert_eq!(redacted["Password"], "******");
+        assert_eq!(redacted["db_secret"], "******");
+        assert_eq!(redacted["oss"]["access_key_id"], "******");
+        assert_eq!(redacted["oss"]["endpoint"], "oss.example.com");
+        assert_eq!(redacted["users"][0]["password"], "******");
+        assert_eq!(redacted["users"][0]["name"], "a");
+    }
+
+    #[test]
+    fn test_redactor_custom_patterns() {
+        let redactor = Redactor::new(["token"]);
+        let redacted = redactor.redact(&json!({"api_token": "t", "password": "p"}));
+        assert_eq!(redacted["api_token"], "******");
+        assert_eq!(redacted["password"], "p");
+
+        let redactor: Redactor = serde_json::from_str(r#"{"mask": "<hidden>"}"#).unwrap();
+        assert_eq!(redactor.patterns.len(), 3);
+        assert_eq!(redactor.redact(&json!({"secret": 1}))["secret"], "<hidden>");
+    }
+
+    #[test]
+    fn test_snapshot_diff() {
+        let old = ConfigSnapshot {
+            values: BTreeMap::from([
+                ("database".to_string(), json!({"host": "a", "pool": {"max": 10, "min": 1}})),
+                ("cache".to_string(), json!({"ttl": 60})),
+            ]),
+        };
+        let new = ConfigSnapshot {
+            values: BTreeMap::from([
+                ("database".to_string(), json!({"host": "b", "pool": {"max": 10}, "tags": ["x"]})),
+                ("queue".to_string(), json!("kafka")),
+            ]),
+        };
+
+        let diffs = old.diff(&new);
+        let lines: Vec<String> = diffs.iter().map(|d| d.to_string()).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "- cache: {\"ttl\":60}",
+                "~ database.host: \"a\" -> \"b\"",
+                "- database.pool.min: 1",
+                "+ database.tags: [\"x\"]",
+                "+ queue: \"kafka\"",
+            ]
+        );
+
+        assert!(old.diff(&old).is_empty());
+    }
+}
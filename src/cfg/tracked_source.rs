@@ -0,0 +1,182 @@
+//! 记录配置的配置源
+//!
+//! 包装任意 ConfigSource，记录加载和监听过的 key 及其当前生效的配置，用于导出配置快照
+
+use anyhow::Result;
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use super::snapshot::{ConfigSnapshot, Redactor};
+use super::source::{ConfigChange, ConfigSource, ConfigValue};
+
+/// 单个 key 的记录
+#[derive(Debug, Clone, Default)]
+struct TrackedEntry {
+    /// 当前生效的配置，仅监听且尚未加载或已被删除时为 None
+    value: Option<JsonValue>,
+    /// 是否被监听
+    watched: bool,
+}
+
+/// 记录配置的配置源
+///
+/// - `load` 成功时记录 key 和加载到的配置
+/// - `watch` 时记录 key，并在配置更新或删除时同步更新记录
+///
+/// 全局配置源（`cfg::init`）会自动使用 TrackedSource 包装
+///
+/// # 示例
+/// ```ignore
+/// let source = TrackedSource::new(Box::new(FileSource::new(config)));
+/// let service = source.create_with_watch::<DatabaseService, DatabaseConfig>("database.json", None)?;
+///
+/// // 启动时输出当前配置（敏感字段脱敏）
+/// println!("{}", source.snapshot(&Redactor::default()).to_json_pretty());
+/// ```
+pub struct TrackedSource {
+    source: Box<dyn ConfigSource>,
+    entries: Arc<RwLock<BTreeMap<String, TrackedEntry>>>,
+}
+
+impl TrackedSource {
+    /// 包装配置源
+    pub fn new(source: Box<dyn ConfigSource>) -> Self {
+        Self {
+            source,
+            entries: Arc::new(RwLock::new(BTreeMap::new())),
+        }
+    }
+
+    /// 所有加载或监听过的 key（按字典序）
+    pub fn keys(&self) -> Vec<String> {
+        self.entries.read().unwrap().keys().cloned().collect()
+    }
+
+    /// 所有监听中的 key（按字典序）
+    pub fn watched_keys(&self) -> Vec<String> {
+        self.entries
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(_, entry)| entry.watched)
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    /// 导出当前生效的配置快照，敏感字段按 `redactor` 脱敏
+    pub fn snapshot(&self, redactor: &Redactor) -> ConfigSnapshot {
+        let values = self
+            .entries
+            .read()
+            .unwrap()
+            .iter()
+            .filter_map(|(key, entry)| {
+                entry
+                    .value
+                    .as_ref()
+                    .map(|value| (key.clone(), redactor.redact(value)))
+            })
+            .collect();
+
+        ConfigSnapshot { values }
+    }
+}
+
+impl ConfigSource for TrackedSource {
+    fn load(&self, key: &str, format: Option<&str>) -> Result<ConfigValue> {
+        let value = self.source.load(key, format)?;
+        self.entries
+            .write()
+            .unwrap()
+            .entry(key.to_string())
+            .or_default()
+            .value = Some(value.as_value().clone());
+        Ok(value)
+    }
+
+    fn watch(
+        &self,
+        key: &str,
+        format: Option<&str>,
+        handler: Box<dyn Fn(ConfigChange) + Send + Sync + 'static>,
+    ) -> Result<()> {
+        let entries = self.entries.clone();
+        let key_owned = key.to_string();
+
+        self.source.watch(key, format, Box::new(move |change| {
+            match &change {
+                ConfigChange::Updated(value) => {
+                    entries.write().unwrap().entry(key_owned.clone()).or_default().value =
+                        Some(value.as_value().clone());
+                }
+                ConfigChange::Deleted => {
+                    if let Some(entry) = entries.write().unwrap().get_mut(&key_owned) {
+                        entry.value = None;
+                    }
+                }
+                ConfigChange::Error(_) => {}
+            }
+            handler(change);
+        }))?;
+
+        self.entries
+            .write()
+            .unwrap()
+            .entry(key.to_string())
+            .or_default()
+            .watched = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cfg::{FileSource, FileSourceConfig};
+    use std::fs;
+    use std::thread;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_tracked_source_snapshot() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let config_path = temp_dir.path().join("database.json");
+        fs::write(&config_path, r#"{"host": "a", "password": "p"}"#)?;
+        fs::write(temp_dir.path().join("cache.json"), r#"{"ttl": 60}"#)?;
+
+        let source = TrackedSource::new(Box::new(FileSource::new(FileSourceConfig {
+            base_path: temp_dir.path().to_string_lossy().to_string(),
+            logger: None,
+            interpolate: false,
+        })));
+
+        source.load("cache.json", None)?;
+        source.load("database.json", None)?;
+        source.watch("database.json", None, Box::new(|_| {}))?;
+        assert!(source.load("missing.json", None).is_err());
+
+        assert_eq!(source.keys(), vec!["cache.json", "database.json"]);
+        assert_eq!(source.watched_keys(), vec!["database.json"]);
+
+        let before = source.snapshot(&Redactor::default());
+        assert_eq!(before.values["database.json"]["password"], "******");
+        assert_eq!(before.values["cache.json"]["ttl"], 60);
+
+        // 监听到的变化同步到快照
+        thread::sleep(Duration::from_millis(200));
+        fs::write(&config_path, r#"{"host": "b", "password": "q"}"#)?;
+        thread::sleep(Duration::from_millis(500));
+
+        let after = source.snapshot(&Redactor::default());
+        assert_eq!(after.values["database.json"]["host"], "b");
+
+        let diffs = before.diff(&after);
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].key, "database.json");
+        assert_eq!(diffs[0].path, "host");
+
+        Ok(())
+    }
+}
@@ -1,20 +1,129 @@
 // TypeOptions 序列化相关实现
 
 use anyhow::Result;
-use serde::{Deserialize, Serialize};
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value as JsonValue;
 use smart_default::SmartDefault;
+use std::borrow::Cow;
+
+/// 引用命名实例时使用的保留类型名称
+pub const INSTANCE_TYPE_NAME: &str = "$instance";
 
 /// 类型选项结构
-#[derive(Debug, Clone, Serialize, Deserialize, SmartDefault)]
+///
+/// 支持两种形式：
+/// - 创建新实例：`{"type": "RedisStore", "options": {...}}`
+/// - 引用命名实例：`{"$instance": "shared-redis"}`，解析为 `type_name` 为 `$instance`、
+///   `options` 为实例名称的 TypeOptions，由 `create_shared_trait_from_type_options` 返回共享的实例
+#[derive(Debug, Clone, SmartDefault)]
 pub struct TypeOptions {
-    #[serde(rename = "type")]
     #[default = ""]
     pub type_name: String,
-    #[default = "json!({})"]
+    #[default(JsonValue::Object(Default::default()))]
     pub options: JsonValue,
 }
 
+/// 创建新实例形式的序列化结构
+#[derive(Serialize, Deserialize)]
+struct CreateRepr {
+    #[serde(rename = "type")]
+    type_name: String,
+    options: JsonValue,
+}
+
+/// 引用命名实例形式的序列化结构
+#[derive(Serialize)]
+struct InstanceRepr<'a> {
+    #[serde(rename = "$instance")]
+    instance: &'a str,
+}
+
+impl Serialize for TypeOptions {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self.instance_name() {
+            Some(instance) => InstanceRepr { instance }.serialize(serializer),
+            None => CreateRepr {
+                type_name: self.type_name.clone(),
+                options: self.options.clone(),
+            }
+            .serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TypeOptions {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let value = JsonValue::deserialize(deserializer)?;
+
+        if let Some(instance) = value.get(INSTANCE_TYPE_NAME) {
+            let name = instance
+                .as_str()
+                .ok_or_else(|| serde::de::Error::custom("`$instance` must be a string"))?;
+            return Ok(TypeOptions::instance(name));
+        }
+
+        let repr: CreateRepr = serde_json::from_value(value).map_err(serde::de::Error::custom)?;
+        Ok(TypeOptions {
+            type_name: repr.type_name,
+            options: repr.options,
+        })
+    }
+}
+
+/// 通用的 TypeOptions Schema，不限定 `type` 的取值
+///
+/// 按 `type` 区分的 Schema 见 `cfg::type_options_schema`
+impl JsonSchema for TypeOptions {
+    fn schema_name() -> Cow<'static, str> {
+        "TypeOptions".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "oneOf": [
+                {
+                    "type": "object",
+                    "properties": {
+                        "type": {"type": "string"},
+                        "options": {}
+                    },
+                    "required": ["type", "options"],
+                    "additionalProperties": false
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "$instance": {"type": "string"}
+                    },
+                    "required": ["$instance"],
+                    "additionalProperties": false
+                }
+            ]
+        })
+    }
+}
+
+/// 命名实例引用
+impl TypeOptions {
+    /// 创建引用命名实例的 TypeOptions
+    pub fn instance(name: &str) -> Self {
+        Self {
+            type_name: INSTANCE_TYPE_NAME.to_string(),
+            options: JsonValue::String(name.to_string()),
+        }
+    }
+
+    /// 引用的命名实例名称，非引用形式返回 None
+    pub fn instance_name(&self) -> Option<&str> {
+        if self.type_name == INSTANCE_TYPE_NAME {
+            self.options.as_str()
+        } else {
+            None
+        }
+    }
+}
+
 /// TypeOptions 的便利函数 - 从各种格式创建和导出
 impl TypeOptions {
     /// 从 JSON 字符串创建 TypeOptions（支持 JSON5 格式）
@@ -373,6 +482,28 @@ mod tests {
         assert_eq!(deserialized.options, type_options.options);
     }
 
+    #[test]
+    fn test_type_options_instance_reference() -> Result<()> {
+        let opts = TypeOptions::from_json(r#"{"$instance": "shared-redis"}"#)?;
+        assert_eq!(opts.instance_name(), Some("shared-redis"));
+        assert_eq!(opts, TypeOptions::instance("shared-redis"));
+
+        // 序列化保持引用形式
+        let serialized = serde_json::to_value(&opts)?;
+        assert_eq!(serialized, serde_json::json!({"$instance": "shared-redis"}));
+
+        let opts = TypeOptions::from_yaml("$instance: shared-redis")?;
+        assert_eq!(opts.instance_name(), Some("shared-redis"));
+
+        // 普通形式没有实例名称，且仍要求 type 字段
+        let opts = TypeOptions::from_json(r#"{"type": "RedisStore", "options": {}}"#)?;
+        assert_eq!(opts.instance_name(), None);
+        assert!(TypeOptions::from_json(r#"{"options": {}}"#).is_err());
+        assert!(TypeOptions::from_json(r#"{"$instance": 1}"#).is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_json5_support() -> Result<()> {
         // JSON5 支持注释
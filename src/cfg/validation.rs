@@ -0,0 +1,198 @@
+//! 配置校验
+//!
+//! 基于 garde::Validate 校验配置，并将校验结果转换为结构化的错误报告
+
+use garde::Validate;
+use serde::de::DeserializeOwned;
+use serde_json::Value as JsonValue;
+use std::fmt;
+use thiserror::Error;
+
+use super::source::ConfigValue;
+
+/// 单个字段的校验错误
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldError {
+    /// 字段路径，如 `retry.max_times`、`endpoints[0]`，顶层校验为空字符串
+    pub path: String,
+    /// 违反的校验规则描述（garde 错误信息），如 `greater than 100`
+    pub rule: String,
+    /// 原始配置中该字段的值，字段不存在时为 None
+    pub value: Option<JsonValue>,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() { "<root>" } else { &self.path };
+        match &self.value {
+            Some(value) => write!(f, "{}: {} (value={})", path, self.rule, value),
+            None => write!(f, "{}: {}", path, self.rule),
+        }
+    }
+}
+
+/// 配置校验错误
+///
+/// 通过 `anyhow::Error::downcast_ref::<ValidationError>()` 获取结构化的字段错误
+#[derive(Debug, Clone, Error)]
+#[error("配置校验失败 [{key}]: {}", .errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; "))]
+pub struct ValidationError {
+    /// 配置键
+    pub key: String,
+    /// 所有字段错误
+    pub errors: Vec<FieldError>,
+}
+
+impl ValidationError {
+    /// 从 garde 校验报告创建，并从原始配置中提取字段值
+    pub fn from_report(key: &str, report: &garde::Report, raw: &JsonValue) -> Self {
+        let errors = report
+            .iter()
+            .map(|(path, error)| {
+                let path = path.to_string();
+                FieldError {
+                    value: lookup_path(raw, &path).cloned(),
+                    rule: error.message().to_string(),
+                    path,
+                }
+            })
+            .collect();
+
+        Self {
+            key: key.to_string(),
+            errors,
+        }
+    }
+}
+
+/// 将配置值反序列化为指定类型并执行 garde 校验
+///
+/// # 返回
+/// - 反序列化失败返回原始错误
+/// - 校验失败返回 `ValidationError`
+pub fn parse_and_validate<Config>(key: &str, value: ConfigValue) -> anyhow::Result<Config>
+where
+    Config: DeserializeOwned + Validate,
+    Config::Context: Default,
+{
+    let config: Config = value.as_type()?;
+    config
+        .validate()
+        .map_err(|report| ValidationError::from_report(key, &report, value.as_value()))?;
+    Ok(config)
+}
+
+/// 根据 garde 路径（如 `a.b[0].c`）查找 JSON 中的值
+fn lookup_path<'a>(value: &'a JsonValue, path: &str) -> Option<&'a JsonValue> {
+    if path.is_empty() {
+        return Some(value);
+    }
+
+    let mut current = value;
+    for segment in path.split('.') {
+        let (name, indexes) = match segment.find('[') {
+            Some(pos) => (&segment[..pos], &segment[pos..]),
+            None => (segment, ""),
+        };
+        if !name.is_empty() {
+            current = current.get(name)?;
+        }
+        for index in indexes.split(']').filter(|s| !s.is_empty()) {
+            let index = index.trim_start_matches('[');
+            current = match index.parse::<usize>() {
+                Ok(i) => current.get(i)?,
+                Err(_) => current.get(index)?,
+            };
+        }
+    }
+
+    Some(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, Validate)]
+    struct RetryConfig {
+        #[garde(range(min = 1, max = 10))]
+        max_times: u32,
+    }
+
+    #[derive(Debug, Deserialize, Validate)]
+    struct ServiceConfig {
+        #[garde(length(min = 1))]
+        name: String,
+        #[garde(dive)]
+        retry: RetryConfig,
+        #[garde(inner(length(min = 1)))]
+        endpoints: Vec<String>,
+    }
+
+    #[test]
+    fn test_lookup_path() {
+        let value = serde_json::json!({
+            "retry": {"max_times": 20},
+            "endpoints": ["a", ""]
+        });
+
+        assert_eq!(lookup_path(&value, "retry.max_times"), Some(&serde_json::json!(20)));
+        assert_eq!(lookup_path(&value, "endpoints[1]"), Some(&serde_json::json!("")));
+        assert_eq!(lookup_path(&value, ""), Some(&value));
+        assert_eq!(lookup_path(&value, "missing.field"), None);
+    }
+
+    #[test]
+    fn test_parse_and_validate_ok() {
+        let value = ConfigValue::new(serde_json::json!({
+            "name": "svc",
+            "retry": {"max_times": 3},
+            "endpoints": ["a"]
+        }));
+
+        let config: ServiceConfig = parse_and_validate("service", value).unwrap();
+        assert_eq!(config.name, "svc");
+        assert_eq!(config.retry.max_times, 3);
+    }
+
+    #[test]
+    fn test_parse_and_validate_errors() {
+        let value = ConfigValue::new(serde_json::json!({
+            "name": "",
+            "retry": {"max_times": 20},
+            "endpoints": ["a", ""]
+        }));
+
+        let err = parse_and_validate::<ServiceConfig>("service", value).unwrap_err();
+        let validation = err.downcast_ref::<ValidationError>().unwrap();
+        assert_eq!(validation.key, "service");
+        assert_eq!(validation.errors.len(), 3);
+
+        let retry = validation
+            .errors
+            .iter()
+            .find(|e| e.path == "retry.max_times")
+            .unwrap();
+        assert_eq!(retry.value, Some(serde_json::json!(20)));
+        assert!(!retry.rule.is_empty());
+
+        let endpoint = validation
+            .errors
+            .iter()
+            .find(|e| e.path == "endpoints[1]")
+            .unwrap();
+        assert_eq!(endpoint.value, Some(serde_json::json!("")));
+
+        let msg = err.to_string();
+        assert!(msg.contains("service"));
+        assert!(msg.contains("retry.max_times"));
+    }
+
+    #[test]
+    fn test_parse_and_validate_deserialize_error() {
+        let value = ConfigValue::new(serde_json::json!({"name": 1}));
+        let err = parse_and_validate::<ServiceConfig>("service", value).unwrap_err();
+        assert!(err.downcast_ref::<ValidationError>().is_none());
+    }
+}
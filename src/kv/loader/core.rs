@@ -37,6 +37,11 @@ pub enum LoaderError {
 pub trait Stream<K, V>: Send + Sync {
     /// 遍历数据流中的每个元素（对应 Golang Each 方法）
     fn each(&self, callback: &dyn Fn(ChangeType, K, V) -> Result<(), LoaderError>) -> Result<(), LoaderError>;
+
+    /// 是否包含数据源的全部数据（全量数据流），inplace 加载时会删除全量数据流中不存在的键
+    fn is_full(&self) -> bool {
+        false
+    }
 }
 
 /// 监听器：处理 KV 数据变更的回调（对应 Golang Listener[K, V]）
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use schemars::JsonSchema;
 use std::sync::atomic::{AtomicBool, Ordering};
 
 use crate::fs::{watch, FileEvent};
@@ -7,7 +8,7 @@ use crate::kv::loader::core::{Loader, Listener, LoaderError};
 /// FileTrigger 配置（遵循 cfg/README.md 最佳实践）
 ///
 /// 监听文件变化并触发通知，不读取文件内容。
-#[derive(Debug, Clone, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Deserialize, JsonSchema)]
 pub struct FileTriggerConfig {
     /// 文件路径
     pub file_path: String,
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use schemars::JsonSchema;
 use std::sync::atomic::{AtomicBool, Ordering};
 
 use crate::cfg::TypeOptions;
@@ -8,7 +9,7 @@ use crate::kv::parser::Parser;
 use smart_default::SmartDefault;
 
 /// KvFileLoader 配置（遵循 cfg/README.md 最佳实践）
-#[derive(Debug, Clone, serde::Deserialize, SmartDefault)]
+#[derive(Debug, Clone, serde::Deserialize, SmartDefault, JsonSchema)]
 #[serde(default)]
 pub struct KvFileLoaderConfig {
     /// 文件路径
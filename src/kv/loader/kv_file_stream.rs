@@ -111,6 +111,11 @@ where
 
         Ok(())
     }
+
+    // 每次都读取整个文件
+    fn is_full(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
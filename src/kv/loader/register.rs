@@ -1,7 +1,7 @@
 use anyhow::Result;
 use std::hash::Hash;
 
-use crate::cfg::register_trait;
+use crate::cfg::register_trait_with_schema;
 
 use super::{Loader, KvFileLoader, KvFileLoaderConfig, FileTrigger, FileTriggerConfig};
 
@@ -48,8 +48,8 @@ where
     K: Clone + Send + Sync + Eq + Hash + 'static,
     V: Clone + Send + Sync + 'static,
 {
-    register_trait::<KvFileLoader<K, V>, dyn Loader<K, V>, KvFileLoaderConfig>("KvFileLoader")?;
-    register_trait::<FileTrigger<K, V>, dyn Loader<K, V>, FileTriggerConfig>("FileTrigger")?;
+    register_trait_with_schema::<KvFileLoader<K, V>, dyn Loader<K, V>, KvFileLoaderConfig>("KvFileLoader")?;
+    register_trait_with_schema::<FileTrigger<K, V>, dyn Loader<K, V>, FileTriggerConfig>("FileTrigger")?;
 
     Ok(())
 }
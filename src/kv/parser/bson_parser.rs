@@ -1,4 +1,5 @@
 use bson::Document;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use std::marker::PhantomData;
 
@@ -11,7 +12,7 @@ use serde_json::json;
 /// BsonParser 配置（遵循 cfg/README.md 最佳实践）
 ///
 /// JsonParser 中的 Condition 和 ChangeTypeRule 被复用。
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 pub struct BsonParserConfig {
     /// 用于生成 key 的字段路径列表（默认：["id"]）
     #[serde(default = "default_key_fields")]
@@ -1,3 +1,4 @@
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
 use serde::Deserialize;
 use serde::de::Deserializer;
 use smart_default::SmartDefault;
@@ -8,7 +9,7 @@ use super::{Parser, ParserError, ChangeType, ParseValue};
 /// 条件表达式（对应 Golang Condition）
 ///
 /// 用于定义匹配规则，指定字段路径和期望值。
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 pub struct Condition {
     /// 字段路径，支持嵌套，如 "user.status" 或 "metadata.timestamp"
     pub field: String,
@@ -19,7 +20,7 @@ pub struct Condition {
 /// 变更类型规则（对应 Golang ChangeTypeRule）
 ///
 /// 定义一组条件，当条件满足时返回指定的变更类型。
-#[derive(Debug, Clone, Deserialize, SmartDefault)]
+#[derive(Debug, Clone, Deserialize, SmartDefault, JsonSchema)]
 #[serde(default)]
 pub struct ChangeTypeRule {
     /// 条件列表
@@ -29,9 +30,20 @@ pub struct ChangeTypeRule {
     pub logic: String,
     /// 满足条件时的变更类型
     #[serde(deserialize_with = "deserialize_change_type")]
+    #[schemars(schema_with = "change_type_schema")]
     pub r#type: ChangeType,
 }
 
+/// 变更类型的 Schema，与 `deserialize_change_type` 支持的取值一致
+fn change_type_schema(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "oneOf": [
+            {"type": "integer", "enum": [1, 2, 3]},
+            {"type": "string", "enum": ["add", "update", "delete"]}
+        ]
+    })
+}
+
 pub(crate) fn deserialize_change_type<'de, D>(deserializer: D) -> Result<ChangeType, D::Error>
 where
     D: Deserializer<'de>,
@@ -64,7 +76,7 @@ where
 }
 
 /// JsonParser 配置（遵循 cfg/README.md 最佳实践）
-#[derive(Debug, Clone, Deserialize, SmartDefault)]
+#[derive(Debug, Clone, Deserialize, SmartDefault, JsonSchema)]
 #[serde(default)]
 pub struct JsonParserConfig {
     /// 用于生成 key 的字段路径列表（默认：["id"]）
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::Deserialize;
 use std::marker::PhantomData;
 
@@ -6,7 +7,7 @@ use super::{ChangeType, ParseValue, Parser, ParserError};
 /// LineParser 配置（遵循 cfg/README.md 最佳实践）
 ///
 /// 配置行解析器的行为，定义分隔符等参数。
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 pub struct LineParserConfig {
     /// 字段分隔符（默认：制表符）
     #[serde(default = "default_separator")]
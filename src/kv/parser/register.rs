@@ -1,7 +1,7 @@
 use anyhow::Result;
 use std::hash::Hash;
 
-use crate::cfg::register_trait;
+use crate::cfg::register_trait_with_schema;
 
 use super::{
     Parser, LineParser, LineParserConfig, JsonParser, JsonParserConfig, BsonParser,
@@ -46,9 +46,9 @@ where
     K: Clone + Send + Sync + Eq + Hash + ParseValue + 'static,
     V: Clone + Send + Sync + ParseValue + for<'de> serde::Deserialize<'de> + 'static,
 {
-    register_trait::<LineParser<K, V>, dyn Parser<K, V>, LineParserConfig>("LineParser")?;
-    register_trait::<JsonParser<K, V>, dyn Parser<K, V>, JsonParserConfig>("JsonParser")?;
-    register_trait::<BsonParser<K, V>, dyn Parser<K, V>, BsonParserConfig>("BsonParser")?;
+    register_trait_with_schema::<LineParser<K, V>, dyn Parser<K, V>, LineParserConfig>("LineParser")?;
+    register_trait_with_schema::<JsonParser<K, V>, dyn Parser<K, V>, JsonParserConfig>("JsonParser")?;
+    register_trait_with_schema::<BsonParser<K, V>, dyn Parser<K, V>, BsonParserConfig>("BsonParser")?;
 
     Ok(())
 }
@@ -1,9 +1,10 @@
 use crate::kv::serializer::core::{Serializer, SerializerError};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
 
 /// BSON 序列化器配置
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Debug, Clone, JsonSchema)]
 pub struct BsonSerializerConfig {}
 impl Default for BsonSerializerConfig {
     fn default() -> Self {
@@ -1,9 +1,10 @@
 use std::marker::PhantomData;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use crate::kv::serializer::core::{Serializer, SerializerError};
 
 /// JSON 序列化器配置
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Debug, Clone, JsonSchema)]
 pub struct JsonSerializerConfig {
     /// 是否格式化输出（美化 JSON）
     #[serde(default)]
@@ -1,9 +1,10 @@
 use std::marker::PhantomData;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use crate::kv::serializer::core::{Serializer, SerializerError};
 
 /// MessagePack 序列化器配置
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Debug, Clone, JsonSchema)]
 pub struct MsgPackSerializerConfig {
     /// 是否使用命名字段（struct字段名）
     #[serde(default = "default_named")]
@@ -1,10 +1,11 @@
 use crate::kv::serializer::core::{Serializer, SerializerError};
 use prost::Message;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use std::marker::PhantomData;
 
 /// Protobuf 序列化器配置
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Debug, Clone, JsonSchema)]
 pub struct ProtobufSerializerConfig {}
 
 impl Default for ProtobufSerializerConfig {
@@ -1,7 +1,7 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
-use crate::cfg::register_trait;
+use crate::cfg::register_trait_with_schema;
 
 use super::{
     BsonSerializer, BsonSerializerConfig, JsonSerializer, JsonSerializerConfig, MsgPackSerializer,
@@ -18,13 +18,13 @@ pub fn register_serde_serializers<T>() -> Result<()>
 where
     T: Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
 {
-    register_trait::<JsonSerializer<T>, dyn Serializer<T, Vec<u8>>, JsonSerializerConfig>(
+    register_trait_with_schema::<JsonSerializer<T>, dyn Serializer<T, Vec<u8>>, JsonSerializerConfig>(
         "JsonSerializer",
     )?;
-    register_trait::<MsgPackSerializer<T>, dyn Serializer<T, Vec<u8>>, MsgPackSerializerConfig>(
+    register_trait_with_schema::<MsgPackSerializer<T>, dyn Serializer<T, Vec<u8>>, MsgPackSerializerConfig>(
         "MsgPackSerializer",
     )?;
-    register_trait::<BsonSerializer<T>, dyn Serializer<T, Vec<u8>>, BsonSerializerConfig>(
+    register_trait_with_schema::<BsonSerializer<T>, dyn Serializer<T, Vec<u8>>, BsonSerializerConfig>(
         "BsonSerializer",
     )?;
     Ok(())
@@ -40,7 +40,7 @@ pub fn register_protobuf_serializers<T>() -> Result<()>
 where
     T: prost::Message + Default + Send + Sync + 'static,
 {
-    register_trait::<ProtobufSerializer<T>, dyn Serializer<T, Vec<u8>>, ProtobufSerializerConfig>(
+    register_trait_with_schema::<ProtobufSerializer<T>, dyn Serializer<T, Vec<u8>>, ProtobufSerializerConfig>(
         "ProtobufSerializer",
     )?;
     Ok(())
@@ -0,0 +1,577 @@
+//! AOP Store 装饰器
+//!
+//! 为 AsyncStore 提供 AOP（面向切面编程）功能，包括日志记录、重试、链路追踪和指标。
+
+use async_trait::async_trait;
+use garde::Validate;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use smart_default::SmartDefault;
+use std::fmt::Debug;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::aop;
+use crate::aop::{Aop, AopConfig};
+use crate::cfg::{create_shared_trait_from_type_options, TypeOptions};
+
+use super::core::{AsyncStore, IsAsyncStore, KvError, SetOptions, Store, Subscription, SyncStore};
+
+/// AopStore 配置
+#[derive(Debug, Clone, Deserialize, SmartDefault, Validate, JsonSchema)]
+#[serde(default)]
+pub struct AopStoreConfig {
+    /// 内部 Store 配置，支持 `{"$instance": "name"}` 引用共享实例
+    #[garde(skip)]
+    pub store: TypeOptions,
+
+    /// AOP 配置（可选），支持 `{"$instance": "name"}` 引用共享的 aop 实例
+    #[garde(skip)]
+    pub aop: Option<AopConfig>,
+}
+
+/// AOP Store 装饰器
+///
+/// 包装一个 AsyncStore 实现，为其方法添加 AOP 功能（日志、重试、链路追踪和指标），
+/// 调用方无需修改即可获得统一的治理能力。
+///
+/// `KeyNotFound`、`ConditionFailed` 是正常的业务结果，不会触发重试，也不计为失败；
+/// `close` 和 `subscribe` 直接透传。
+pub struct AopStore<K, V>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    /// 内部 Store 实例
+    inner: Inner<K, V>,
+
+    /// AOP 配置
+    aop: Option<Arc<Aop>>,
+
+    // 为共享实例时，由实例的所有者负责关闭
+    owns_inner: bool,
+}
+
+/// 内部 Store 的适配层
+///
+/// 可能返回 `KeyNotFound`、`ConditionFailed` 的方法把它们放到内层 Result 中，
+/// 外层的 Err 只包含需要重试的错误；其他方法通过 Deref 直接调用内部 Store
+struct Inner<K, V>(Arc<dyn AsyncStore<K, V>>);
+
+impl<K, V> Deref for Inner<K, V> {
+    type Target = dyn AsyncStore<K, V>;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+/// 区分业务结果和需要重试的错误
+fn split_expected<T>(result: Result<T, KvError>) -> Result<Result<T, KvError>, KvError> {
+    match result {
+        Err(e @ (KvError::KeyNotFound | KvError::ConditionFailed)) => Ok(Err(e)),
+        result => result.map(Ok),
+    }
+}
+
+impl<K, V> Inner<K, V>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    async fn set(
+        &self,
+        key: &K,
+        value: &V,
+        options: &SetOptions,
+    ) -> Result<Result<(), KvError>, KvError> {
+        split_expected(self.0.set(key, value, options).await)
+    }
+
+    async fn get(&self, key: &K) -> Result<Result<V, KvError>, KvError> {
+        split_expected(self.0.get(key).await)
+    }
+
+    async fn compare_and_swap(
+        &self,
+        key: &K,
+        expected: &V,
+        value: &V,
+        options: &SetOptions,
+    ) -> Result<Result<(), KvError>, KvError>
+    where
+        V: PartialEq,
+    {
+        split_expected(self.0.compare_and_swap(key, expected, value, options).await)
+    }
+}
+
+impl<K, V> AopStore<K, V>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    /// 创建 AopStore 实例
+    ///
+    /// # 参数
+    ///
+    /// - `config`: AopStore 配置
+    ///
+    /// # 返回值
+    ///
+    /// - `Ok(Self)`: 创建成功
+    /// - `Err(KvError)`: 创建失败
+    pub fn new(config: AopStoreConfig) -> Result<Self, KvError> {
+        // 验证配置
+        if let Err(e) = config.validate() {
+            return Err(KvError::Other(format!("{}", e)));
+        }
+
+        // 创建内部 Store
+        let inner = create_shared_trait_from_type_options::<dyn AsyncStore<K, V>>(&config.store)
+            .map_err(|e| KvError::Other(format!("failed to create inner store: {}", e)))?;
+
+        // 解析 AOP 配置
+        let aop = match config.aop {
+            Some(aop_config) => Some(
+                Aop::resolve(aop_config)
+                    .map_err(|e| KvError::Other(format!("failed to resolve aop: {}", e)))?,
+            ),
+            None => None,
+        };
+
+        Ok(Self {
+            inner: Inner(inner),
+            aop,
+            owns_inner: config.store.instance_name().is_none(),
+        })
+    }
+
+    /// 从已有的 Store 和 Aop 创建
+    ///
+    /// # 参数
+    ///
+    /// - `inner`: 内部 Store 实例
+    /// - `aop`: AOP 实例（可选）
+    pub fn from_parts(inner: Box<dyn AsyncStore<K, V>>, aop: Option<Arc<Aop>>) -> Self {
+        Self {
+            inner: Inner(inner.into()),
+            aop,
+            owns_inner: true,
+        }
+    }
+}
+
+#[async_trait]
+impl<K, V> AsyncStore<K, V> for AopStore<K, V>
+where
+    K: Clone + Send + Sync + Debug + 'static,
+    V: Clone + Send + Sync + Debug + 'static,
+{
+    async fn set(&self, key: &K, value: &V, options: &SetOptions) -> Result<(), KvError> {
+        aop!(&self.aop, self.inner.set(key, value, options).await)?
+    }
+
+    async fn get(&self, key: &K) -> Result<V, KvError> {
+        aop!(&self.aop, self.inner.get(key).await)?
+    }
+
+    async fn del(&self, key: &K) -> Result<(), KvError> {
+        aop!(&self.aop, self.inner.del(key).await)
+    }
+
+    async fn batch_set(
+        &self,
+        keys: &[K],
+        vals: &[V],
+        options: &SetOptions,
+    ) -> Result<Vec<Result<(), KvError>>, KvError> {
+        aop!(&self.aop, self.inner.batch_set(keys, vals, options).await)
+    }
+
+    async fn batch_get(
+        &self,
+        keys: &[K],
+    ) -> Result<(Vec<Option<V>>, Vec<Option<KvError>>), KvError> {
+        aop!(&self.aop, self.inner.batch_get(keys).await)
+    }
+
+    async fn batch_del(&self, keys: &[K]) -> Result<Vec<Result<(), KvError>>, KvError> {
+        aop!(&self.aop, self.inner.batch_del(keys).await)
+    }
+
+    async fn close(&self) -> Result<(), KvError> {
+        if self.owns_inner {
+            self.inner.close().await?;
+        }
+        Ok(())
+    }
+
+    fn is_exclusive(&self) -> bool {
+        self.inner.is_exclusive()
+    }
+
+    async fn scan(&self, cursor: u64, count: usize) -> Result<(u64, Vec<K>), KvError> {
+        aop!(&self.aop, self.inner.scan(cursor, count).await)
+    }
+
+    async fn keys(&self) -> Result<Vec<K>, KvError> {
+        aop!(&self.aop, self.inner.keys().await)
+    }
+
+    async fn keys_with_prefix(&self, prefix: &str) -> Result<Vec<K>, KvError>
+    where
+        K: AsRef<str>,
+    {
+        aop!(&self.aop, self.inner.keys_with_prefix(prefix).await)
+    }
+
+    async fn len(&self) -> Result<usize, KvError> {
+        aop!(&self.aop, self.inner.len().await)
+    }
+
+    async fn compare_and_swap(
+        &self,
+        key: &K,
+        expected: &V,
+        value: &V,
+        options: &SetOptions,
+    ) -> Result<(), KvError>
+    where
+        V: PartialEq,
+    {
+        aop!(
+            &self.aop,
+            self.inner
+                .compare_and_swap(key, expected, value, options)
+                .await
+        )?
+    }
+
+    /// incr 不是幂等操作，超时等错误重试时可能重复累加，需要精确计数时不要对其启用重试
+    async fn incr(&self, key: &K, delta: i64) -> Result<i64, KvError>
+    where
+        V: Into<i64> + TryFrom<i64>,
+    {
+        aop!(&self.aop, self.inner.incr(key, delta).await)
+    }
+
+    async fn decr(&self, key: &K, delta: i64) -> Result<i64, KvError>
+    where
+        V: Into<i64> + TryFrom<i64>,
+    {
+        aop!(&self.aop, self.inner.decr(key, delta).await)
+    }
+
+    async fn subscribe(&self) -> Result<Subscription<K>, KvError> {
+        self.inner.subscribe().await
+    }
+}
+
+// 实现 cfg 模块要求的 From trait
+impl<K, V> From<AopStoreConfig> for AopStore<K, V>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn from(config: AopStoreConfig) -> Self {
+        Self::new(config).expect("Failed to create AopStore")
+    }
+}
+
+impl<K, V> From<Box<AopStore<K, V>>> for Box<dyn AsyncStore<K, V>>
+where
+    K: Clone + Send + Sync + Debug + 'static,
+    V: Clone + Send + Sync + Debug + 'static,
+{
+    fn from(source: Box<AopStore<K, V>>) -> Self {
+        source as Box<dyn AsyncStore<K, V>>
+    }
+}
+
+impl<K, V> From<Box<AopStore<K, V>>> for Box<dyn SyncStore<K, V>>
+where
+    K: Clone + Send + Sync + Debug + 'static,
+    V: Clone + Send + Sync + Debug + 'static,
+{
+    fn from(source: Box<AopStore<K, V>>) -> Self {
+        source as Box<dyn SyncStore<K, V>>
+    }
+}
+
+impl<K, V> From<Box<AopStore<K, V>>> for Box<dyn Store<K, V>>
+where
+    K: Clone + Send + Sync + Debug + 'static,
+    V: Clone + Send + Sync + Debug + 'static,
+{
+    fn from(source: Box<AopStore<K, V>>) -> Self {
+        source as Box<dyn Store<K, V>>
+    }
+}
+
+/// 实现 IsAsyncStore 标记，让 AopStore 自动获得 SyncStore 能力
+impl<K, V> IsAsyncStore for AopStore<K, V>
+where
+    K: Clone + Send + Sync + Debug + 'static,
+    V: Clone + Send + Sync + Debug + 'static,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kv::store::common_tests::*;
+    use crate::kv::store::{register_hash_stores, RwLockHashMapStore, RwLockHashMapStoreConfig};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn make_aop() -> Arc<Aop> {
+        let config: AopConfig = json5::from_str(
+            r#"{
+                retry: {
+                    max_times: 3,
+                    strategy: "constant",
+                    delay: "1ms"
+                }
+            }"#,
+        )
+        .unwrap();
+        Aop::resolve(config).unwrap()
+    }
+
+    fn make_store<V>() -> AopStore<String, V>
+    where
+        V: Clone + Send + Sync + 'static,
+    {
+        register_hash_stores::<String, V>().unwrap();
+        let config: AopStoreConfig = json5::from_str(
+            r#"{
+                store: {
+                    type: "RwLockHashMapStore",
+                    options: {}
+                },
+                aop: {
+                    retry: {
+                        max_times: 3,
+                        strategy: "constant",
+                        delay: "1ms"
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        AopStore::new(config).unwrap()
+    }
+
+    // 前 failures 次调用返回 Other 错误的 Store，用于测试重试
+    struct FlakyStore {
+        inner: RwLockHashMapStore<String, String>,
+        failures: usize,
+        calls: AtomicUsize,
+    }
+
+    impl FlakyStore {
+        fn new(failures: usize) -> Self {
+            Self {
+                inner: RwLockHashMapStore::new(RwLockHashMapStoreConfig::default()),
+                failures,
+                calls: AtomicUsize::new(0),
+            }
+        }
+
+        fn fail(&self) -> Result<(), KvError> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                return Err(KvError::Other("connection reset".to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncStore<String, String> for Arc<FlakyStore> {
+        async fn set(
+            &self,
+            key: &String,
+            value: &String,
+            options: &SetOptions,
+        ) -> Result<(), KvError> {
+            self.fail()?;
+            self.inner.set(key, value, options).await
+        }
+
+        async fn get(&self, key: &String) -> Result<String, KvError> {
+            self.fail()?;
+            self.inner.get(key).await
+        }
+
+        async fn del(&self, key: &String) -> Result<(), KvError> {
+            self.fail()?;
+            self.inner.del(key).await
+        }
+
+        async fn batch_set(
+            &self,
+            keys: &[String],
+            vals: &[String],
+            options: &SetOptions,
+        ) -> Result<Vec<Result<(), KvError>>, KvError> {
+            self.fail()?;
+            self.inner.batch_set(keys, vals, options).await
+        }
+
+        async fn batch_get(
+            &self,
+            keys: &[String],
+        ) -> Result<(Vec<Option<String>>, Vec<Option<KvError>>), KvError> {
+            self.fail()?;
+            self.inner.batch_get(keys).await
+        }
+
+        async fn batch_del(&self, keys: &[String]) -> Result<Vec<Result<(), KvError>>, KvError> {
+            self.fail()?;
+            self.inner.batch_del(keys).await
+        }
+
+        async fn close(&self) -> Result<(), KvError> {
+            Ok(())
+        }
+    }
+
+    // ========== 公共测试 ==========
+
+    #[tokio::test]
+    async fn test_store_set() {
+        test_set(make_store::<String>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_get() {
+        test_get(make_store::<String>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_del() {
+        test_del(make_store::<String>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_batch_set() {
+        test_batch_set(make_store::<i32>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_batch_get() {
+        test_batch_get(make_store::<i32>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_batch_del() {
+        test_batch_del(make_store::<i32>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_close() {
+        test_close(make_store::<i32>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_scan() {
+        test_scan(make_store::<String>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_compare_and_swap() {
+        test_compare_and_swap(make_store::<String>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_incr() {
+        test_incr(make_store::<i64>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_subscribe() {
+        test_subscribe(make_store::<String>()).await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_store_set_sync() {
+        test_set_sync(make_store::<String>());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_store_get_sync() {
+        test_get_sync(make_store::<String>());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_store_batch_get_sync() {
+        test_batch_get_sync(make_store::<i32>());
+    }
+
+    // ========== 场景测试 ==========
+
+    #[test]
+    fn test_config_deserialize() {
+        let config: AopStoreConfig = json5::from_str(
+            r#"{
+                store: {
+                    type: "RedisStore",
+                    options: {
+                        endpoint: "localhost:6379"
+                    }
+                },
+                aop: {
+                    "$instance": "main"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.store.type_name, "RedisStore");
+        assert!(config.aop.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_errors() {
+        let flaky = Arc::new(FlakyStore::new(2));
+        let store = AopStore::from_parts(Box::new(Arc::clone(&flaky)), Some(make_aop()));
+
+        let key = "key".to_string();
+        store
+            .set(&key, &"value".to_string(), &SetOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 3);
+        assert_eq!(store.get(&key).await.unwrap(), "value");
+
+        // 没有 aop 时不重试
+        let flaky = Arc::new(FlakyStore::new(1));
+        let store = AopStore::from_parts(Box::new(Arc::clone(&flaky)), None);
+        assert!(matches!(store.get(&key).await, Err(KvError::Other(_))));
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_expected_errors_not_retried() {
+        let flaky = Arc::new(FlakyStore::new(0));
+        let store = AopStore::from_parts(Box::new(Arc::clone(&flaky)), Some(make_aop()));
+
+        let key = "key".to_string();
+        assert!(matches!(store.get(&key).await, Err(KvError::KeyNotFound)));
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 1);
+
+        store
+            .set(&key, &"v1".to_string(), &SetOptions::new())
+            .await
+            .unwrap();
+        let result = store
+            .set(
+                &key,
+                &"v2".to_string(),
+                &SetOptions::new().with_if_not_exist(),
+            )
+            .await;
+        assert!(matches!(result, Err(KvError::ConditionFailed)));
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 3);
+    }
+}
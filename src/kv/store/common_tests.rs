@@ -423,3 +423,380 @@ where
     let result = store.get_sync(&"key2".to_string());
     assert!(matches!(result, Ok(2)));
 }
+
+/// 测试 `expiration` 选项
+///
+/// 测试内容:
+/// - 过期前可以获取，过期后返回 KeyNotFound
+/// - 过期的 key 不影响 `if_not_exist`
+/// - 不带过期时间覆盖后不再过期
+/// - `batch_set` 的过期时间对 `batch_get` 生效
+#[cfg(test)]
+pub async fn test_expiration<S>(store: S)
+where
+    S: AsyncStore<String, String>,
+{
+    let ttl = SetOptions::new().with_expiration(std::time::Duration::from_millis(100));
+    let key = "expire_key".to_string();
+    let persist_key = "persist_key".to_string();
+    store.set(&key, &"value".to_string(), &ttl).await.unwrap();
+    store.set(&persist_key, &"value".to_string(), &ttl).await.unwrap();
+    store
+        .set(&persist_key, &"persist".to_string(), &SetOptions::new())
+        .await
+        .unwrap();
+    assert_eq!(store.get(&key).await.unwrap(), "value");
+
+    let keys = vec!["batch_key1".to_string(), "batch_key2".to_string()];
+    let vals = vec!["value1".to_string(), "value2".to_string()];
+    store.batch_set(&keys, &vals, &ttl).await.unwrap();
+    let (values, _) = store.batch_get(&keys).await.unwrap();
+    assert_eq!(values, vec![Some("value1".to_string()), Some("value2".to_string())]);
+
+    tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+
+    // 过期后视为不存在
+    let result = store.get(&key).await;
+    assert!(matches!(result, Err(KvError::KeyNotFound)));
+    let (values, errors) = store.batch_get(&keys).await.unwrap();
+    assert_eq!(values, vec![None, None]);
+    assert!(errors.iter().all(|e| matches!(e, Some(KvError::KeyNotFound))));
+
+    // 过期的 key 可以通过 if_not_exist 重新设置
+    store
+        .set(&key, &"new_value".to_string(), &SetOptions::new().with_if_not_exist())
+        .await
+        .unwrap();
+    assert_eq!(store.get(&key).await.unwrap(), "new_value");
+
+    // 覆盖时未设置过期时间，不再过期
+    assert_eq!(store.get(&persist_key).await.unwrap(), "persist");
+
+    // 过期的键不会被遍历到
+    let keys = store.keys().await.unwrap();
+    assert!(keys.contains(&persist_key));
+    assert!(!keys.iter().any(|key| key.starts_with("batch_key")));
+}
+
+/// 同步版本的 `expiration` 选项测试
+#[cfg(test)]
+pub fn test_expiration_sync<S>(store: S)
+where
+    S: SyncStore<String, String>,
+{
+    let ttl = SetOptions::new().with_expiration(std::time::Duration::from_millis(100));
+    let key = "expire_key".to_string();
+    let persist_key = "persist_key".to_string();
+    store.set_sync(&key, &"value".to_string(), &ttl).unwrap();
+    store.set_sync(&persist_key, &"value".to_string(), &ttl).unwrap();
+    store
+        .set_sync(&persist_key, &"persist".to_string(), &SetOptions::new())
+        .unwrap();
+    assert_eq!(store.get_sync(&key).unwrap(), "value");
+
+    let keys = vec!["batch_key1".to_string(), "batch_key2".to_string()];
+    let vals = vec!["value1".to_string(), "value2".to_string()];
+    store.batch_set_sync(&keys, &vals, &ttl).unwrap();
+    let (values, _) = store.batch_get_sync(&keys).unwrap();
+    assert_eq!(values, vec![Some("value1".to_string()), Some("value2".to_string())]);
+
+    std::thread::sleep(std::time::Duration::from_millis(150));
+
+    // 过期后视为不存在
+    let result = store.get_sync(&key);
+    assert!(matches!(result, Err(KvError::KeyNotFound)));
+    let (values, errors) = store.batch_get_sync(&keys).unwrap();
+    assert_eq!(values, vec![None, None]);
+    assert!(errors.iter().all(|e| matches!(e, Some(KvError::KeyNotFound))));
+
+    // 过期的 key 可以通过 if_not_exist 重新设置
+    store
+        .set_sync(&key, &"new_value".to_string(), &SetOptions::new().with_if_not_exist())
+        .unwrap();
+    assert_eq!(store.get_sync(&key).unwrap(), "new_value");
+
+    // 覆盖时未设置过期时间，不再过期
+    assert_eq!(store.get_sync(&persist_key).unwrap(), "persist");
+
+    // 过期的键不会被遍历到
+    let keys = store.keys_sync().unwrap();
+    assert!(keys.contains(&persist_key));
+    assert!(!keys.iter().any(|key| key.starts_with("batch_key")));
+}
+
+/// 测试 `scan`、`keys`、`keys_with_prefix`、`len`、`is_empty` 方法
+///
+/// 测试内容:
+/// - 分批遍历直到 cursor 为 0，得到所有键
+/// - 按前缀过滤键
+/// - 键数量随写入和删除变化
+#[cfg(test)]
+pub async fn test_scan<S>(store: S)
+where
+    S: AsyncStore<String, String>,
+{
+    let len_before = store.len().await.unwrap();
+    let user_keys: Vec<String> = (0..25).map(|i| format!("scan_user:{}", i)).collect();
+    let order_keys: Vec<String> = (0..5).map(|i| format!("scan_order:{}", i)).collect();
+    for key in user_keys.iter().chain(order_keys.iter()) {
+        store.set(key, &"value".to_string(), &SetOptions::new()).await.unwrap();
+    }
+    assert_eq!(store.len().await.unwrap(), len_before + 30);
+    assert!(!store.is_empty().await.unwrap());
+
+    // 分批遍历
+    let mut scanned = std::collections::HashSet::new();
+    let mut cursor = 0;
+    loop {
+        let (next, keys) = store.scan(cursor, 7).await.unwrap();
+        scanned.extend(keys);
+        if next == 0 {
+            break;
+        }
+        cursor = next;
+    }
+    assert!(user_keys.iter().chain(order_keys.iter()).all(|key| scanned.contains(key)));
+
+    let all_keys = store.keys().await.unwrap();
+    assert_eq!(all_keys.len(), len_before + 30);
+
+    // 按前缀过滤
+    let mut prefixed = store.keys_with_prefix("scan_user:").await.unwrap();
+    prefixed.sort();
+    let mut expected = user_keys.clone();
+    expected.sort();
+    assert_eq!(prefixed, expected);
+    assert!(store.keys_with_prefix("scan_none:").await.unwrap().is_empty());
+
+    store.del(&user_keys[0]).await.unwrap();
+    assert_eq!(store.len().await.unwrap(), len_before + 29);
+    assert_eq!(store.keys_with_prefix("scan_user:").await.unwrap().len(), 24);
+}
+
+/// 同步版本的 `scan`、`keys`、`keys_with_prefix`、`len`、`is_empty` 方法测试
+#[cfg(test)]
+pub fn test_scan_sync<S>(store: S)
+where
+    S: SyncStore<String, String>,
+{
+    let len_before = store.len_sync().unwrap();
+    let user_keys: Vec<String> = (0..25).map(|i| format!("scan_user:{}", i)).collect();
+    let order_keys: Vec<String> = (0..5).map(|i| format!("scan_order:{}", i)).collect();
+    for key in user_keys.iter().chain(order_keys.iter()) {
+        store.set_sync(key, &"value".to_string(), &SetOptions::new()).unwrap();
+    }
+    assert_eq!(store.len_sync().unwrap(), len_before + 30);
+    assert!(!store.is_empty_sync().unwrap());
+
+    // 分批遍历
+    let mut scanned = std::collections::HashSet::new();
+    let mut cursor = 0;
+    loop {
+        let (next, keys) = store.scan_sync(cursor, 7).unwrap();
+        scanned.extend(keys);
+        if next == 0 {
+            break;
+        }
+        cursor = next;
+    }
+    assert!(user_keys.iter().chain(order_keys.iter()).all(|key| scanned.contains(key)));
+
+    let all_keys = store.keys_sync().unwrap();
+    assert_eq!(all_keys.len(), len_before + 30);
+
+    // 按前缀过滤
+    let mut prefixed = store.keys_with_prefix_sync("scan_user:").unwrap();
+    prefixed.sort();
+    let mut expected = user_keys.clone();
+    expected.sort();
+    assert_eq!(prefixed, expected);
+    assert!(store.keys_with_prefix_sync("scan_none:").unwrap().is_empty());
+
+    store.del_sync(&user_keys[0]).unwrap();
+    assert_eq!(store.len_sync().unwrap(), len_before + 29);
+    assert_eq!(store.keys_with_prefix_sync("scan_user:").unwrap().len(), 24);
+}
+
+/// 测试 `if_exist` 选项和 `compare_and_swap` 方法
+///
+/// 测试内容:
+/// - `if_exist` 在键不存在时失败，存在时成功
+/// - 值相等时交换成功，不相等或键不存在时返回 ConditionFailed
+#[cfg(test)]
+pub async fn test_compare_and_swap<S>(store: S)
+where
+    S: AsyncStore<String, String>,
+{
+    let key = "cas_key".to_string();
+    let missing_key = "cas_missing_key".to_string();
+
+    // if_exist 在键不存在时失败
+    let result = store
+        .set(&key, &"v1".to_string(), &SetOptions::new().with_if_exist())
+        .await;
+    assert!(matches!(result, Err(KvError::ConditionFailed)));
+    assert!(matches!(store.get(&key).await, Err(KvError::KeyNotFound)));
+
+    store.set(&key, &"v1".to_string(), &SetOptions::new()).await.unwrap();
+    store
+        .set(&key, &"v2".to_string(), &SetOptions::new().with_if_exist())
+        .await
+        .unwrap();
+    assert_eq!(store.get(&key).await.unwrap(), "v2");
+
+    // 值相等时交换成功
+    store
+        .compare_and_swap(&key, &"v2".to_string(), &"v3".to_string(), &SetOptions::new())
+        .await
+        .unwrap();
+    assert_eq!(store.get(&key).await.unwrap(), "v3");
+
+    // 值不相等时失败，值不变
+    let result = store
+        .compare_and_swap(&key, &"v2".to_string(), &"v4".to_string(), &SetOptions::new())
+        .await;
+    assert!(matches!(result, Err(KvError::ConditionFailed)));
+    assert_eq!(store.get(&key).await.unwrap(), "v3");
+
+    // 键不存在时失败
+    let result = store
+        .compare_and_swap(&missing_key, &"v1".to_string(), &"v2".to_string(), &SetOptions::new())
+        .await;
+    assert!(matches!(result, Err(KvError::ConditionFailed)));
+    assert!(matches!(store.get(&missing_key).await, Err(KvError::KeyNotFound)));
+}
+
+/// 同步版本的 `if_exist` 选项和 `compare_and_swap` 方法测试
+#[cfg(test)]
+pub fn test_compare_and_swap_sync<S>(store: S)
+where
+    S: SyncStore<String, String>,
+{
+    let key = "cas_key".to_string();
+    let missing_key = "cas_missing_key".to_string();
+
+    // if_exist 在键不存在时失败
+    let result = store.set_sync(&key, &"v1".to_string(), &SetOptions::new().with_if_exist());
+    assert!(matches!(result, Err(KvError::ConditionFailed)));
+    assert!(matches!(store.get_sync(&key), Err(KvError::KeyNotFound)));
+
+    store.set_sync(&key, &"v1".to_string(), &SetOptions::new()).unwrap();
+    store
+        .set_sync(&key, &"v2".to_string(), &SetOptions::new().with_if_exist())
+        .unwrap();
+    assert_eq!(store.get_sync(&key).unwrap(), "v2");
+
+    // 值相等时交换成功
+    store
+        .compare_and_swap_sync(&key, &"v2".to_string(), &"v3".to_string(), &SetOptions::new())
+        .unwrap();
+    assert_eq!(store.get_sync(&key).unwrap(), "v3");
+
+    // 值不相等时失败，值不变
+    let result =
+        store.compare_and_swap_sync(&key, &"v2".to_string(), &"v4".to_string(), &SetOptions::new());
+    assert!(matches!(result, Err(KvError::ConditionFailed)));
+    assert_eq!(store.get_sync(&key).unwrap(), "v3");
+
+    // 键不存在时失败
+    let result = store.compare_and_swap_sync(
+        &missing_key,
+        &"v1".to_string(),
+        &"v2".to_string(),
+        &SetOptions::new(),
+    );
+    assert!(matches!(result, Err(KvError::ConditionFailed)));
+    assert!(matches!(store.get_sync(&missing_key), Err(KvError::KeyNotFound)));
+}
+
+/// 测试 `incr` 和 `decr` 方法
+///
+/// 测试内容:
+/// - 键不存在时从 0 开始
+/// - 在已有的值上增减
+/// - 结果可以通过 `get` 读取
+#[cfg(test)]
+pub async fn test_incr<S>(store: S)
+where
+    S: AsyncStore<String, i64>,
+{
+    let key = "counter_key".to_string();
+    assert_eq!(store.incr(&key, 5).await.unwrap(), 5);
+    assert_eq!(store.incr(&key, 3).await.unwrap(), 8);
+    assert_eq!(store.decr(&key, 10).await.unwrap(), -2);
+    assert_eq!(store.get(&key).await.unwrap(), -2);
+
+    store.set(&key, &100, &SetOptions::new()).await.unwrap();
+    assert_eq!(store.incr(&key, 1).await.unwrap(), 101);
+}
+
+/// 同步版本的 `incr` 和 `decr` 方法测试
+#[cfg(test)]
+pub fn test_incr_sync<S>(store: S)
+where
+    S: SyncStore<String, i64>,
+{
+    let key = "counter_key".to_string();
+    assert_eq!(store.incr_sync(&key, 5).unwrap(), 5);
+    assert_eq!(store.incr_sync(&key, 3).unwrap(), 8);
+    assert_eq!(store.decr_sync(&key, 10).unwrap(), -2);
+    assert_eq!(store.get_sync(&key).unwrap(), -2);
+
+    store.set_sync(&key, &100, &SetOptions::new()).unwrap();
+    assert_eq!(store.incr_sync(&key, 1).unwrap(), 101);
+}
+
+/// 测试 `subscribe` 方法
+///
+/// 测试内容:
+/// - 写入新键产生 Add 事件，覆盖已有的键产生 Update 事件
+/// - 删除已有的键产生 Delete 事件，删除不存在的键不产生事件
+/// - 条件不满足的写入不产生事件
+#[cfg(test)]
+pub async fn test_subscribe<S>(store: S)
+where
+    S: AsyncStore<String, String>,
+{
+    use crate::kv::parser::ChangeType;
+
+    let key = "subscribe_key".to_string();
+    let mut subscription = store.subscribe().await.unwrap();
+
+    store.set(&key, &"v1".to_string(), &SetOptions::new()).await.unwrap();
+    store.set(&key, &"v2".to_string(), &SetOptions::new()).await.unwrap();
+    let result = store
+        .set(&key, &"v3".to_string(), &SetOptions::new().with_if_not_exist())
+        .await;
+    assert!(matches!(result, Err(KvError::ConditionFailed)));
+    store.del(&key).await.unwrap();
+    store.del(&key).await.unwrap();
+
+    assert_eq!(subscription.recv().await.unwrap(), (ChangeType::Add, key.clone()));
+    assert_eq!(subscription.recv().await.unwrap(), (ChangeType::Update, key.clone()));
+    assert_eq!(subscription.recv().await.unwrap(), (ChangeType::Delete, key.clone()));
+    assert!(subscription.try_recv().is_err());
+}
+
+/// 同步版本的 `subscribe` 方法测试
+#[cfg(test)]
+pub fn test_subscribe_sync<S>(store: S)
+where
+    S: SyncStore<String, String>,
+{
+    use crate::kv::parser::ChangeType;
+
+    let key = "subscribe_key".to_string();
+    let mut subscription = store.subscribe_sync().unwrap();
+
+    store.set_sync(&key, &"v1".to_string(), &SetOptions::new()).unwrap();
+    store.set_sync(&key, &"v2".to_string(), &SetOptions::new()).unwrap();
+    let result = store.set_sync(&key, &"v3".to_string(), &SetOptions::new().with_if_not_exist());
+    assert!(matches!(result, Err(KvError::ConditionFailed)));
+    store.del_sync(&key).unwrap();
+    store.del_sync(&key).unwrap();
+
+    assert_eq!(subscription.try_recv().unwrap(), (ChangeType::Add, key.clone()));
+    assert_eq!(subscription.try_recv().unwrap(), (ChangeType::Update, key.clone()));
+    assert_eq!(subscription.try_recv().unwrap(), (ChangeType::Delete, key.clone()));
+    assert!(subscription.try_recv().is_err());
+}
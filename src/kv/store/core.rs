@@ -1,6 +1,9 @@
 use async_trait::async_trait;
 use std::time::Duration;
 use thiserror::Error;
+use tokio::sync::broadcast;
+
+use crate::kv::parser::ChangeType;
 
 /// KV 存储相关错误类型（对应 Golang 版本的错误）
 #[derive(Error, Debug)]
@@ -14,12 +17,14 @@ pub enum KvError {
 }
 
 /// 设置选项（对应 Golang 版本的 setOptions）
-#[derive(Default, Clone)]
+#[derive(Debug, Default, Clone)]
 pub struct SetOptions {
     /// 过期时间
     pub expiration: Option<Duration>,
     /// 仅在键不存在时设置
     pub if_not_exist: bool,
+    /// 仅在键存在时设置
+    pub if_exist: bool,
 }
 
 impl SetOptions {
@@ -38,8 +43,78 @@ impl SetOptions {
         self.if_not_exist = true;
         self
     }
+
+    /// 仅在键存在时设置（类似 Redis SET XX）
+    pub fn with_if_exist(mut self) -> Self {
+        self.if_exist = true;
+        self
+    }
+
+    /// 检查 if_not_exist 和 if_exist 条件，`exists` 为键当前是否存在，不满足时返回 ErrConditionFailed
+    ///
+    /// 两个条件同时设置时总是不满足
+    pub fn check_condition(&self, exists: bool) -> Result<(), KvError> {
+        if (self.if_not_exist && exists) || (self.if_exist && !exists) {
+            return Err(KvError::ConditionFailed);
+        }
+        Ok(())
+    }
+}
+
+/// 计算 incr 的结果，`current` 为 None 时视为 0，返回新值及其对应的 V
+pub(crate) fn incr_value<V>(current: Option<&V>, delta: i64) -> Result<(i64, V), KvError>
+where
+    V: Clone + Into<i64> + TryFrom<i64>,
+{
+    let current: i64 = current.cloned().map(Into::into).unwrap_or(0);
+    let next = current
+        .checked_add(delta)
+        .ok_or_else(|| KvError::Other("incr overflow".to_string()))?;
+    let value = V::try_from(next)
+        .map_err(|_| KvError::Other(format!("incr result {} out of range", next)))?;
+    Ok((next, value))
+}
+
+/// V 能表示的 i64 取值范围 `[min, max]`，用于在存储端检查 incr 的结果
+///
+/// 按 `TryFrom<i64>` 二分查找，要求取值范围是包含 0 的连续区间，所有整数类型都满足
+pub(crate) fn incr_bounds<V>() -> (i64, i64)
+where
+    V: TryFrom<i64>,
+{
+    let fits = |n: i64| V::try_from(n).is_ok();
+    if !fits(0) {
+        return (1, 0);
+    }
+    // 在 [ok, err] 之间找到最后一个能表示的值
+    let search = |mut ok: i64, mut err: i64| {
+        while ok.abs_diff(err) > 1 {
+            let mid = ok + (err - ok) / 2;
+            if fits(mid) {
+                ok = mid;
+            } else {
+                err = mid;
+            }
+        }
+        ok
+    };
+    let max = if fits(i64::MAX) { i64::MAX } else { search(0, i64::MAX) };
+    let min = if fits(i64::MIN) { i64::MIN } else { search(0, i64::MIN) };
+    (min, max)
+}
+
+fn negate(delta: i64) -> Result<i64, KvError> {
+    delta
+        .checked_neg()
+        .ok_or_else(|| KvError::Other("decr overflow".to_string()))
 }
 
+/// 变更订阅，接收 `(ChangeType, K)` 事件
+///
+/// 基于 tokio broadcast channel，异步代码中使用 `recv().await`，同步代码中使用 `blocking_recv()` 或 `try_recv()`；
+/// 消费过慢时最旧的事件被丢弃，`recv` 返回 `RecvError::Lagged`
+pub type Subscription<K> = broadcast::Receiver<(ChangeType, K)>;
+
 /// 标记 trait：标识同步存储类型
 ///
 /// 只有实现此 trait 的 SyncStore 才会自动获得 Store trait 的异步包装
@@ -60,7 +135,7 @@ where
     K: Clone + Send + Sync,
     V: Clone + Send + Sync,
 {
-    /// 设置键值对，WithIfNotExist 时键存在则返回 ErrConditionFailed
+    /// 设置键值对，WithIfNotExist 时键存在、WithIfExist 时键不存在则返回 ErrConditionFailed
     fn set_sync(&self, key: &K, value: &V, options: &SetOptions) -> Result<(), KvError>;
 
     /// 获取键对应的值，键不存在时返回 ErrKeyNotFound
@@ -88,8 +163,101 @@ where
 
     /// 关闭存储
     fn close_sync(&self) -> Result<(), KvError>;
+
+    /// 分批遍历键，首次调用 cursor 传 0，返回下一次调用的 cursor 和本批的键，cursor 为 0 时遍历结束
+    ///
+    /// `count` 只是每批数量的提示，实际返回的数量可能更少；遍历期间有写入时可能返回重复的键或遗漏键
+    fn scan_sync(&self, _cursor: u64, _count: usize) -> Result<(u64, Vec<K>), KvError> {
+        Err(KvError::Other("scan is not supported".to_string()))
+    }
+
+    /// 获取所有键
+    ///
+    /// 默认按 `scan_sync` 分批遍历，能直接遍历底层数据的存储应覆盖为一次遍历
+    fn keys_sync(&self) -> Result<Vec<K>, KvError> {
+        let mut keys = Vec::new();
+        let mut cursor = 0;
+        loop {
+            let (next, batch) = self.scan_sync(cursor, SCAN_BATCH_SIZE)?;
+            keys.extend(batch);
+            if next == 0 {
+                return Ok(keys);
+            }
+            cursor = next;
+        }
+    }
+
+    /// 获取以 prefix 开头的键
+    fn keys_with_prefix_sync(&self, prefix: &str) -> Result<Vec<K>, KvError>
+    where
+        K: AsRef<str>,
+    {
+        let mut keys = self.keys_sync()?;
+        keys.retain(|key| key.as_ref().starts_with(prefix));
+        Ok(keys)
+    }
+
+    /// 获取键的数量
+    fn len_sync(&self) -> Result<usize, KvError> {
+        Ok(self.keys_sync()?.len())
+    }
+
+    /// 判断是否没有任何键
+    fn is_empty_sync(&self) -> Result<bool, KvError> {
+        Ok(self.len_sync()? == 0)
+    }
+
+    /// 是否独占底层资源（如 DiskStore 的文件锁），独占的 store 不能同时打开两个实例
+    ///
+    /// 包装其他 store 的实现需要转发给内部 store
+    fn is_exclusive_sync(&self) -> bool {
+        false
+    }
+
+    /// 比较并交换：键当前的值等于 `expected` 时设置为 `value`，键不存在或值不相等时返回 ErrConditionFailed
+    ///
+    /// 只使用 options 的 expiration，比较和设置是原子的
+    fn compare_and_swap_sync(
+        &self,
+        _key: &K,
+        _expected: &V,
+        _value: &V,
+        _options: &SetOptions,
+    ) -> Result<(), KvError>
+    where
+        V: PartialEq,
+    {
+        Err(KvError::Other("compare_and_swap is not supported".to_string()))
+    }
+
+    /// 原子地将键的值加上 delta 并返回新值，键不存在时视为 0，键原有的过期时间保持不变
+    ///
+    /// 仅适用于能与 i64 互相转换的值类型（如 `i8`~`i64`、`u8`~`u32`），`String`、`u64` 等值类型无法调用；
+    /// 结果超出 V 的范围时返回错误
+    fn incr_sync(&self, _key: &K, _delta: i64) -> Result<i64, KvError>
+    where
+        V: Into<i64> + TryFrom<i64>,
+    {
+        Err(KvError::Other("incr is not supported".to_string()))
+    }
+
+    /// 原子地将键的值减去 delta 并返回新值，等价于 `incr_sync(key, -delta)`
+    fn decr_sync(&self, key: &K, delta: i64) -> Result<i64, KvError>
+    where
+        V: Into<i64> + TryFrom<i64>,
+    {
+        self.incr_sync(key, negate(delta)?)
+    }
+
+    /// 订阅键的变更事件，只接收订阅之后发生的变更
+    fn subscribe_sync(&self) -> Result<Subscription<K>, KvError> {
+        Err(KvError::Other("subscribe is not supported".to_string()))
+    }
 }
 
+/// `keys` 默认实现每批遍历的键数量
+pub const SCAN_BATCH_SIZE: usize = 1000;
+
 /// 异步 KV 存储接口
 ///
 /// 用于远程存储实现（如 Redis、云存储等）
@@ -100,7 +268,7 @@ where
     K: Clone + Send + Sync,
     V: Clone + Send + Sync,
 {
-    /// 设置键值对，WithIfNotExist 时键存在则返回 ErrConditionFailed
+    /// 设置键值对，WithIfNotExist 时键存在、WithIfExist 时键不存在则返回 ErrConditionFailed
     async fn set(&self, key: &K, value: &V, options: &SetOptions) -> Result<(), KvError>;
 
     /// 获取键对应的值，键不存在时返回 ErrKeyNotFound
@@ -128,6 +296,94 @@ where
 
     /// 关闭存储
     async fn close(&self) -> Result<(), KvError>;
+
+    /// 分批遍历键，首次调用 cursor 传 0，返回下一次调用的 cursor 和本批的键，cursor 为 0 时遍历结束
+    ///
+    /// `count` 只是每批数量的提示，实际返回的数量可能更少；遍历期间有写入时可能返回重复的键或遗漏键
+    async fn scan(&self, _cursor: u64, _count: usize) -> Result<(u64, Vec<K>), KvError> {
+        Err(KvError::Other("scan is not supported".to_string()))
+    }
+
+    /// 获取所有键
+    async fn keys(&self) -> Result<Vec<K>, KvError> {
+        let mut keys = Vec::new();
+        let mut cursor = 0;
+        loop {
+            let (next, batch) = self.scan(cursor, SCAN_BATCH_SIZE).await?;
+            keys.extend(batch);
+            if next == 0 {
+                return Ok(keys);
+            }
+            cursor = next;
+        }
+    }
+
+    /// 获取以 prefix 开头的键
+    async fn keys_with_prefix(&self, prefix: &str) -> Result<Vec<K>, KvError>
+    where
+        K: AsRef<str>,
+    {
+        let mut keys = self.keys().await?;
+        keys.retain(|key| key.as_ref().starts_with(prefix));
+        Ok(keys)
+    }
+
+    /// 获取键的数量
+    async fn len(&self) -> Result<usize, KvError> {
+        Ok(self.keys().await?.len())
+    }
+
+    /// 判断是否没有任何键
+    async fn is_empty(&self) -> Result<bool, KvError> {
+        Ok(self.len().await? == 0)
+    }
+
+    /// 是否独占底层资源（如 DiskStore 的文件锁），独占的 store 不能同时打开两个实例
+    ///
+    /// 包装其他 store 的实现需要转发给内部 store
+    fn is_exclusive(&self) -> bool {
+        false
+    }
+
+    /// 比较并交换：键当前的值等于 `expected` 时设置为 `value`，键不存在或值不相等时返回 ErrConditionFailed
+    ///
+    /// 只使用 options 的 expiration，比较和设置是原子的
+    async fn compare_and_swap(
+        &self,
+        _key: &K,
+        _expected: &V,
+        _value: &V,
+        _options: &SetOptions,
+    ) -> Result<(), KvError>
+    where
+        V: PartialEq,
+    {
+        Err(KvError::Other("compare_and_swap is not supported".to_string()))
+    }
+
+    /// 原子地将键的值加上 delta 并返回新值，键不存在时视为 0，键原有的过期时间保持不变
+    ///
+    /// 仅适用于能与 i64 互相转换的值类型（如 `i8`~`i64`、`u8`~`u32`），`String`、`u64` 等值类型无法调用；
+    /// 结果超出 V 的范围时返回错误
+    async fn incr(&self, _key: &K, _delta: i64) -> Result<i64, KvError>
+    where
+        V: Into<i64> + TryFrom<i64>,
+    {
+        Err(KvError::Other("incr is not supported".to_string()))
+    }
+
+    /// 原子地将键的值减去 delta 并返回新值，等价于 `incr(key, -delta)`
+    async fn decr(&self, key: &K, delta: i64) -> Result<i64, KvError>
+    where
+        V: Into<i64> + TryFrom<i64>,
+    {
+        self.incr(key, negate(delta)?).await
+    }
+
+    /// 订阅键的变更事件，只接收订阅之后发生的变更
+    async fn subscribe(&self) -> Result<Subscription<K>, KvError> {
+        Err(KvError::Other("subscribe is not supported".to_string()))
+    }
 }
 
 /// 统一 KV 存储接口
@@ -209,6 +465,64 @@ where
     async fn close(&self) -> Result<(), KvError> {
         self.close_sync()
     }
+
+    async fn scan(&self, cursor: u64, count: usize) -> Result<(u64, Vec<K>), KvError> {
+        self.scan_sync(cursor, count)
+    }
+
+    async fn keys(&self) -> Result<Vec<K>, KvError> {
+        self.keys_sync()
+    }
+
+    async fn keys_with_prefix(&self, prefix: &str) -> Result<Vec<K>, KvError>
+    where
+        K: AsRef<str>,
+    {
+        self.keys_with_prefix_sync(prefix)
+    }
+
+    async fn len(&self) -> Result<usize, KvError> {
+        self.len_sync()
+    }
+
+    async fn is_empty(&self) -> Result<bool, KvError> {
+        self.is_empty_sync()
+    }
+
+    fn is_exclusive(&self) -> bool {
+        self.is_exclusive_sync()
+    }
+
+    async fn compare_and_swap(
+        &self,
+        key: &K,
+        expected: &V,
+        value: &V,
+        options: &SetOptions,
+    ) -> Result<(), KvError>
+    where
+        V: PartialEq,
+    {
+        self.compare_and_swap_sync(key, expected, value, options)
+    }
+
+    async fn incr(&self, key: &K, delta: i64) -> Result<i64, KvError>
+    where
+        V: Into<i64> + TryFrom<i64>,
+    {
+        self.incr_sync(key, delta)
+    }
+
+    async fn decr(&self, key: &K, delta: i64) -> Result<i64, KvError>
+    where
+        V: Into<i64> + TryFrom<i64>,
+    {
+        self.decr_sync(key, delta)
+    }
+
+    async fn subscribe(&self) -> Result<Subscription<K>, KvError> {
+        self.subscribe_sync()
+    }
 }
 
 /// 为所有异步存储（AsyncStore + IsAsyncStore）自动提供 SyncStore trait 的同步包装
@@ -284,6 +598,100 @@ where
                 .block_on(self.close())
         })
     }
+
+    fn scan_sync(&self, cursor: u64, count: usize) -> Result<(u64, Vec<K>), KvError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::try_current()
+                .map_err(|e| KvError::Other(format!("no runtime: {}", e)))?
+                .block_on(self.scan(cursor, count))
+        })
+    }
+
+    fn keys_sync(&self) -> Result<Vec<K>, KvError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::try_current()
+                .map_err(|e| KvError::Other(format!("no runtime: {}", e)))?
+                .block_on(self.keys())
+        })
+    }
+
+    fn keys_with_prefix_sync(&self, prefix: &str) -> Result<Vec<K>, KvError>
+    where
+        K: AsRef<str>,
+    {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::try_current()
+                .map_err(|e| KvError::Other(format!("no runtime: {}", e)))?
+                .block_on(self.keys_with_prefix(prefix))
+        })
+    }
+
+    fn len_sync(&self) -> Result<usize, KvError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::try_current()
+                .map_err(|e| KvError::Other(format!("no runtime: {}", e)))?
+                .block_on(self.len())
+        })
+    }
+
+    fn is_empty_sync(&self) -> Result<bool, KvError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::try_current()
+                .map_err(|e| KvError::Other(format!("no runtime: {}", e)))?
+                .block_on(self.is_empty())
+        })
+    }
+
+    fn is_exclusive_sync(&self) -> bool {
+        self.is_exclusive()
+    }
+
+    fn compare_and_swap_sync(
+        &self,
+        key: &K,
+        expected: &V,
+        value: &V,
+        options: &SetOptions,
+    ) -> Result<(), KvError>
+    where
+        V: PartialEq,
+    {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::try_current()
+                .map_err(|e| KvError::Other(format!("no runtime: {}", e)))?
+                .block_on(self.compare_and_swap(key, expected, value, options))
+        })
+    }
+
+    fn incr_sync(&self, key: &K, delta: i64) -> Result<i64, KvError>
+    where
+        V: Into<i64> + TryFrom<i64>,
+    {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::try_current()
+                .map_err(|e| KvError::Other(format!("no runtime: {}", e)))?
+                .block_on(self.incr(key, delta))
+        })
+    }
+
+    fn decr_sync(&self, key: &K, delta: i64) -> Result<i64, KvError>
+    where
+        V: Into<i64> + TryFrom<i64>,
+    {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::try_current()
+                .map_err(|e| KvError::Other(format!("no runtime: {}", e)))?
+                .block_on(self.decr(key, delta))
+        })
+    }
+
+    fn subscribe_sync(&self) -> Result<Subscription<K>, KvError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::try_current()
+                .map_err(|e| KvError::Other(format!("no runtime: {}", e)))?
+                .block_on(self.subscribe())
+        })
+    }
 }
 
 /// 为所有同时实现 SyncStore 和 AsyncStore 的类型自动实现 Store trait
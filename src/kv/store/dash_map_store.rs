@@ -1,27 +1,51 @@
+use dashmap::mapref::entry::Entry as MapEntry;
 use dashmap::DashMap;
 use garde::Validate;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
 use std::hash::Hash;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
 
-use super::core::{IsSyncStore, KvError, SetOptions, Store, AsyncStore, SyncStore};
+use super::core::{
+    incr_value, IsSyncStore, KvError, SetOptions, Store, AsyncStore, Subscription, SyncStore,
+};
+use super::expiry::{Entry, Sweeper};
+use super::notify::ChangeNotifier;
+use crate::kv::parser::ChangeType;
+use crate::cfg::serde_duration::{serde_as, HumanDur};
 
 /// DashMapStore 配置结构体
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, SmartDefault, Validate)]
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, SmartDefault, Validate, JsonSchema)]
 #[serde(default)]
 pub struct DashMapStoreConfig {
     /// 初始容量（可选）
     #[garde(skip)]
     pub initial_capacity: Option<usize>,
+
+    /// 过期键的后台清理间隔，首次设置带过期时间的键时启动清理线程
+    #[serde_as(as = "HumanDur")]
+    #[default(Duration::from_secs(60))]
+    #[garde(skip)]
+    pub sweep_interval: Duration,
 }
 
 /// 基于 DashMap 的 KV 存储实现
+///
+/// 支持 `SetOptions.expiration`：读取时惰性淘汰已过期的键，并由后台线程按 `sweep_interval` 定期清理
+///
+/// 支持 `subscribe`：写入和删除时通知订阅者，过期淘汰不产生事件
 pub struct DashMapStore<K, V>
 where
     K: Clone + Send + Sync + Eq + Hash,
     V: Clone + Send + Sync,
 {
-    map: DashMap<K, V>,
+    map: Arc<DashMap<K, Entry<V>>>,
+    sweep_interval: Duration,
+    sweeper: OnceLock<Sweeper>,
+    notifier: ChangeNotifier<K>,
 }
 
 impl<K, V> DashMapStore<K, V>
@@ -36,7 +60,99 @@ where
             None => DashMap::new(),
         };
 
-        Self { map: initial_map }
+        Self {
+            map: Arc::new(initial_map),
+            sweep_interval: config.sweep_interval,
+            sweeper: OnceLock::new(),
+            notifier: ChangeNotifier::new(),
+        }
+    }
+
+    /// 写入键值，检查 if_not_exist、if_exist 条件时已过期的键视为不存在
+    fn insert(&self, key: &K, value: &V, options: &SetOptions) -> Result<(), KvError> {
+        let entry = Entry::new(value.clone(), options);
+        match self.map.entry(key.clone()) {
+            MapEntry::Occupied(mut occupied) => {
+                let exists = !occupied.get().is_expired(Instant::now());
+                options.check_condition(exists)?;
+                occupied.insert(entry);
+                self.notifier.notify_set(key, exists);
+            }
+            MapEntry::Vacant(vacant) => {
+                options.check_condition(false)?;
+                vacant.insert(entry);
+                self.notifier.notify_set(key, false);
+            }
+        }
+        Ok(())
+    }
+
+    /// 删除键，删除未过期的键时通知订阅者
+    fn remove(&self, key: &K) {
+        let now = Instant::now();
+        if self
+            .map
+            .remove(key)
+            .is_some_and(|(_, entry)| !entry.is_expired(now))
+        {
+            self.notifier.notify(ChangeType::Delete, key);
+        }
+    }
+
+    /// 读取未过期的值，已过期的键在读取时删除
+    fn get_live(&self, key: &K) -> Option<V> {
+        let now = Instant::now();
+        if let Some(entry) = self.map.get(key) {
+            if let Some(value) = entry.live_value(now) {
+                return Some(value.clone());
+            }
+        } else {
+            return None;
+        }
+
+        self.map.remove_if(key, |_, entry| entry.is_expired(now));
+        None
+    }
+}
+
+impl<K, V> DashMapStore<K, V>
+where
+    K: Clone + Send + Sync + Eq + Hash + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    /// 设置带过期时间的键时启动后台清理线程
+    fn ensure_sweeper(&self, options: &SetOptions) {
+        if options.expiration.is_none() {
+            return;
+        }
+        self.sweeper.get_or_init(|| {
+            let map = Arc::downgrade(&self.map);
+            Sweeper::spawn(self.sweep_interval, move || match map.upgrade() {
+                Some(map) => {
+                    let now = Instant::now();
+                    map.retain(|_, entry| !entry.is_expired(now));
+                    true
+                }
+                None => false,
+            })
+        });
+    }
+}
+
+impl<K, V> DashMapStore<K, V>
+where
+    K: Clone + Send + Sync + Eq + Hash,
+    V: Clone + Send + Sync,
+{
+    // 一次遍历收集满足条件的未过期键，迭代器依次持有各分片的读锁
+    fn collect_keys(&self, filter: impl Fn(&K) -> bool) -> Result<Vec<K>, KvError> {
+        let now = Instant::now();
+        Ok(self
+            .map
+            .iter()
+            .filter(|item| !item.value().is_expired(now) && filter(item.key()))
+            .map(|item| item.key().clone())
+            .collect())
     }
 }
 
@@ -60,28 +176,20 @@ where
 
 impl<K, V> SyncStore<K, V> for DashMapStore<K, V>
 where
-    K: Clone + Send + Sync + Eq + Hash,
-    V: Clone + Send + Sync,
+    K: Clone + Send + Sync + Eq + Hash + 'static,
+    V: Clone + Send + Sync + 'static,
 {
     fn set_sync(&self, key: &K, value: &V, options: &SetOptions) -> Result<(), KvError> {
-        // 检查 if_not_exist 条件
-        if options.if_not_exist && self.map.contains_key(key) {
-            return Err(KvError::ConditionFailed);
-        }
-
-        self.map.insert(key.clone(), value.clone());
-        Ok(())
+        self.ensure_sweeper(options);
+        self.insert(key, value, options)
     }
 
     fn get_sync(&self, key: &K) -> Result<V, KvError> {
-        match self.map.get(key) {
-            Some(value_ref) => Ok(value_ref.clone()),
-            None => Err(KvError::KeyNotFound),
-        }
+        self.get_live(key).ok_or(KvError::KeyNotFound)
     }
 
     fn del_sync(&self, key: &K) -> Result<(), KvError> {
-        self.map.remove(key);
+        self.remove(key);
         Ok(())
     }
 
@@ -97,18 +205,12 @@ where
             ));
         }
 
-        let mut results = Vec::with_capacity(keys.len());
-
-        for (key, value) in keys.iter().zip(vals.iter()) {
-            // 检查 if_not_exist 条件
-            if options.if_not_exist && self.map.contains_key(key) {
-                results.push(Err(KvError::ConditionFailed));
-                continue;
-            }
-
-            self.map.insert(key.clone(), value.clone());
-            results.push(Ok(()));
-        }
+        self.ensure_sweeper(options);
+        let results = keys
+            .iter()
+            .zip(vals.iter())
+            .map(|(key, value)| self.insert(key, value, options))
+            .collect();
 
         Ok(results)
     }
@@ -121,9 +223,9 @@ where
         let mut errors = Vec::with_capacity(keys.len());
 
         for key in keys {
-            match self.map.get(key) {
-                Some(value_ref) => {
-                    values.push(Some(value_ref.clone()));
+            match self.get_live(key) {
+                Some(value) => {
+                    values.push(Some(value));
                     errors.push(None);
                 }
                 None => {
@@ -140,7 +242,7 @@ where
         let mut results = Vec::with_capacity(keys.len());
 
         for key in keys {
-            self.map.remove(key);
+            self.remove(key);
             results.push(Ok(()));
         }
 
@@ -151,6 +253,102 @@ where
         // 不清空数据，只做资源清理
         Ok(())
     }
+
+    // DashMap 的迭代器持有分片读锁，逐批复制键后释放
+    fn scan_sync(&self, cursor: u64, count: usize) -> Result<(u64, Vec<K>), KvError> {
+        let count = count.max(1);
+        let now = Instant::now();
+        let mut iter = self.map.iter().skip(cursor as usize);
+        let keys = iter
+            .by_ref()
+            .take(count)
+            .filter(|item| !item.value().is_expired(now))
+            .map(|item| item.key().clone())
+            .collect();
+
+        let next = if iter.next().is_some() {
+            cursor + count as u64
+        } else {
+            0
+        };
+        Ok((next, keys))
+    }
+
+    // 一次遍历所有分片，不像 scan 一样每批从头跳过
+    fn keys_sync(&self) -> Result<Vec<K>, KvError> {
+        self.collect_keys(|_| true)
+    }
+
+    fn keys_with_prefix_sync(&self, prefix: &str) -> Result<Vec<K>, KvError>
+    where
+        K: AsRef<str>,
+    {
+        self.collect_keys(|key| key.as_ref().starts_with(prefix))
+    }
+
+    fn len_sync(&self) -> Result<usize, KvError> {
+        let now = Instant::now();
+        Ok(self
+            .map
+            .iter()
+            .filter(|item| !item.value().is_expired(now))
+            .count())
+    }
+
+    // 通过 entry 持有分片的写锁，保证读取和写入是原子的
+    fn compare_and_swap_sync(
+        &self,
+        key: &K,
+        expected: &V,
+        value: &V,
+        options: &SetOptions,
+    ) -> Result<(), KvError>
+    where
+        V: PartialEq,
+    {
+        self.ensure_sweeper(options);
+        match self.map.entry(key.clone()) {
+            MapEntry::Occupied(mut occupied)
+                if occupied.get().live_value(Instant::now()) == Some(expected) =>
+            {
+                occupied.insert(Entry::new(value.clone(), options));
+                self.notifier.notify(ChangeType::Update, key);
+                Ok(())
+            }
+            _ => Err(KvError::ConditionFailed),
+        }
+    }
+
+    fn incr_sync(&self, key: &K, delta: i64) -> Result<i64, KvError>
+    where
+        V: Into<i64> + TryFrom<i64>,
+    {
+        match self.map.entry(key.clone()) {
+            MapEntry::Occupied(mut occupied) => {
+                // 保留原有的过期时间，已过期的键从 0 开始
+                let live = Some(occupied.get()).filter(|entry| !entry.is_expired(Instant::now()));
+                let (next, value) = incr_value(live.map(|entry| &entry.value), delta)?;
+                let expire_at = live.and_then(|entry| entry.expire_at);
+                let exists = live.is_some();
+                occupied.insert(Entry { value, expire_at });
+                self.notifier.notify_set(key, exists);
+                Ok(next)
+            }
+            MapEntry::Vacant(vacant) => {
+                let (next, value) = incr_value(None, delta)?;
+                vacant.insert(Entry {
+                    value,
+                    expire_at: None,
+                });
+                self.notifier.notify_set(key, false);
+                Ok(next)
+            }
+        }
+    }
+
+    fn subscribe_sync(&self) -> Result<Subscription<K>, KvError> {
+        Ok(self.notifier.subscribe())
+    }
 }
 
 // 为 DashMapStore 实现 From trait
@@ -285,6 +483,66 @@ mod tests {
         test_close_sync(store);
     }
 
+    #[tokio::test]
+    async fn test_store_expiration() {
+        let store = DashMapStore::<String, String>::new(DashMapStoreConfig::default());
+        test_expiration(store).await;
+    }
+
+    #[test]
+    fn test_store_expiration_sync() {
+        let store = DashMapStore::<String, String>::new(DashMapStoreConfig::default());
+        test_expiration_sync(store);
+    }
+
+    #[tokio::test]
+    async fn test_store_scan() {
+        let store = DashMapStore::<String, String>::new(DashMapStoreConfig::default());
+        test_scan(store).await;
+    }
+
+    #[test]
+    fn test_store_scan_sync() {
+        let store = DashMapStore::<String, String>::new(DashMapStoreConfig::default());
+        test_scan_sync(store);
+    }
+
+    #[tokio::test]
+    async fn test_store_compare_and_swap() {
+        let store = DashMapStore::<String, String>::new(DashMapStoreConfig::default());
+        test_compare_and_swap(store).await;
+    }
+
+    #[test]
+    fn test_store_compare_and_swap_sync() {
+        let store = DashMapStore::<String, String>::new(DashMapStoreConfig::default());
+        test_compare_and_swap_sync(store);
+    }
+
+    #[tokio::test]
+    async fn test_store_incr() {
+        let store = DashMapStore::<String, i64>::new(DashMapStoreConfig::default());
+        test_incr(store).await;
+    }
+
+    #[test]
+    fn test_store_incr_sync() {
+        let store = DashMapStore::<String, i64>::new(DashMapStoreConfig::default());
+        test_incr_sync(store);
+    }
+
+    #[tokio::test]
+    async fn test_store_subscribe() {
+        let store = DashMapStore::<String, String>::new(DashMapStoreConfig::default());
+        test_subscribe(store).await;
+    }
+
+    #[test]
+    fn test_store_subscribe_sync() {
+        let store = DashMapStore::<String, String>::new(DashMapStoreConfig::default());
+        test_subscribe_sync(store);
+    }
+
     // ========== 场景测试 ==========
 
     #[tokio::test]
@@ -426,4 +684,74 @@ mod tests {
         let final_value = store.get(&"final_test".to_string()).await.unwrap();
         assert_eq!(final_value, 999);
     }
+
+    #[test]
+    fn test_expired_keys_swept_in_background() {
+        let store = DashMapStore::<String, i32>::new(DashMapStoreConfig {
+            sweep_interval: Duration::from_millis(50),
+            ..Default::default()
+        });
+        let ttl = SetOptions::new().with_expiration(Duration::from_millis(20));
+        for i in 0..10 {
+            store.set_sync(&format!("key{}", i), &i, &ttl).unwrap();
+        }
+        store.set_sync(&"persist".to_string(), &0, &SetOptions::new()).unwrap();
+        assert_eq!(store.map.len(), 11);
+
+        // 未读取的过期键由后台线程清理
+        std::thread::sleep(Duration::from_millis(200));
+        assert_eq!(store.map.len(), 1);
+    }
+
+    #[test]
+    fn test_concurrent_incr_and_compare_and_swap() {
+        let store = Arc::new(DashMapStore::<String, i64>::new(DashMapStoreConfig::default()));
+        let key = "counter".to_string();
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let store = Arc::clone(&store);
+                let key = key.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..500 {
+                        store.incr_sync(&key, 1).unwrap();
+
+                        // 读取后交换，冲突时重试
+                        loop {
+                            let current = store.get_sync(&key).unwrap();
+                            match store.compare_and_swap_sync(&key, &current, &(current + 1), &SetOptions::new()) {
+                                Ok(()) => break,
+                                Err(KvError::ConditionFailed) => continue,
+                                Err(e) => panic!("unexpected error: {}", e),
+                            }
+                        }
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        // 没有丢失的更新
+        assert_eq!(store.get_sync(&key).unwrap(), 8 * 500 * 2);
+    }
+
+    #[test]
+    fn test_incr_keeps_expiration() {
+        let store = DashMapStore::<String, i64>::new(DashMapStoreConfig::default());
+        let key = "counter".to_string();
+        store
+            .set_sync(&key, &1, &SetOptions::new().with_expiration(Duration::from_millis(100)))
+            .unwrap();
+        assert_eq!(store.incr_sync(&key, 1).unwrap(), 2);
+
+        std::thread::sleep(Duration::from_millis(150));
+        assert!(matches!(store.get_sync(&key), Err(KvError::KeyNotFound)));
+
+        // 过期后从 0 开始，且不再过期
+        assert_eq!(store.incr_sync(&key, 1).unwrap(), 1);
+        std::thread::sleep(Duration::from_millis(150));
+        assert_eq!(store.get_sync(&key).unwrap(), 1);
+    }
 }
@@ -0,0 +1,1648 @@
+use garde::Validate;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use smart_default::SmartDefault;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::hash::Hash;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use super::core::{
+    incr_value, AsyncStore, IsSyncStore, KvError, SetOptions, Store, Subscription, SyncStore,
+};
+use super::expiry::{live_keys, scan_live_keys, Entry, Sweeper};
+use super::notify::ChangeNotifier;
+use crate::cfg::serde_duration::{serde_as, HumanDur};
+use crate::cfg::{create_shared_trait_from_type_options, TypeOptions};
+use crate::kv::parser::ChangeType;
+use crate::kv::serializer::Serializer;
+
+/// 刷盘策略
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, SmartDefault, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FsyncPolicy {
+    /// 每次写入后 fsync，机器崩溃也不丢失已返回的写入
+    Always,
+    /// 后台线程按 `fsync_interval` 刷盘，机器崩溃时最多丢失一个间隔内的写入
+    #[default]
+    Interval,
+    /// 不主动刷盘，由操作系统决定写回时机
+    Never,
+}
+
+/// DiskStore 配置结构体
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, SmartDefault, Validate, JsonSchema)]
+#[serde(default)]
+pub struct DiskStoreConfig {
+    /// 日志文件路径，所在目录不存在时自动创建
+    #[garde(length(min = 1))]
+    pub path: String,
+
+    /// 刷盘策略: "always"、"interval" 或 "never"
+    #[garde(skip)]
+    pub fsync: FsyncPolicy,
+
+    /// interval 策略的刷盘间隔
+    #[serde_as(as = "HumanDur")]
+    #[default(Duration::from_secs(1))]
+    #[garde(skip)]
+    pub fsync_interval: Duration,
+
+    /// 无效数据（被覆盖、删除或过期的记录）达到该字节数，且占日志的比例不低于 `compact_ratio` 时自动压缩
+    #[default = 67108864]
+    #[garde(skip)]
+    pub compact_min_bytes: u64,
+
+    /// 触发自动压缩的无效数据比例
+    #[default = 0.5]
+    #[garde(range(min = 0.0, max = 1.0))]
+    pub compact_ratio: f64,
+
+    /// 过期键的后台清理间隔，首次设置带过期时间的键时启动清理线程
+    #[serde_as(as = "HumanDur")]
+    #[default(Duration::from_secs(60))]
+    #[garde(skip)]
+    pub sweep_interval: Duration,
+
+    /// 键序列化器配置（使用 TypeOptions 动态创建，支持 `{"$instance": "name"}` 引用共享实例）
+    /// 默认使用 "JsonSerializer"
+    #[garde(skip)]
+    pub key_serializer: Option<TypeOptions>,
+
+    /// 值序列化器配置（使用 TypeOptions 动态创建，支持 `{"$instance": "name"}` 引用共享实例）
+    /// 默认使用 "JsonSerializer"
+    #[garde(skip)]
+    pub val_serializer: Option<TypeOptions>,
+}
+
+// 记录格式（小端序）：crc32(4) | kind(1) | expire_at(8) | key_len(4) | val_len(4) | key | val
+// crc32 覆盖其后的所有字节，expire_at 为过期时间的 Unix 毫秒数，0 表示不过期
+const HEADER_LEN: u64 = 21;
+const KIND_PUT: u8 = 0;
+const KIND_DEL: u8 = 1;
+
+fn encode_record(kind: u8, expire_ms: u64, key: &[u8], val: &[u8]) -> Vec<u8> {
+    let mut record = Vec::with_capacity(HEADER_LEN as usize + key.len() + val.len());
+    record.extend_from_slice(&[0; 4]);
+    record.push(kind);
+    record.extend_from_slice(&expire_ms.to_le_bytes());
+    record.extend_from_slice(&(key.len() as u32).to_le_bytes());
+    record.extend_from_slice(&(val.len() as u32).to_le_bytes());
+    record.extend_from_slice(key);
+    record.extend_from_slice(val);
+    let crc = crc32fast::hash(&record[4..]);
+    record[..4].copy_from_slice(&crc.to_le_bytes());
+    record
+}
+
+/// 加载时从日志中读出的记录，不保留值的内容
+struct Record {
+    kind: u8,
+    expire_ms: u64,
+    key: Vec<u8>,
+    len: u64,
+    val_len: u64,
+    crc: u32,
+}
+
+/// 读取记录的结果
+enum ReadResult {
+    Record(Record),
+    /// 日志末尾不完整或校验失败的记录，即写入时崩溃留下的残缺数据，可以安全截断
+    Torn,
+    /// 日志中间的记录校验失败，其后还有数据，不能截断
+    Corrupted,
+}
+
+/// 读取一条记录，`remaining` 为日志剩余的字节数
+///
+/// 校验失败的记录恰好延伸到文件末尾时视为写入时崩溃留下的残缺记录
+fn read_record(reader: &mut impl Read, remaining: u64) -> io::Result<ReadResult> {
+    if remaining < HEADER_LEN {
+        return Ok(ReadResult::Torn);
+    }
+    let mut header = [0u8; HEADER_LEN as usize];
+    reader.read_exact(&mut header)?;
+
+    let crc = u32::from_le_bytes(header[0..4].try_into().unwrap());
+    let kind = header[4];
+    let expire_ms = u64::from_le_bytes(header[5..13].try_into().unwrap());
+    let key_len = u32::from_le_bytes(header[13..17].try_into().unwrap()) as u64;
+    let val_len = u32::from_le_bytes(header[17..21].try_into().unwrap()) as u64;
+    let len = HEADER_LEN + key_len + val_len;
+    if len > remaining {
+        return Ok(ReadResult::Torn);
+    }
+    let bad = if len == remaining {
+        ReadResult::Torn
+    } else {
+        ReadResult::Corrupted
+    };
+    if kind > KIND_DEL {
+        return Ok(bad);
+    }
+
+    let mut body = vec![0u8; (key_len + val_len) as usize];
+    reader.read_exact(&mut body)?;
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(&header[4..]);
+    hasher.update(&body);
+    if hasher.finalize() != crc {
+        return Ok(bad);
+    }
+
+    body.truncate(key_len as usize);
+    Ok(ReadResult::Record(Record {
+        kind,
+        expire_ms,
+        key: body,
+        len,
+        val_len,
+        crc,
+    }))
+}
+
+/// 索引中记录的位置
+#[derive(Debug, Clone, Copy)]
+struct Slot {
+    offset: u64,
+    len: u64,
+    val_len: u64,
+    crc: u32,
+    /// 落盘的过期时间，incr 重写记录时保留
+    expire_ms: u64,
+}
+
+/// 日志文件和内存索引，由同一把锁保护
+struct State<K> {
+    index: HashMap<K, Entry<Slot>>,
+    file: File,
+    /// 日志文件长度
+    size: u64,
+    /// 索引中的记录占用的字节数，其余为可以压缩掉的无效数据
+    live: u64,
+}
+
+impl<K> State<K>
+where
+    K: Eq + Hash,
+{
+    /// 追加记录，返回记录的偏移量
+    fn append(&mut self, record: &[u8]) -> io::Result<u64> {
+        let offset = self.size;
+        if let Err(e) = (&self.file).write_all(record) {
+            // 截掉写了一半的记录，保证后续记录的偏移量正确
+            let _ = self.file.set_len(offset);
+            return Err(e);
+        }
+        self.size += record.len() as u64;
+        Ok(offset)
+    }
+
+    fn insert(&mut self, key: K, entry: Entry<Slot>) {
+        self.live += entry.value.len;
+        if let Some(old) = self.index.insert(key, entry) {
+            self.live -= old.value.len;
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> Option<Entry<Slot>> {
+        let entry = self.index.remove(key)?;
+        self.live -= entry.value.len;
+        Some(entry)
+    }
+
+    fn remove_expired(&mut self, now: Instant) {
+        let live = &mut self.live;
+        self.index.retain(|_, entry| {
+            if entry.is_expired(now) {
+                *live -= entry.value.len;
+                return false;
+            }
+            true
+        });
+    }
+
+    fn read(&self, offset: u64, len: u64) -> io::Result<Vec<u8>> {
+        let mut buf = vec![0u8; len as usize];
+        read_at(&self.file, &mut buf, offset)?;
+        Ok(buf)
+    }
+
+    fn read_value(&self, slot: &Slot) -> io::Result<Vec<u8>> {
+        self.read(slot.offset + slot.len - slot.val_len, slot.val_len)
+    }
+
+    fn garbage(&self) -> u64 {
+        self.size - self.live
+    }
+}
+
+#[cfg(unix)]
+fn read_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<()> {
+    use std::os::unix::fs::FileExt;
+    file.read_exact_at(buf, offset)
+}
+
+#[cfg(windows)]
+fn read_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<()> {
+    use std::os::windows::fs::FileExt;
+    let mut read = 0;
+    while read < buf.len() {
+        match file.seek_read(&mut buf[read..], offset + read as u64)? {
+            0 => return Err(io::ErrorKind::UnexpectedEof.into()),
+            n => read += n,
+        }
+    }
+    Ok(())
+}
+
+// rename 后同步目录，保证机器崩溃后新文件名仍然可见
+#[cfg(unix)]
+fn sync_dir(path: &Path) -> io::Result<()> {
+    match path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        Some(dir) => File::open(dir)?.sync_all(),
+        None => File::open(".")?.sync_all(),
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+/// 在路径后追加后缀，用于锁文件和压缩时的临时文件
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = OsString::from(path);
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+fn open_log(path: &Path) -> io::Result<File> {
+    OpenOptions::new()
+        .read(true)
+        .append(true)
+        .create(true)
+        .open(path)
+}
+
+fn io_error(e: io::Error) -> KvError {
+    KvError::Other(format!("disk store io error: {}", e))
+}
+
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// 根据 SetOptions 的 expiration 计算落盘的过期时间（Unix 毫秒数，0 表示不过期）和内存中的过期时间点
+fn expiration(options: &SetOptions) -> (u64, Option<Instant>) {
+    match options.expiration {
+        Some(ttl) => (
+            unix_millis(SystemTime::now() + ttl).max(1),
+            Some(Instant::now() + ttl),
+        ),
+        None => (0, None),
+    }
+}
+
+/// 创建序列化器，未配置时使用 JsonSerializer
+///
+/// 注意：调用此方法前必须先注册相应的序列化器类型
+/// 例如：register_serde_serializers::<String>()?
+fn create_serializer<T>(
+    type_options: Option<TypeOptions>,
+) -> Result<Arc<dyn Serializer<T, Vec<u8>>>, KvError>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    let type_options = type_options.unwrap_or_else(|| TypeOptions {
+        type_name: "JsonSerializer".to_string(),
+        options: serde_json::json!({}),
+    });
+    create_shared_trait_from_type_options(&type_options).map_err(|e| {
+        KvError::Other(format!(
+            "failed to create serializer ({}): {}. \
+             Make sure to register the serializer first using register_serde_serializers::<T>()?",
+            type_options.type_name, e
+        ))
+    })
+}
+
+/// 基于追加日志的持久化 KV 存储
+///
+/// 所有写入以记录的形式追加到日志文件，内存中只保存键到记录位置的索引，读取时从文件读出值再反序列化。
+/// 启动时顺序扫描日志重建索引，末尾不完整或校验失败的记录（写入时崩溃）会被截断。
+///
+/// - 持久化：按 `fsync` 策略刷盘
+/// - 压缩：无效数据超过阈值时把有效记录重写到新文件并原子替换，`close` 时也会压缩，作为下次启动的快照
+/// - 过期：过期时间以绝对时间落盘，重启后仍然生效
+/// - 重复写入与当前记录完全相同的数据时不追加记录，作为 LoadableSyncStore（inplace 策略）的底层存储时，
+///   重启后可以直接读到上次的数据，重新加载未变化的词典也不会让日志增长
+///
+/// 同一个日志文件同时只能被一个未关闭的实例打开（通过 `<path>.lock` 文件锁保证），
+/// 因此不能用于 LoadableSyncStore 的 replace 策略
+pub struct DiskStore<K, V>
+where
+    K: Clone + Send + Sync + Eq + Hash,
+    V: Clone + Send + Sync,
+{
+    path: PathBuf,
+    state: Arc<RwLock<State<K>>>,
+    key_serializer: Arc<dyn Serializer<K, Vec<u8>>>,
+    val_serializer: Arc<dyn Serializer<V, Vec<u8>>>,
+    fsync: FsyncPolicy,
+    // 上次刷盘后是否有写入，interval 策略使用
+    dirty: Arc<AtomicBool>,
+    compact_min_bytes: u64,
+    compact_ratio: f64,
+    sweep_interval: Duration,
+    sweeper: OnceLock<Sweeper>,
+    _flusher: Option<Sweeper>,
+    // 持有文件锁直到 store 被关闭或释放
+    lock: File,
+    notifier: ChangeNotifier<K>,
+}
+
+impl<K, V> DiskStore<K, V>
+where
+    K: Clone + Send + Sync + Eq + Hash + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    /// 打开或创建日志文件并重建索引
+    pub fn new(config: DiskStoreConfig) -> Result<Self, KvError> {
+        if let Err(e) = config.validate() {
+            return Err(KvError::Other(format!(
+                "configuration validation failed: {}",
+                e
+            )));
+        }
+
+        let key_serializer = create_serializer::<K>(config.key_serializer.clone())?;
+        let val_serializer = create_serializer::<V>(config.val_serializer.clone())?;
+
+        let path = PathBuf::from(&config.path);
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+
+        let lock = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(with_suffix(&path, ".lock"))
+            .map_err(io_error)?;
+        lock.try_lock().map_err(|e| match e {
+            TryLockError::WouldBlock => KvError::Other(format!(
+                "disk store {} is already opened by another instance",
+                path.display()
+            )),
+            TryLockError::Error(e) => io_error(e),
+        })?;
+
+        let state = load(open_log(&path).map_err(io_error)?, key_serializer.as_ref())?;
+        let has_expiring = state.index.values().any(|entry| entry.expire_at.is_some());
+        let state = Arc::new(RwLock::new(state));
+
+        let dirty = Arc::new(AtomicBool::new(false));
+        let flusher = (config.fsync == FsyncPolicy::Interval).then(|| {
+            let state = Arc::downgrade(&state);
+            let dirty = Arc::clone(&dirty);
+            Sweeper::spawn(config.fsync_interval, move || match state.upgrade() {
+                Some(state) => {
+                    if dirty.swap(false, Ordering::SeqCst) {
+                        // 在锁外刷盘，不阻塞写入
+                        let file = state.read().unwrap().file.try_clone();
+                        if file.and_then(|file| file.sync_data()).is_err() {
+                            dirty.store(true, Ordering::SeqCst);
+                        }
+                    }
+                    true
+                }
+                None => false,
+            })
+        });
+
+        let store = Self {
+            path,
+            state,
+            key_serializer,
+            val_serializer,
+            fsync: config.fsync,
+            dirty,
+            compact_min_bytes: config.compact_min_bytes,
+            compact_ratio: config.compact_ratio,
+            sweep_interval: config.sweep_interval,
+            sweeper: OnceLock::new(),
+            _flusher: flusher,
+            lock,
+            notifier: ChangeNotifier::new(),
+        };
+        if has_expiring {
+            store.start_sweeper();
+        }
+        Ok(store)
+    }
+
+    /// 压缩日志：只保留未过期的最新记录，写入临时文件后原子替换
+    pub fn compact(&self) -> Result<(), KvError> {
+        let mut state = self.state.write().unwrap();
+        self.compact_locked(&mut state)
+    }
+
+    fn compact_locked(&self, state: &mut State<K>) -> Result<(), KvError> {
+        state.remove_expired(Instant::now());
+
+        let tmp_path = with_suffix(&self.path, ".compact");
+        let _ = fs::remove_file(&tmp_path);
+        let file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create_new(true)
+            .open(&tmp_path)
+            .map_err(io_error)?;
+
+        let result = (|| {
+            let mut writer = BufWriter::new(&file);
+            let mut offsets = Vec::with_capacity(state.index.len());
+            let mut size = 0;
+            for entry in state.index.values() {
+                writer.write_all(&state.read(entry.value.offset, entry.value.len)?)?;
+                offsets.push(size);
+                size += entry.value.len;
+            }
+            writer.flush()?;
+            drop(writer);
+            file.sync_all()?;
+            fs::rename(&tmp_path, &self.path)?;
+            Ok((offsets, size))
+        })();
+        let (offsets, size) = match result {
+            Ok(result) => result,
+            Err(e) => {
+                let _ = fs::remove_file(&tmp_path);
+                return Err(io_error(e));
+            }
+        };
+        let _ = sync_dir(&self.path);
+
+        // 重命名后原来的句柄指向新的日志文件
+        for (entry, offset) in state.index.values_mut().zip(offsets) {
+            entry.value.offset = offset;
+        }
+        state.file = file;
+        state.size = size;
+        state.live = size;
+        self.dirty.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// 写入后按刷盘策略持久化，并在无效数据过多时压缩
+    fn commit(&self, state: &mut State<K>) -> Result<(), KvError> {
+        match self.fsync {
+            FsyncPolicy::Always => state.file.sync_data().map_err(io_error)?,
+            FsyncPolicy::Interval => self.dirty.store(true, Ordering::SeqCst),
+            FsyncPolicy::Never => {}
+        }
+
+        let garbage = state.garbage();
+        if garbage > 0
+            && garbage >= self.compact_min_bytes
+            && garbage as f64 >= state.size as f64 * self.compact_ratio
+        {
+            // 写入已经成功，压缩失败不影响本次写入的结果
+            if let Err(e) = self.compact_locked(state) {
+                log::warn!("disk store compaction failed: {}", e);
+            }
+        }
+        Ok(())
+    }
+
+    /// 追加写入记录并更新索引，与当前记录完全相同时不追加
+    fn put(
+        &self,
+        state: &mut State<K>,
+        key: &K,
+        val: &[u8],
+        expire_ms: u64,
+        expire_at: Option<Instant>,
+    ) -> Result<(), KvError> {
+        let record = encode_record(KIND_PUT, expire_ms, &self.serialize_key(key)?, val);
+        let crc = u32::from_le_bytes(record[..4].try_into().unwrap());
+        let len = record.len() as u64;
+
+        if let Some(current) = state.index.get(key) {
+            if current.value.crc == crc
+                && current.value.len == len
+                && state
+                    .read(current.value.offset, current.value.len)
+                    .map_err(io_error)?
+                    == record
+            {
+                return Ok(());
+            }
+        }
+
+        let offset = state.append(&record).map_err(io_error)?;
+        let slot = Slot {
+            offset,
+            len,
+            val_len: val.len() as u64,
+            crc,
+            expire_ms,
+        };
+        state.insert(
+            key.clone(),
+            Entry {
+                value: slot,
+                expire_at,
+            },
+        );
+        Ok(())
+    }
+
+    /// 追加删除记录并更新索引，返回删除前键是否存在
+    fn delete(&self, state: &mut State<K>, key: &K, now: Instant) -> Result<bool, KvError> {
+        let Some(entry) = state.index.get(key) else {
+            return Ok(false);
+        };
+        // 已过期的记录重启后也会被忽略，不需要删除记录
+        if entry.is_expired(now) {
+            state.remove(key);
+            return Ok(false);
+        }
+
+        let record = encode_record(KIND_DEL, 0, &self.serialize_key(key)?, &[]);
+        state.append(&record).map_err(io_error)?;
+        state.remove(key);
+        Ok(true)
+    }
+
+    /// 读取未过期的值
+    fn live_value(
+        &self,
+        state: &State<K>,
+        key: &K,
+        now: Instant,
+    ) -> Result<Option<(Entry<Slot>, V)>, KvError> {
+        match state.index.get(key).filter(|entry| !entry.is_expired(now)) {
+            Some(entry) => {
+                let bytes = state.read_value(&entry.value).map_err(io_error)?;
+                Ok(Some((entry.clone(), self.deserialize_value(bytes)?)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn serialize_key(&self, key: &K) -> Result<Vec<u8>, KvError> {
+        self.key_serializer
+            .serialize(key.clone())
+            .map_err(|e| KvError::Other(format!("failed to serialize key: {}", e)))
+    }
+
+    fn serialize_value(&self, value: &V) -> Result<Vec<u8>, KvError> {
+        self.val_serializer
+            .serialize(value.clone())
+            .map_err(|e| KvError::Other(format!("failed to serialize value: {}", e)))
+    }
+
+    fn deserialize_value(&self, bytes: Vec<u8>) -> Result<V, KvError> {
+        self.val_serializer
+            .deserialize(bytes)
+            .map_err(|e| KvError::Other(format!("failed to deserialize value: {}", e)))
+    }
+
+    /// 删除已过期的键（读取时惰性淘汰）
+    fn evict_expired(&self, keys: &[&K], now: Instant) {
+        let mut state = self.state.write().unwrap();
+        for key in keys {
+            if state
+                .index
+                .get(*key)
+                .is_some_and(|entry| entry.is_expired(now))
+            {
+                state.remove(*key);
+            }
+        }
+    }
+
+    /// 设置带过期时间的键时启动后台清理线程
+    fn ensure_sweeper(&self, options: &SetOptions) {
+        if options.expiration.is_some() {
+            self.start_sweeper();
+        }
+    }
+
+    fn start_sweeper(&self) {
+        self.sweeper.get_or_init(|| {
+            let state = Arc::downgrade(&self.state);
+            Sweeper::spawn(self.sweep_interval, move || match state.upgrade() {
+                Some(state) => {
+                    state.write().unwrap().remove_expired(Instant::now());
+                    true
+                }
+                None => false,
+            })
+        });
+    }
+}
+
+/// 从日志重建索引
+///
+/// 末尾不完整或校验失败的记录会被截断；中间的记录校验失败时返回错误，不修改文件
+fn load<K>(file: File, key_serializer: &dyn Serializer<K, Vec<u8>>) -> Result<State<K>, KvError>
+where
+    K: Eq + Hash,
+{
+    let file_len = file.metadata().map_err(io_error)?.len();
+    let mut reader = BufReader::new(file.try_clone().map_err(io_error)?);
+    let mut state = State {
+        index: HashMap::new(),
+        file,
+        size: 0,
+        live: 0,
+    };
+
+    let now = Instant::now();
+    let now_ms = unix_millis(SystemTime::now());
+    let mut offset = 0;
+    while offset < file_len {
+        let record = match read_record(&mut reader, file_len - offset).map_err(io_error)? {
+            ReadResult::Record(record) => record,
+            ReadResult::Torn => break,
+            ReadResult::Corrupted => {
+                return Err(KvError::Other(format!(
+                    "corrupted record at offset {} in {} bytes log",
+                    offset, file_len
+                )))
+            }
+        };
+        let key = key_serializer.deserialize(record.key).map_err(|e| {
+            KvError::Other(format!(
+                "failed to deserialize key at offset {}: {}",
+                offset, e
+            ))
+        })?;
+
+        match record.kind {
+            KIND_PUT if record.expire_ms == 0 || record.expire_ms > now_ms => {
+                let expire_at = (record.expire_ms != 0)
+                    .then(|| now + Duration::from_millis(record.expire_ms - now_ms));
+                let slot = Slot {
+                    offset,
+                    len: record.len,
+                    val_len: record.val_len,
+                    crc: record.crc,
+                    expire_ms: record.expire_ms,
+                };
+                state.insert(
+                    key,
+                    Entry {
+                        value: slot,
+                        expire_at,
+                    },
+                );
+            }
+            // 删除记录和已过期的写入记录
+            _ => {
+                state.remove(&key);
+            }
+        }
+        offset += record.len;
+    }
+
+    if offset < file_len {
+        state.file.set_len(offset).map_err(io_error)?;
+    }
+    state.size = offset;
+    Ok(state)
+}
+
+impl<K, V> Drop for DiskStore<K, V>
+where
+    K: Clone + Send + Sync + Eq + Hash,
+    V: Clone + Send + Sync,
+{
+    fn drop(&mut self) {
+        if self.dirty.load(Ordering::SeqCst) {
+            if let Ok(state) = self.state.read() {
+                let _ = state.file.sync_data();
+            }
+        }
+    }
+}
+
+// 标记为同步存储，自动获得 Store 异步接口
+impl<K, V> IsSyncStore for DiskStore<K, V>
+where
+    K: Clone + Send + Sync + Eq + Hash,
+    V: Clone + Send + Sync,
+{
+}
+
+impl<K, V> SyncStore<K, V> for DiskStore<K, V>
+where
+    K: Clone + Send + Sync + Eq + Hash + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn set_sync(&self, key: &K, value: &V, options: &SetOptions) -> Result<(), KvError> {
+        let val = self.serialize_value(value)?;
+        let (expire_ms, expire_at) = expiration(options);
+        self.ensure_sweeper(options);
+        let now = Instant::now();
+        let mut state = self.state.write().unwrap();
+
+        // 检查 if_not_exist、if_exist 条件，已过期的键视为不存在
+        let exists = state
+            .index
+            .get(key)
+            .is_some_and(|entry| !entry.is_expired(now));
+        options.check_condition(exists)?;
+
+        self.put(&mut state, key, &val, expire_ms, expire_at)?;
+        self.commit(&mut state)?;
+        self.notifier.notify_set(key, exists);
+        Ok(())
+    }
+
+    fn get_sync(&self, key: &K) -> Result<V, KvError> {
+        let now = Instant::now();
+        let bytes = {
+            let state = self.state.read().unwrap();
+            match state.index.get(key) {
+                Some(entry) if !entry.is_expired(now) => {
+                    Some(state.read_value(&entry.value).map_err(io_error)?)
+                }
+                Some(_) => None,
+                None => return Err(KvError::KeyNotFound),
+            }
+        };
+
+        match bytes {
+            Some(bytes) => self.deserialize_value(bytes),
+            None => {
+                self.evict_expired(&[key], now);
+                Err(KvError::KeyNotFound)
+            }
+        }
+    }
+
+    fn del_sync(&self, key: &K) -> Result<(), KvError> {
+        let now = Instant::now();
+        let mut state = self.state.write().unwrap();
+        if self.delete(&mut state, key, now)? {
+            self.commit(&mut state)?;
+            self.notifier.notify(ChangeType::Delete, key);
+        }
+        Ok(())
+    }
+
+    fn batch_set_sync(
+        &self,
+        keys: &[K],
+        vals: &[V],
+        options: &SetOptions,
+    ) -> Result<Vec<Result<(), KvError>>, KvError> {
+        if keys.len() != vals.len() {
+            return Err(KvError::Other(
+                "Keys and values length mismatch".to_string(),
+            ));
+        }
+
+        let vals = vals
+            .iter()
+            .map(|value| self.serialize_value(value))
+            .collect::<Result<Vec<_>, _>>()?;
+        let (expire_ms, expire_at) = expiration(options);
+        self.ensure_sweeper(options);
+        let now = Instant::now();
+        let mut state = self.state.write().unwrap();
+        let mut results = Vec::with_capacity(keys.len());
+        let mut changes = Vec::new();
+
+        for (key, val) in keys.iter().zip(vals.iter()) {
+            // 检查 if_not_exist、if_exist 条件
+            let exists = state
+                .index
+                .get(key)
+                .is_some_and(|entry| !entry.is_expired(now));
+            if let Err(e) = options.check_condition(exists) {
+                results.push(Err(e));
+                continue;
+            }
+
+            match self.put(&mut state, key, val, expire_ms, expire_at) {
+                Ok(()) => {
+                    changes.push((key, exists));
+                    results.push(Ok(()));
+                }
+                Err(e) => results.push(Err(e)),
+            }
+        }
+
+        // 整批写入只刷盘一次
+        self.commit(&mut state)?;
+        for (key, exists) in changes {
+            self.notifier.notify_set(key, exists);
+        }
+        Ok(results)
+    }
+
+    fn batch_get_sync(
+        &self,
+        keys: &[K],
+    ) -> Result<(Vec<Option<V>>, Vec<Option<KvError>>), KvError> {
+        let now = Instant::now();
+        let mut bytes = Vec::with_capacity(keys.len());
+        let mut expired = Vec::new();
+
+        {
+            let state = self.state.read().unwrap();
+            for key in keys {
+                match state.index.get(key) {
+                    Some(entry) if !entry.is_expired(now) => {
+                        bytes.push(Some(state.read_value(&entry.value).map_err(io_error)?));
+                    }
+                    other => {
+                        if other.is_some() {
+                            expired.push(key);
+                        }
+                        bytes.push(None);
+                    }
+                }
+            }
+        }
+
+        if !expired.is_empty() {
+            self.evict_expired(&expired, now);
+        }
+
+        let mut values = Vec::with_capacity(keys.len());
+        let mut errors = Vec::with_capacity(keys.len());
+        for bytes in bytes {
+            match bytes.map(|bytes| self.deserialize_value(bytes)) {
+                Some(Ok(value)) => {
+                    values.push(Some(value));
+                    errors.push(None);
+                }
+                Some(Err(e)) => {
+                    values.push(None);
+                    errors.push(Some(e));
+                }
+                None => {
+                    values.push(None);
+                    errors.push(Some(KvError::KeyNotFound));
+                }
+            }
+        }
+
+        Ok((values, errors))
+    }
+
+    fn batch_del_sync(&self, keys: &[K]) -> Result<Vec<Result<(), KvError>>, KvError> {
+        let now = Instant::now();
+        let mut state = self.state.write().unwrap();
+        let mut results = Vec::with_capacity(keys.len());
+        let mut deleted = Vec::new();
+
+        for key in keys {
+            match self.delete(&mut state, key, now) {
+                Ok(existed) => {
+                    if existed {
+                        deleted.push(key);
+                    }
+                    results.push(Ok(()));
+                }
+                Err(e) => results.push(Err(e)),
+            }
+        }
+
+        self.commit(&mut state)?;
+        for key in deleted {
+            self.notifier.notify(ChangeType::Delete, key);
+        }
+        Ok(results)
+    }
+
+    /// 压缩日志作为下次启动的快照并刷盘，然后释放文件锁，不清空数据
+    ///
+    /// 关闭后仍然可以读取，但不应再写入，日志文件可能已被新的实例打开
+    fn close_sync(&self) -> Result<(), KvError> {
+        let mut state = self.state.write().unwrap();
+        if state.garbage() > 0 {
+            self.compact_locked(&mut state)?;
+        } else {
+            state.file.sync_data().map_err(io_error)?;
+            self.dirty.store(false, Ordering::SeqCst);
+        }
+        self.lock.unlock().map_err(io_error)
+    }
+
+    fn is_exclusive_sync(&self) -> bool {
+        true
+    }
+
+    fn scan_sync(&self, cursor: u64, count: usize) -> Result<(u64, Vec<K>), KvError> {
+        let state = self.state.read().unwrap();
+        Ok(scan_live_keys(
+            state.index.iter(),
+            cursor,
+            count,
+            Instant::now(),
+        ))
+    }
+
+    fn keys_sync(&self) -> Result<Vec<K>, KvError> {
+        let state = self.state.read().unwrap();
+        Ok(live_keys(state.index.iter(), Instant::now(), |_| true))
+    }
+
+    fn keys_with_prefix_sync(&self, prefix: &str) -> Result<Vec<K>, KvError>
+    where
+        K: AsRef<str>,
+    {
+        let state = self.state.read().unwrap();
+        Ok(live_keys(state.index.iter(), Instant::now(), |key| {
+            key.as_ref().starts_with(prefix)
+        }))
+    }
+
+    fn len_sync(&self) -> Result<usize, KvError> {
+        let now = Instant::now();
+        let state = self.state.read().unwrap();
+        Ok(state
+            .index
+            .values()
+            .filter(|entry| !entry.is_expired(now))
+            .count())
+    }
+
+    fn compare_and_swap_sync(
+        &self,
+        key: &K,
+        expected: &V,
+        value: &V,
+        options: &SetOptions,
+    ) -> Result<(), KvError>
+    where
+        V: PartialEq,
+    {
+        let val = self.serialize_value(value)?;
+        let (expire_ms, expire_at) = expiration(options);
+        self.ensure_sweeper(options);
+        let now = Instant::now();
+        let mut state = self.state.write().unwrap();
+
+        match self.live_value(&state, key, now)? {
+            Some((_, current)) if current == *expected => {
+                self.put(&mut state, key, &val, expire_ms, expire_at)?;
+                self.commit(&mut state)?;
+                self.notifier.notify(ChangeType::Update, key);
+                Ok(())
+            }
+            _ => Err(KvError::ConditionFailed),
+        }
+    }
+
+    fn incr_sync(&self, key: &K, delta: i64) -> Result<i64, KvError>
+    where
+        V: Into<i64> + TryFrom<i64>,
+    {
+        let now = Instant::now();
+        let mut state = self.state.write().unwrap();
+
+        // 保留原有的过期时间，已过期的键从 0 开始
+        let live = self.live_value(&state, key, now)?;
+        let (next, value) = incr_value(live.as_ref().map(|(_, value)| value), delta)?;
+        let (expire_ms, expire_at) = live.as_ref().map_or((0, None), |(entry, _)| {
+            (entry.value.expire_ms, entry.expire_at)
+        });
+
+        let val = self.serialize_value(&value)?;
+        self.put(&mut state, key, &val, expire_ms, expire_at)?;
+        self.commit(&mut state)?;
+        self.notifier.notify_set(key, live.is_some());
+        Ok(next)
+    }
+
+    fn subscribe_sync(&self) -> Result<Subscription<K>, KvError> {
+        Ok(self.notifier.subscribe())
+    }
+}
+
+// 实现 cfg 模块要求的 From trait
+impl<K, V> From<DiskStoreConfig> for DiskStore<K, V>
+where
+    K: Clone + Send + Sync + Eq + Hash + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn from(config: DiskStoreConfig) -> Self {
+        DiskStore::new(config).expect("Failed to create DiskStore")
+    }
+}
+
+impl<K, V> From<Box<DiskStore<K, V>>> for Box<dyn AsyncStore<K, V>>
+where
+    K: Clone + Send + Sync + Eq + Hash + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn from(source: Box<DiskStore<K, V>>) -> Self {
+        source as Box<dyn AsyncStore<K, V>>
+    }
+}
+
+impl<K, V> From<Box<DiskStore<K, V>>> for Box<dyn SyncStore<K, V>>
+where
+    K: Clone + Send + Sync + Eq + Hash + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn from(source: Box<DiskStore<K, V>>) -> Self {
+        source as Box<dyn SyncStore<K, V>>
+    }
+}
+
+impl<K, V> From<Box<DiskStore<K, V>>> for Box<dyn Store<K, V>>
+where
+    K: Clone + Send + Sync + Eq + Hash + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn from(source: Box<DiskStore<K, V>>) -> Self {
+        source as Box<dyn Store<K, V>>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kv::serializer::register_serde_serializers;
+    use crate::kv::store::common_tests::*;
+    use serde::de::DeserializeOwned;
+    use tempfile::TempDir;
+
+    fn make_config(dir: &TempDir) -> DiskStoreConfig {
+        DiskStoreConfig {
+            path: dir.path().join("store.log").to_string_lossy().into_owned(),
+            ..Default::default()
+        }
+    }
+
+    fn make_store<V>(dir: &TempDir) -> DiskStore<String, V>
+    where
+        V: Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+    {
+        register_serde_serializers::<String>().unwrap();
+        register_serde_serializers::<V>().unwrap();
+        DiskStore::new(make_config(dir)).unwrap()
+    }
+
+    fn log_size(dir: &TempDir) -> u64 {
+        fs::metadata(dir.path().join("store.log")).unwrap().len()
+    }
+
+    // ========== 公共测试 ==========
+
+    #[tokio::test]
+    async fn test_store_set() {
+        let dir = TempDir::new().unwrap();
+        test_set(make_store::<String>(&dir)).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_get() {
+        let dir = TempDir::new().unwrap();
+        test_get(make_store::<String>(&dir)).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_del() {
+        let dir = TempDir::new().unwrap();
+        test_del(make_store::<String>(&dir)).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_batch_set() {
+        let dir = TempDir::new().unwrap();
+        test_batch_set(make_store::<i32>(&dir)).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_batch_get() {
+        let dir = TempDir::new().unwrap();
+        test_batch_get(make_store::<i32>(&dir)).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_batch_del() {
+        let dir = TempDir::new().unwrap();
+        test_batch_del(make_store::<i32>(&dir)).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_close() {
+        let dir = TempDir::new().unwrap();
+        test_close(make_store::<i32>(&dir)).await;
+    }
+
+    #[test]
+    fn test_store_set_sync() {
+        let dir = TempDir::new().unwrap();
+        test_set_sync(make_store::<String>(&dir));
+    }
+
+    #[test]
+    fn test_store_get_sync() {
+        let dir = TempDir::new().unwrap();
+        test_get_sync(make_store::<String>(&dir));
+    }
+
+    #[test]
+    fn test_store_del_sync() {
+        let dir = TempDir::new().unwrap();
+        test_del_sync(make_store::<String>(&dir));
+    }
+
+    #[test]
+    fn test_store_batch_set_sync() {
+        let dir = TempDir::new().unwrap();
+        test_batch_set_sync(make_store::<i32>(&dir));
+    }
+
+    #[test]
+    fn test_store_batch_get_sync() {
+        let dir = TempDir::new().unwrap();
+        test_batch_get_sync(make_store::<i32>(&dir));
+    }
+
+    #[test]
+    fn test_store_batch_del_sync() {
+        let dir = TempDir::new().unwrap();
+        test_batch_del_sync(make_store::<i32>(&dir));
+    }
+
+    #[test]
+    fn test_store_close_sync() {
+        let dir = TempDir::new().unwrap();
+        test_close_sync(make_store::<i32>(&dir));
+    }
+
+    #[tokio::test]
+    async fn test_store_expiration() {
+        let dir = TempDir::new().unwrap();
+        test_expiration(make_store::<String>(&dir)).await;
+    }
+
+    #[test]
+    fn test_store_expiration_sync() {
+        let dir = TempDir::new().unwrap();
+        test_expiration_sync(make_store::<String>(&dir));
+    }
+
+    #[tokio::test]
+    async fn test_store_scan() {
+        let dir = TempDir::new().unwrap();
+        test_scan(make_store::<String>(&dir)).await;
+    }
+
+    #[test]
+    fn test_store_scan_sync() {
+        let dir = TempDir::new().unwrap();
+        test_scan_sync(make_store::<String>(&dir));
+    }
+
+    #[tokio::test]
+    async fn test_store_compare_and_swap() {
+        let dir = TempDir::new().unwrap();
+        test_compare_and_swap(make_store::<String>(&dir)).await;
+    }
+
+    #[test]
+    fn test_store_compare_and_swap_sync() {
+        let dir = TempDir::new().unwrap();
+        test_compare_and_swap_sync(make_store::<String>(&dir));
+    }
+
+    #[tokio::test]
+    async fn test_store_incr() {
+        let dir = TempDir::new().unwrap();
+        test_incr(make_store::<i64>(&dir)).await;
+    }
+
+    #[test]
+    fn test_store_incr_sync() {
+        let dir = TempDir::new().unwrap();
+        test_incr_sync(make_store::<i64>(&dir));
+    }
+
+    #[tokio::test]
+    async fn test_store_subscribe() {
+        let dir = TempDir::new().unwrap();
+        test_subscribe(make_store::<String>(&dir)).await;
+    }
+
+    #[test]
+    fn test_store_subscribe_sync() {
+        let dir = TempDir::new().unwrap();
+        test_subscribe_sync(make_store::<String>(&dir));
+    }
+
+    // ========== 场景测试 ==========
+
+    #[test]
+    fn test_store_from_json5_config() {
+        let config: DiskStoreConfig = json5::from_str(
+            r#"{
+                path: "/data/kv/store.log",
+                fsync: "always",
+                compact_min_bytes: 1024,
+                compact_ratio: 0.3,
+                val_serializer: {
+                    type: "MsgPackSerializer",
+                    options: {}
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(config.path, "/data/kv/store.log");
+        assert_eq!(config.fsync, FsyncPolicy::Always);
+        assert_eq!(config.fsync_interval, Duration::from_secs(1));
+        assert_eq!(config.compact_min_bytes, 1024);
+        assert_eq!(
+            config.val_serializer.unwrap().type_name,
+            "MsgPackSerializer"
+        );
+
+        // path 必填
+        register_serde_serializers::<String>().unwrap();
+        let result = DiskStore::<String, String>::new(DiskStoreConfig::default());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_reopen_restores_data() {
+        let dir = TempDir::new().unwrap();
+        {
+            let store = make_store::<String>(&dir);
+            store
+                .set_sync(&"k1".to_string(), &"v1".to_string(), &SetOptions::new())
+                .unwrap();
+            store
+                .set_sync(&"k2".to_string(), &"v2".to_string(), &SetOptions::new())
+                .unwrap();
+            store
+                .set_sync(&"k2".to_string(), &"v2-new".to_string(), &SetOptions::new())
+                .unwrap();
+            store
+                .set_sync(&"k3".to_string(), &"v3".to_string(), &SetOptions::new())
+                .unwrap();
+            store.del_sync(&"k3".to_string()).unwrap();
+            store
+                .set_sync(
+                    &"ttl".to_string(),
+                    &"v".to_string(),
+                    &SetOptions::new().with_expiration(Duration::from_secs(3600)),
+                )
+                .unwrap();
+            store
+                .set_sync(
+                    &"short".to_string(),
+                    &"v".to_string(),
+                    &SetOptions::new().with_expiration(Duration::from_millis(50)),
+                )
+                .unwrap();
+        }
+
+        std::thread::sleep(Duration::from_millis(100));
+        let store = make_store::<String>(&dir);
+        assert_eq!(store.get_sync(&"k1".to_string()).unwrap(), "v1");
+        assert_eq!(store.get_sync(&"k2".to_string()).unwrap(), "v2-new");
+        assert!(matches!(
+            store.get_sync(&"k3".to_string()),
+            Err(KvError::KeyNotFound)
+        ));
+        assert_eq!(store.get_sync(&"ttl".to_string()).unwrap(), "v");
+        // 过期时间落盘，重启后仍然生效
+        assert!(matches!(
+            store.get_sync(&"short".to_string()),
+            Err(KvError::KeyNotFound)
+        ));
+        assert_eq!(store.len_sync().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_torn_write_truncated() {
+        let dir = TempDir::new().unwrap();
+        {
+            let store = make_store::<String>(&dir);
+            store
+                .set_sync(&"k1".to_string(), &"v1".to_string(), &SetOptions::new())
+                .unwrap();
+            store
+                .set_sync(&"k2".to_string(), &"v2".to_string(), &SetOptions::new())
+                .unwrap();
+        }
+        let size = log_size(&dir);
+
+        // 模拟写入时崩溃：最后一条记录只写了一半
+        let record = encode_record(KIND_PUT, 0, b"\"k3\"", b"\"v3\"");
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(dir.path().join("store.log"))
+            .unwrap();
+        file.write_all(&record[..record.len() - 2]).unwrap();
+        drop(file);
+
+        let store = make_store::<String>(&dir);
+        assert_eq!(store.get_sync(&"k1".to_string()).unwrap(), "v1");
+        assert_eq!(store.get_sync(&"k2".to_string()).unwrap(), "v2");
+        assert!(matches!(
+            store.get_sync(&"k3".to_string()),
+            Err(KvError::KeyNotFound)
+        ));
+        assert_eq!(log_size(&dir), size);
+
+        // 截断后可以继续写入
+        store
+            .set_sync(&"k3".to_string(), &"v3".to_string(), &SetOptions::new())
+            .unwrap();
+        drop(store);
+        let store = make_store::<String>(&dir);
+        assert_eq!(store.get_sync(&"k3".to_string()).unwrap(), "v3");
+    }
+
+    #[test]
+    fn test_corrupted_record_truncated() {
+        let dir = TempDir::new().unwrap();
+        {
+            let store = make_store::<String>(&dir);
+            store
+                .set_sync(&"k1".to_string(), &"v1".to_string(), &SetOptions::new())
+                .unwrap();
+            store
+                .set_sync(&"k2".to_string(), &"v2".to_string(), &SetOptions::new())
+                .unwrap();
+        }
+
+        // 修改最后一条记录的值，校验失败
+        let path = dir.path().join("store.log");
+        let mut data = fs::read(&path).unwrap();
+        let last = data.len() - 1;
+        data[last] ^= 0xff;
+        fs::write(&path, &data).unwrap();
+
+        let store = make_store::<String>(&dir);
+        assert_eq!(store.get_sync(&"k1".to_string()).unwrap(), "v1");
+        assert!(matches!(
+            store.get_sync(&"k2".to_string()),
+            Err(KvError::KeyNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_corrupted_record_in_middle() {
+        let dir = TempDir::new().unwrap();
+        {
+            let store = make_store::<String>(&dir);
+            store
+                .set_sync(&"k1".to_string(), &"v1".to_string(), &SetOptions::new())
+                .unwrap();
+            store
+                .set_sync(&"k2".to_string(), &"v2".to_string(), &SetOptions::new())
+                .unwrap();
+        }
+
+        // 修改第一条记录的值，其后的记录仍然完整
+        let path = dir.path().join("store.log");
+        let mut data = fs::read(&path).unwrap();
+        let first = encode_record(KIND_PUT, 0, b"\"k1\"", b"\"v1\"").len();
+        data[first - 1] ^= 0xff;
+        fs::write(&path, &data).unwrap();
+
+        // 无法区分损坏范围，拒绝打开并保留文件，避免丢失后续数据
+        let result = DiskStore::<String, String>::new(make_config(&dir));
+        assert!(
+            matches!(result, Err(KvError::Other(msg)) if msg.contains("corrupted record at offset 0"))
+        );
+        assert_eq!(fs::read(&path).unwrap(), data);
+    }
+
+    #[test]
+    fn test_compaction() {
+        register_serde_serializers::<String>().unwrap();
+        register_serde_serializers::<i64>().unwrap();
+        let dir = TempDir::new().unwrap();
+        let store = DiskStore::<String, i64>::new(DiskStoreConfig {
+            compact_min_bytes: 1024,
+            ..make_config(&dir)
+        })
+        .unwrap();
+
+        for i in 0..1000 {
+            store
+                .set_sync(&"counter".to_string(), &i, &SetOptions::new())
+                .unwrap();
+            store
+                .set_sync(&format!("key{}", i % 10), &i, &SetOptions::new())
+                .unwrap();
+        }
+        // 无效数据超过阈值后自动压缩，日志不会无限增长
+        assert!(log_size(&dir) < 4096);
+        assert_eq!(store.get_sync(&"counter".to_string()).unwrap(), 999);
+
+        // close 时压缩为只包含有效记录的快照
+        store.del_sync(&"key0".to_string()).unwrap();
+        store.close_sync().unwrap();
+        let state = store.state.read().unwrap();
+        assert_eq!(state.size, state.live);
+        assert_eq!(log_size(&dir), state.size);
+        drop(state);
+        drop(store);
+
+        let store = DiskStore::<String, i64>::new(make_config(&dir)).unwrap();
+        assert_eq!(store.get_sync(&"counter".to_string()).unwrap(), 999);
+        assert_eq!(store.get_sync(&"key9".to_string()).unwrap(), 999);
+        assert!(matches!(
+            store.get_sync(&"key0".to_string()),
+            Err(KvError::KeyNotFound)
+        ));
+        assert_eq!(store.len_sync().unwrap(), 10);
+    }
+
+    #[test]
+    fn test_same_value_not_appended() {
+        let dir = TempDir::new().unwrap();
+        let store = make_store::<String>(&dir);
+        store
+            .set_sync(&"k1".to_string(), &"v1".to_string(), &SetOptions::new())
+            .unwrap();
+        let size = log_size(&dir);
+
+        store
+            .set_sync(&"k1".to_string(), &"v1".to_string(), &SetOptions::new())
+            .unwrap();
+        assert_eq!(log_size(&dir), size);
+
+        store
+            .set_sync(&"k1".to_string(), &"v2".to_string(), &SetOptions::new())
+            .unwrap();
+        assert!(log_size(&dir) > size);
+    }
+
+    #[test]
+    fn test_exclusive_lock() {
+        let dir = TempDir::new().unwrap();
+        let store = make_store::<String>(&dir);
+
+        let result = DiskStore::<String, String>::new(make_config(&dir));
+        assert!(matches!(result, Err(KvError::Other(msg)) if msg.contains("already opened")));
+
+        // 关闭或释放后可以重新打开
+        store.close_sync().unwrap();
+        let store = DiskStore::<String, String>::new(make_config(&dir)).unwrap();
+        drop(store);
+        assert!(DiskStore::<String, String>::new(make_config(&dir)).is_ok());
+    }
+
+    #[test]
+    fn test_create_from_config_locked() -> Result<(), anyhow::Error> {
+        use crate::cfg::{create_trait_from_type_options, TypeOptions};
+        use crate::kv::store::register_hash_stores;
+
+        register_hash_stores::<String, String>()?;
+        let dir = TempDir::new()?;
+        let _store = make_store::<String>(&dir);
+
+        // 日志已被占用时通过配置创建返回错误，而不是 panic
+        let opts = TypeOptions {
+            type_name: "DiskStore".to_string(),
+            options: serde_json::to_value(make_config(&dir))?,
+        };
+        let result = create_trait_from_type_options::<dyn SyncStore<String, String>>(&opts);
+        let err = result.err().expect("locked DiskStore should fail");
+        assert!(err.to_string().contains("already opened"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fsync_policies() {
+        register_serde_serializers::<String>().unwrap();
+        for fsync in [
+            FsyncPolicy::Always,
+            FsyncPolicy::Interval,
+            FsyncPolicy::Never,
+        ] {
+            let dir = TempDir::new().unwrap();
+            let store = DiskStore::<String, String>::new(DiskStoreConfig {
+                fsync,
+                fsync_interval: Duration::from_millis(10),
+                ..make_config(&dir)
+            })
+            .unwrap();
+            store
+                .set_sync(&"key".to_string(), &"value".to_string(), &SetOptions::new())
+                .unwrap();
+            if fsync == FsyncPolicy::Interval {
+                std::thread::sleep(Duration::from_millis(100));
+                assert!(!store.dirty.load(Ordering::SeqCst));
+            }
+            drop(store);
+
+            let store = DiskStore::<String, String>::new(make_config(&dir)).unwrap();
+            assert_eq!(store.get_sync(&"key".to_string()).unwrap(), "value");
+        }
+    }
+
+    #[test]
+    fn test_as_loadable_sync_store_inner() -> Result<(), anyhow::Error> {
+        use crate::kv::loader::register::register_loaders;
+        use crate::kv::parser::register_parsers;
+        use crate::cfg::TypeOptions;
+        use crate::kv::store::{
+            register_hash_stores, register_stores, LoadableSyncStore, LoadableSyncStoreConfig,
+        };
+
+        register_parsers::<String, String>()?;
+        register_loaders::<String, String>()?;
+        register_hash_stores::<String, String>()?;
+        register_serde_serializers::<String>()?;
+
+        let dir = TempDir::new()?;
+        let data_path = dir.path().join("data.txt");
+        fs::write(&data_path, "k1\tv1\nk2\tv2\n")?;
+        let store_path = dir.path().join("store.log");
+        let config: LoadableSyncStoreConfig = json5::from_str(&format!(
+            r#"{{
+                store: {{
+                    type: "DiskStore",
+                    options: {{ path: "{}" }}
+                }},
+                loader: {{
+                    type: "KvFileLoader",
+                    options: {{
+                        file_path: "{}",
+                        parser: {{ type: "LineParser", options: {{ separator: "\t" }} }}
+                    }}
+                }}
+            }}"#,
+            store_path.display(),
+            data_path.display()
+        ))?;
+
+        let store = LoadableSyncStore::<String, String>::new(config.clone())?;
+        assert_eq!(store.get_sync(&"k1".to_string())?, "v1");
+        // 释放后文件锁随底层 store 一起释放，可以重新打开
+        drop(store);
+        let size = fs::metadata(&store_path)?.len();
+
+        // 重启后重新加载未变化的数据，日志不增长
+        let store = LoadableSyncStore::<String, String>::new(config.clone())?;
+        assert_eq!(store.get_sync(&"k2".to_string())?, "v2");
+        assert_eq!(fs::metadata(&store_path)?.len(), size);
+        drop(store);
+
+        // 重启期间从数据源删除的键在重新加载后一并删除
+        fs::write(&data_path, "k1\tv1\n")?;
+        let store = LoadableSyncStore::<String, String>::new(config.clone())?;
+        assert_eq!(store.get_sync(&"k1".to_string())?, "v1");
+        assert!(matches!(
+            store.get_sync(&"k2".to_string()),
+            Err(KvError::KeyNotFound)
+        ));
+        drop(store);
+        let store = DiskStore::<String, String>::new(DiskStoreConfig {
+            path: store_path.to_string_lossy().into_owned(),
+            ..Default::default()
+        })?;
+        assert_eq!(store.keys_sync()?, vec!["k1".to_string()]);
+        drop(store);
+
+        // replace 策略需要同时打开新旧 store，创建时拒绝
+        let result = LoadableSyncStore::<String, String>::new(LoadableSyncStoreConfig {
+            load_strategy: "replace".to_string(),
+            ..config.clone()
+        });
+        assert!(result.err().unwrap().to_string().contains("exclusive store"));
+
+        // 包装 DiskStore 的 store 同样独占日志文件
+        register_stores::<String, String>()?;
+        let prefixed = TypeOptions {
+            type_name: "PrefixedStore".to_string(),
+            options: serde_json::json!({ "store": config.store, "prefix": "p:" }),
+        };
+        let result = LoadableSyncStore::<String, String>::new(LoadableSyncStoreConfig {
+            store: prefixed,
+            load_strategy: "replace".to_string(),
+            ..config
+        });
+        assert!(result.err().unwrap().to_string().contains("exclusive store"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+//! 内存存储的过期支持
+//!
+//! - `Entry`: 带过期时间的值，读取时惰性淘汰已过期的键
+//! - `Sweeper`: 后台清理线程，定期删除已过期但未被读取的键
+//! - `scan_live_keys`: 按偏移量分批遍历未过期的键
+//! - `live_keys`: 一次遍历收集满足条件的未过期键
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use super::core::SetOptions;
+
+/// 带过期时间的值
+#[derive(Debug, Clone)]
+pub(crate) struct Entry<V> {
+    pub(crate) value: V,
+    /// 过期时间点，None 表示不过期
+    pub(crate) expire_at: Option<Instant>,
+}
+
+impl<V> Entry<V> {
+    /// 根据 SetOptions 的 expiration 创建
+    pub(crate) fn new(value: V, options: &SetOptions) -> Self {
+        Self {
+            value,
+            expire_at: options.expiration.map(|ttl| Instant::now() + ttl),
+        }
+    }
+
+    /// 在 `now` 时刻是否已过期
+    pub(crate) fn is_expired(&self, now: Instant) -> bool {
+        self.expire_at.is_some_and(|expire_at| expire_at <= now)
+    }
+
+    /// 在 `now` 时刻未过期时返回值
+    pub(crate) fn live_value(&self, now: Instant) -> Option<&V> {
+        (!self.is_expired(now)).then_some(&self.value)
+    }
+}
+
+/// 后台清理线程
+///
+/// 按 `interval` 周期调用清理函数，清理函数返回 false（如存储已释放）或 Sweeper 被 drop 时退出
+pub(crate) struct Sweeper {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Sweeper {
+    pub(crate) fn spawn<F>(interval: Duration, sweep: F) -> Self
+    where
+        F: Fn() -> bool + Send + 'static,
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_clone = Arc::clone(&stop);
+
+        let handle = thread::spawn(move || loop {
+            thread::park_timeout(interval);
+            if stop_clone.load(Ordering::SeqCst) || !sweep() {
+                break;
+            }
+        });
+
+        Self {
+            stop,
+            handle: Some(handle),
+        }
+    }
+}
+
+impl Drop for Sweeper {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            handle.thread().unpark();
+            let _ = handle.join();
+        }
+    }
+}
+
+/// 按偏移量分批遍历未过期的键，cursor 为已遍历的条目数（包括已过期的条目）
+///
+/// 底层 map 没有写入时遍历顺序不变，因此可以用偏移量作为 cursor
+pub(crate) fn scan_live_keys<'a, K, V, I>(
+    entries: I,
+    cursor: u64,
+    count: usize,
+    now: Instant,
+) -> (u64, Vec<K>)
+where
+    K: Clone + 'a,
+    V: 'a,
+    I: Iterator<Item = (&'a K, &'a Entry<V>)>,
+{
+    let count = count.max(1);
+    let mut entries = entries.skip(cursor as usize);
+    let keys = entries
+        .by_ref()
+        .take(count)
+        .filter(|(_, entry)| !entry.is_expired(now))
+        .map(|(key, _)| key.clone())
+        .collect();
+
+    let next = if entries.next().is_some() {
+        cursor + count as u64
+    } else {
+        0
+    };
+    (next, keys)
+}
+
+/// 一次遍历收集满足 `filter` 的未过期键，供 `keys`、`keys_with_prefix` 在一把锁内得到一致的快照
+pub(crate) fn live_keys<'a, K, V, I>(
+    entries: I,
+    now: Instant,
+    filter: impl Fn(&K) -> bool,
+) -> Vec<K>
+where
+    K: Clone + 'a,
+    V: 'a,
+    I: Iterator<Item = (&'a K, &'a Entry<V>)>,
+{
+    entries
+        .filter(|(key, entry)| !entry.is_expired(now) && filter(key))
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn test_entry_expiration() {
+        let now = Instant::now();
+        let entry = Entry::new(1, &SetOptions::new());
+        assert!(!entry.is_expired(now + Duration::from_secs(3600)));
+
+        let entry = Entry::new(1, &SetOptions::new().with_expiration(Duration::from_millis(100)));
+        assert_eq!(entry.live_value(now), Some(&1));
+        assert!(entry.is_expired(now + Duration::from_millis(200)));
+        assert_eq!(entry.live_value(now + Duration::from_millis(200)), None);
+    }
+
+    #[test]
+    fn test_scan_live_keys() {
+        let ttl = SetOptions::new().with_expiration(Duration::from_millis(100));
+        let entries: Vec<(i32, Entry<i32>)> = (0..5)
+            .map(|i| {
+                let options = if i == 2 { ttl.clone() } else { SetOptions::new() };
+                (i, Entry::new(i, &options))
+            })
+            .collect();
+        let iter = || entries.iter().map(|(k, e)| (k, e));
+        let later = Instant::now() + Duration::from_millis(200);
+
+        assert_eq!(scan_live_keys(iter(), 0, 2, later), (2, vec![0, 1]));
+        // 已过期的条目不返回，但计入偏移量
+        assert_eq!(scan_live_keys(iter(), 2, 2, later), (4, vec![3]));
+        assert_eq!(scan_live_keys(iter(), 4, 2, later), (0, vec![4]));
+        assert_eq!(scan_live_keys(iter(), 0, 10, Instant::now()), (0, vec![0, 1, 2, 3, 4]));
+
+        assert_eq!(live_keys(iter(), later, |_| true), vec![0, 1, 3, 4]);
+        assert_eq!(live_keys(iter(), later, |k| k % 2 == 0), vec![0, 4]);
+    }
+
+    #[test]
+    fn test_sweeper_stop() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let count_clone = Arc::clone(&count);
+        let sweeper = Sweeper::spawn(Duration::from_millis(20), move || {
+            count_clone.fetch_add(1, Ordering::SeqCst);
+            true
+        });
+
+        thread::sleep(Duration::from_millis(150));
+        drop(sweeper);
+        let stopped = count.load(Ordering::SeqCst);
+        assert!(stopped >= 2);
+
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(count.load(Ordering::SeqCst), stopped);
+    }
+}
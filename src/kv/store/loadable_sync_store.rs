@@ -1,23 +1,27 @@
 use arc_swap::ArcSwap;
 use garde::Validate;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
+use std::collections::HashSet;
 use std::hash::Hash;
 use std::sync::{Arc, Mutex};
 
-use crate::cfg::{create_trait_from_type_options, TypeOptions};
+use crate::cfg::{create_shared_trait_from_type_options, create_trait_from_type_options, TypeOptions};
 use crate::kv::loader::core::{
     Listener, Loader, LoaderError, Stream, LOAD_STRATEGY_INPLACE, LOAD_STRATEGY_REPLACE,
 };
 use crate::kv::parser::ChangeType;
 
-use super::core::{IsSyncStore, KvError, SetOptions, Store, AsyncStore, SyncStore};
+use super::core::{IsSyncStore, KvError, SetOptions, Store, AsyncStore, Subscription, SyncStore};
+use super::notify::ChangeNotifier;
 
 /// LoadableSyncStore 配置
-#[derive(Debug, Clone, Serialize, Deserialize, SmartDefault, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, SmartDefault, Validate, JsonSchema)]
 #[serde(default)]
 pub struct LoadableSyncStoreConfig {
-    /// 底层 SyncStore 配置
+    /// 底层 SyncStore 配置，支持 `{"$instance": "name"}` 引用共享实例（仅 inplace 策略）
+    /// 独占底层资源的 store（如 DiskStore）同样仅支持 inplace 策略
     #[garde(skip)]
     pub store: TypeOptions,
 
@@ -31,18 +35,27 @@ pub struct LoadableSyncStoreConfig {
     pub load_strategy: String,
 }
 
+// 可原子替换的底层 store
+type SwappableStore<K, V> = Arc<ArcSwap<Arc<dyn SyncStore<K, V>>>>;
+
 /// 可从外部数据源加载数据的 SyncStore 装饰器
 ///
 /// 通过 Loader 监听数据变更，支持两种加载策略：
-/// - InPlace: 增量更新，直接在当前 store 上 set/del
+/// - InPlace: 增量更新，直接在当前 store 上 set/del，全量数据流加载完后删除数据流中不存在的键
 /// - Replace: 全量替换，创建新 store 加载完数据后原子替换旧 store
+///
+/// 支持 `subscribe`：Loader 驱动的更新和直接写入都会通知订阅者。Replace 策略在替换后通知新加载的键，
+/// 以及旧 store 中有但新 store 中没有的键（需要旧 store 支持遍历）
 pub struct LoadableSyncStore<K, V>
 where
     K: Clone + Send + Sync + Eq + Hash + 'static,
     V: Clone + Send + Sync + 'static,
 {
-    store: Arc<ArcSwap<Box<dyn SyncStore<K, V>>>>,
+    store: SwappableStore<K, V>,
     loader: Mutex<Box<dyn Loader<K, V>>>,
+    // 底层 store 为共享实例时，由实例的所有者负责关闭
+    owns_store: bool,
+    notifier: Arc<ChangeNotifier<K>>,
 }
 
 impl<K, V> LoadableSyncStore<K, V>
@@ -59,63 +72,120 @@ where
             ));
         }
 
-        let store: Box<dyn SyncStore<K, V>> = create_trait_from_type_options(&config.store)?;
+        let owns_store = config.store.instance_name().is_none();
+        if !owns_store && config.load_strategy == LOAD_STRATEGY_REPLACE {
+            return Err(anyhow::anyhow!(
+                "load_strategy 'replace' cannot be used with a shared store instance"
+            ));
+        }
+
+        let store: Arc<dyn SyncStore<K, V>> = create_shared_trait_from_type_options(&config.store)?;
+        // 独占底层资源的 store（如 DiskStore 的日志文件），替换时新旧 store 无法同时打开
+        if store.is_exclusive_sync() && config.load_strategy == LOAD_STRATEGY_REPLACE {
+            return Err(anyhow::anyhow!(
+                "load_strategy 'replace' cannot be used with an exclusive store"
+            ));
+        }
         let store = Arc::new(ArcSwap::from_pointee(store));
 
         let mut loader: Box<dyn Loader<K, V>> = create_trait_from_type_options(&config.loader)?;
 
         let load_strategy = config.load_strategy.clone();
         let store_config = config.store.clone();
-        let store_clone = Arc::clone(&store);
-
-        let listener: Listener<K, V> =
-            Arc::new(
-                move |stream: Arc<dyn Stream<K, V>>| match load_strategy.as_str() {
-                    LOAD_STRATEGY_INPLACE => handle_inplace_load(&store_clone, &stream),
-                    LOAD_STRATEGY_REPLACE => {
-                        handle_replace_load(&store_clone, &store_config, &stream)
-                    }
-                    _ => Err(LoaderError::LoadFailed(format!(
-                        "unknown load strategy: {}",
-                        load_strategy
-                    ))),
-                },
-            );
+        // Loader 的监听回调可能比 LoadableSyncStore 活得更久（如全局文件监听），
+        // 只持有弱引用，LoadableSyncStore 释放后底层 store 随之释放（如 DiskStore 的文件锁）
+        let store_weak = Arc::downgrade(&store);
+        let notifier = Arc::new(ChangeNotifier::new());
+        let notifier_clone = Arc::clone(&notifier);
+
+        let listener: Listener<K, V> = Arc::new(move |stream: Arc<dyn Stream<K, V>>| {
+            let Some(store) = store_weak.upgrade() else {
+                return Ok(());
+            };
+            match load_strategy.as_str() {
+                LOAD_STRATEGY_INPLACE => handle_inplace_load(&store, &stream, &notifier_clone),
+                LOAD_STRATEGY_REPLACE => {
+                    handle_replace_load(&store, &store_config, &stream, &notifier_clone)
+                }
+                _ => Err(LoaderError::LoadFailed(format!(
+                    "unknown load strategy: {}",
+                    load_strategy
+                ))),
+            }
+        });
 
         loader.on_change(listener)?;
 
         Ok(Self {
             store,
             loader: Mutex::new(loader),
+            owns_store,
+            notifier,
         })
     }
 }
 
 /// InPlace 策略：增量更新当前 store
 fn handle_inplace_load<K, V>(
-    store: &Arc<ArcSwap<Box<dyn SyncStore<K, V>>>>,
+    store: &SwappableStore<K, V>,
     stream: &Arc<dyn Stream<K, V>>,
+    notifier: &ChangeNotifier<K>,
 ) -> Result<(), LoaderError>
 where
     K: Clone + Send + Sync + Eq + Hash + 'static,
     V: Clone + Send + Sync + 'static,
 {
     let current = store.load();
-    stream.each(&|change_type, key, val| match change_type {
-        ChangeType::Add | ChangeType::Update | ChangeType::Unknown => current
-            .set_sync(&key, &val, &SetOptions::new())
-            .map_err(|e| LoaderError::LoadFailed(format!("set failed: {}", e))),
-        ChangeType::Delete => current
-            .del_sync(&key)
-            .map_err(|e| LoaderError::LoadFailed(format!("del failed: {}", e))),
-    })
+    // 全量数据流记录加载的键，之前加载过但数据源中已经没有的键（如重启期间删除的）需要删除
+    let full = stream.is_full();
+    let loaded_keys = Mutex::new(HashSet::new());
+    stream.each(&|change_type, key, val| {
+        // 与直接写入一样按键实际是否存在通知，而不是数据源给出的变更类型
+        let exists = notifier.is_active() && current.get_sync(&key).is_ok();
+        match change_type {
+            ChangeType::Add | ChangeType::Update | ChangeType::Unknown => {
+                current
+                    .set_sync(&key, &val, &SetOptions::new())
+                    .map_err(|e| LoaderError::LoadFailed(format!("set failed: {}", e)))?;
+                notifier.notify_set(&key, exists);
+                if full {
+                    loaded_keys.lock().unwrap().insert(key);
+                }
+            }
+            ChangeType::Delete => {
+                current
+                    .del_sync(&key)
+                    .map_err(|e| LoaderError::LoadFailed(format!("del failed: {}", e)))?;
+                if exists {
+                    notifier.notify(ChangeType::Delete, &key);
+                }
+            }
+        }
+        Ok(())
+    })?;
+
+    if full {
+        let loaded_keys = loaded_keys.into_inner().unwrap();
+        // 不支持遍历的 store 无法找出多余的键，保持增量语义
+        if let Ok(keys) = current.keys_sync() {
+            for key in keys.into_iter().filter(|key| !loaded_keys.contains(key)) {
+                current
+                    .del_sync(&key)
+                    .map_err(|e| LoaderError::LoadFailed(format!("del failed: {}", e)))?;
+                notifier.notify(ChangeType::Delete, &key);
+            }
+        }
+    }
+
+    Ok(())
 }
 
 /// Replace 策略：创建新 store，加载完数据后原子替换
 fn handle_replace_load<K, V>(
-    store: &Arc<ArcSwap<Box<dyn SyncStore<K, V>>>>,
+    store: &SwappableStore<K, V>,
     store_config: &TypeOptions,
     stream: &Arc<dyn Stream<K, V>>,
+    notifier: &ChangeNotifier<K>,
 ) -> Result<(), LoaderError>
 where
     K: Clone + Send + Sync + Eq + Hash + 'static,
@@ -124,15 +194,38 @@ where
     let new_store: Box<dyn SyncStore<K, V>> = create_trait_from_type_options(store_config)
         .map_err(|e| LoaderError::LoadFailed(format!("failed to create new store: {}", e)))?;
 
+    // 有订阅者时记录加载的键，替换后通知
+    let notify = notifier.is_active();
+    let loaded_keys = Mutex::new(Vec::new());
     stream.each(&|change_type, key, val| match change_type {
-        ChangeType::Add | ChangeType::Update | ChangeType::Unknown => new_store
-            .set_sync(&key, &val, &SetOptions::new())
-            .map_err(|e| LoaderError::LoadFailed(format!("set failed: {}", e))),
+        ChangeType::Add | ChangeType::Update | ChangeType::Unknown => {
+            new_store
+                .set_sync(&key, &val, &SetOptions::new())
+                .map_err(|e| LoaderError::LoadFailed(format!("set failed: {}", e)))?;
+            if notify {
+                loaded_keys.lock().unwrap().push(key);
+            }
+            Ok(())
+        }
         ChangeType::Delete => Ok(()),
     })?;
 
     // 原子替换
-    let old = store.swap(Arc::new(new_store));
+    let new_store: Arc<dyn SyncStore<K, V>> = Arc::from(new_store);
+    let old = store.swap(Arc::new(Arc::clone(&new_store)));
+
+    if notify {
+        for key in loaded_keys.into_inner().unwrap() {
+            notifier.notify_set(&key, old.get_sync(&key).is_ok());
+        }
+        if let Ok(old_keys) = old.keys_sync() {
+            for key in old_keys {
+                if matches!(new_store.get_sync(&key), Err(KvError::KeyNotFound)) {
+                    notifier.notify(ChangeType::Delete, &key);
+                }
+            }
+        }
+    }
     // 关闭旧 store
     let _ = old.close_sync();
 
@@ -152,7 +245,11 @@ where
     V: Clone + Send + Sync + 'static,
 {
     fn set_sync(&self, key: &K, value: &V, options: &SetOptions) -> Result<(), KvError> {
-        self.store.load().set_sync(key, value, options)
+        let store = self.store.load();
+        let exists = self.notifier.is_active() && store.get_sync(key).is_ok();
+        store.set_sync(key, value, options)?;
+        self.notifier.notify_set(key, exists);
+        Ok(())
     }
 
     fn get_sync(&self, key: &K) -> Result<V, KvError> {
@@ -160,7 +257,13 @@ where
     }
 
     fn del_sync(&self, key: &K) -> Result<(), KvError> {
-        self.store.load().del_sync(key)
+        let store = self.store.load();
+        let exists = self.notifier.is_active() && store.get_sync(key).is_ok();
+        store.del_sync(key)?;
+        if exists {
+            self.notifier.notify(ChangeType::Delete, key);
+        }
+        Ok(())
     }
 
     fn batch_set_sync(
@@ -169,7 +272,18 @@ where
         vals: &[V],
         options: &SetOptions,
     ) -> Result<Vec<Result<(), KvError>>, KvError> {
-        self.store.load().batch_set_sync(keys, vals, options)
+        let store = self.store.load();
+        let exists: Vec<bool> = keys
+            .iter()
+            .map(|key| self.notifier.is_active() && store.get_sync(key).is_ok())
+            .collect();
+        let results = store.batch_set_sync(keys, vals, options)?;
+        for ((key, result), exists) in keys.iter().zip(results.iter()).zip(exists) {
+            if result.is_ok() {
+                self.notifier.notify_set(key, exists);
+            }
+        }
+        Ok(results)
     }
 
     fn batch_get_sync(
@@ -180,7 +294,69 @@ where
     }
 
     fn batch_del_sync(&self, keys: &[K]) -> Result<Vec<Result<(), KvError>>, KvError> {
-        self.store.load().batch_del_sync(keys)
+        let store = self.store.load();
+        let exists: Vec<bool> = keys
+            .iter()
+            .map(|key| self.notifier.is_active() && store.get_sync(key).is_ok())
+            .collect();
+        let results = store.batch_del_sync(keys)?;
+        for ((key, result), exists) in keys.iter().zip(results.iter()).zip(exists) {
+            if result.is_ok() && exists {
+                self.notifier.notify(ChangeType::Delete, key);
+            }
+        }
+        Ok(results)
+    }
+
+    fn scan_sync(&self, cursor: u64, count: usize) -> Result<(u64, Vec<K>), KvError> {
+        self.store.load().scan_sync(cursor, count)
+    }
+
+    fn keys_sync(&self) -> Result<Vec<K>, KvError> {
+        self.store.load().keys_sync()
+    }
+
+    fn keys_with_prefix_sync(&self, prefix: &str) -> Result<Vec<K>, KvError>
+    where
+        K: AsRef<str>,
+    {
+        self.store.load().keys_with_prefix_sync(prefix)
+    }
+
+    fn len_sync(&self) -> Result<usize, KvError> {
+        self.store.load().len_sync()
+    }
+
+    fn compare_and_swap_sync(
+        &self,
+        key: &K,
+        expected: &V,
+        value: &V,
+        options: &SetOptions,
+    ) -> Result<(), KvError>
+    where
+        V: PartialEq,
+    {
+        self.store
+            .load()
+            .compare_and_swap_sync(key, expected, value, options)?;
+        self.notifier.notify(ChangeType::Update, key);
+        Ok(())
+    }
+
+    fn incr_sync(&self, key: &K, delta: i64) -> Result<i64, KvError>
+    where
+        V: Into<i64> + TryFrom<i64>,
+    {
+        let store = self.store.load();
+        let exists = self.notifier.is_active() && store.get_sync(key).is_ok();
+        let value = store.incr_sync(key, delta)?;
+        self.notifier.notify_set(key, exists);
+        Ok(value)
+    }
+
+    fn subscribe_sync(&self) -> Result<Subscription<K>, KvError> {
+        Ok(self.notifier.subscribe())
     }
 
     fn close_sync(&self) -> Result<(), KvError> {
@@ -189,8 +365,15 @@ where
             .unwrap()
             .close()
             .map_err(|e| KvError::Other(e.to_string()))?;
+        if !self.owns_store {
+            return Ok(());
+        }
         self.store.load().close_sync()
     }
+
+    fn is_exclusive_sync(&self) -> bool {
+        self.store.load().is_exclusive_sync()
+    }
 }
 
 impl<K, V> From<LoadableSyncStoreConfig> for LoadableSyncStore<K, V>
@@ -415,6 +598,62 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_store_expiration() -> Result<(), anyhow::Error> {
+        let store = make_store_string()?;
+        test_expiration(store).await;
+        Ok(())
+    }
+
+    #[test]
+    fn test_store_expiration_sync() -> Result<(), anyhow::Error> {
+        let store = make_store_string()?;
+        test_expiration_sync(store);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_store_scan() -> Result<(), anyhow::Error> {
+        let store = make_store_string()?;
+        test_scan(store).await;
+        Ok(())
+    }
+
+    #[test]
+    fn test_store_scan_sync() -> Result<(), anyhow::Error> {
+        let store = make_store_string()?;
+        test_scan_sync(store);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_store_compare_and_swap() -> Result<(), anyhow::Error> {
+        let store = make_store_string()?;
+        test_compare_and_swap(store).await;
+        Ok(())
+    }
+
+    #[test]
+    fn test_store_compare_and_swap_sync() -> Result<(), anyhow::Error> {
+        let store = make_store_string()?;
+        test_compare_and_swap_sync(store);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_store_subscribe() -> Result<(), anyhow::Error> {
+        let store = make_store_string()?;
+        test_subscribe(store).await;
+        Ok(())
+    }
+
+    #[test]
+    fn test_store_subscribe_sync() -> Result<(), anyhow::Error> {
+        let store = make_store_string()?;
+        test_subscribe_sync(store);
+        Ok(())
+    }
+
     // ===== 场景测试 =====
 
     #[test]
@@ -459,6 +698,41 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_loadable_sync_store_shared_instance() -> Result<(), anyhow::Error> {
+        use crate::cfg::{create_instance, TypeOptions};
+
+        setup()?;
+
+        let shared = create_instance::<dyn SyncStore<String, String>>(
+            "loadable-shared-store",
+            &TypeOptions::from_json(r#"{"type": "DashMapStore", "options": {}}"#)?,
+        )?;
+
+        let temp_file = create_temp_file(&["k1\tv1", "k2\tv2"]);
+        let mut config = make_config(
+            "DashMapStore",
+            temp_file.path().to_str().unwrap(),
+            "inplace",
+        );
+        config.store = TypeOptions::instance("loadable-shared-store");
+
+        // 数据加载到共享实例中
+        let store = LoadableSyncStore::<String, String>::new(config.clone())?;
+        assert_eq!(shared.get_sync(&"k1".to_string())?, "v1");
+        assert_eq!(store.get_sync(&"k2".to_string())?, "v2");
+
+        // 关闭时不关闭共享实例
+        store.close_sync()?;
+        assert_eq!(shared.get_sync(&"k1".to_string())?, "v1");
+
+        // replace 策略会替换底层 store，不能与共享实例一起使用
+        config.load_strategy = "replace".to_string();
+        assert!(LoadableSyncStore::<String, String>::new(config).is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_create_from_type_options_as_sync_store() -> Result<(), anyhow::Error> {
         use crate::cfg::{create_trait_from_type_options, TypeOptions};
@@ -575,4 +849,189 @@ mod tests {
             }
         }
     }
+
+    // 按顺序产生变更的数据流，用于直接调用加载函数
+    struct VecStream(Vec<(ChangeType, String, String)>);
+
+    impl Stream<String, String> for VecStream {
+        fn each(
+            &self,
+            callback: &dyn Fn(ChangeType, String, String) -> Result<(), LoaderError>,
+        ) -> Result<(), LoaderError> {
+            for (change_type, key, value) in &self.0 {
+                callback(*change_type, key.clone(), value.clone())?;
+            }
+            Ok(())
+        }
+    }
+
+    fn drain(subscription: &mut Subscription<String>) -> Vec<(ChangeType, String)> {
+        std::iter::from_fn(|| subscription.try_recv().ok()).collect()
+    }
+
+    #[test]
+    fn test_subscribe_loader_changes_inplace() -> Result<(), anyhow::Error> {
+        setup()?;
+        let temp_file = create_temp_file(&["k1\tv1", "k2\tv2"]);
+        let config = make_config(
+            "RwLockHashMapStore",
+            temp_file.path().to_str().unwrap(),
+            "inplace",
+        );
+        let store = LoadableSyncStore::<String, String>::new(config)?;
+        let mut subscription = store.subscribe_sync()?;
+
+        let stream: Arc<dyn Stream<String, String>> = Arc::new(VecStream(vec![
+            (ChangeType::Update, "k1".to_string(), "v1-new".to_string()),
+            (ChangeType::Add, "k3".to_string(), "v3".to_string()),
+            (ChangeType::Delete, "k2".to_string(), String::new()),
+            // 按键实际是否存在通知：Unknown 的新键为 Add，删除不存在的键不通知
+            (ChangeType::Unknown, "k4".to_string(), "v4".to_string()),
+            (ChangeType::Unknown, "k4".to_string(), "v4-new".to_string()),
+            (ChangeType::Delete, "missing".to_string(), String::new()),
+        ]));
+        handle_inplace_load(&store.store, &stream, &store.notifier)?;
+
+        assert_eq!(
+            drain(&mut subscription),
+            vec![
+                (ChangeType::Update, "k1".to_string()),
+                (ChangeType::Add, "k3".to_string()),
+                (ChangeType::Delete, "k2".to_string()),
+                (ChangeType::Add, "k4".to_string()),
+                (ChangeType::Update, "k4".to_string()),
+            ]
+        );
+        assert_eq!(store.get_sync(&"k1".to_string())?, "v1-new");
+        Ok(())
+    }
+
+    // 全量数据流
+    struct FullStream(VecStream);
+
+    impl Stream<String, String> for FullStream {
+        fn each(
+            &self,
+            callback: &dyn Fn(ChangeType, String, String) -> Result<(), LoaderError>,
+        ) -> Result<(), LoaderError> {
+            self.0.each(callback)
+        }
+
+        fn is_full(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_inplace_full_stream_deletes_absent_keys() -> Result<(), anyhow::Error> {
+        setup()?;
+        let temp_file = create_temp_file(&["k1\tv1", "k2\tv2"]);
+        let config = make_config(
+            "RwLockHashMapStore",
+            temp_file.path().to_str().unwrap(),
+            "inplace",
+        );
+        let store = LoadableSyncStore::<String, String>::new(config)?;
+        store.set_sync(&"k3".to_string(), &"v3".to_string(), &SetOptions::new())?;
+        let mut subscription = store.subscribe_sync()?;
+
+        // 全量数据流中没有的键被删除
+        let stream: Arc<dyn Stream<String, String>> = Arc::new(FullStream(VecStream(vec![(
+            ChangeType::Add,
+            "k1".to_string(),
+            "v1".to_string(),
+        )])));
+        handle_inplace_load(&store.store, &stream, &store.notifier)?;
+
+        let events = drain(&mut subscription);
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0], (ChangeType::Update, "k1".to_string()));
+        assert!(events.contains(&(ChangeType::Delete, "k2".to_string())));
+        assert!(events.contains(&(ChangeType::Delete, "k3".to_string())));
+        assert_eq!(store.keys_sync()?, vec!["k1".to_string()]);
+
+        // 增量数据流不删除其他键
+        let stream: Arc<dyn Stream<String, String>> = Arc::new(VecStream(vec![(
+            ChangeType::Add,
+            "k4".to_string(),
+            "v4".to_string(),
+        )]));
+        handle_inplace_load(&store.store, &stream, &store.notifier)?;
+        assert_eq!(store.len_sync()?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_subscribe_loader_changes_replace() -> Result<(), anyhow::Error> {
+        setup()?;
+        let temp_file = create_temp_file(&["k1\tv1", "k2\tv2"]);
+        let config = make_config(
+            "DashMapStore",
+            temp_file.path().to_str().unwrap(),
+            "replace",
+        );
+        let store = LoadableSyncStore::<String, String>::new(config.clone())?;
+        let mut subscription = store.subscribe_sync()?;
+
+        // 全量替换后 k1 更新，k3 新增，k2 被删除
+        let stream: Arc<dyn Stream<String, String>> = Arc::new(VecStream(vec![
+            (ChangeType::Add, "k1".to_string(), "v1-new".to_string()),
+            (ChangeType::Add, "k3".to_string(), "v3".to_string()),
+        ]));
+        handle_replace_load(&store.store, &config.store, &stream, &store.notifier)?;
+
+        assert_eq!(
+            drain(&mut subscription),
+            vec![
+                (ChangeType::Update, "k1".to_string()),
+                (ChangeType::Add, "k3".to_string()),
+                (ChangeType::Delete, "k2".to_string()),
+            ]
+        );
+        assert!(matches!(
+            store.get_sync(&"k2".to_string()),
+            Err(KvError::KeyNotFound)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_subscribe_replace_more_than_scan_batch() -> Result<(), anyhow::Error> {
+        use crate::kv::store::core::SCAN_BATCH_SIZE;
+
+        setup()?;
+        // 旧 store 的键超过一批，替换时需要遍历全部旧键找出被删除的键
+        let total = SCAN_BATCH_SIZE + 10;
+        let lines: Vec<String> = (0..total).map(|i| format!("k{}\tv{}", i, i)).collect();
+        let lines: Vec<&str> = lines.iter().map(|line| line.as_str()).collect();
+        let temp_file = create_temp_file(&lines);
+        let config = make_config(
+            "DashMapStore",
+            temp_file.path().to_str().unwrap(),
+            "replace",
+        );
+        let store = LoadableSyncStore::<String, String>::new(config.clone())?;
+        assert_eq!(store.len_sync()?, total);
+        let mut subscription = store.subscribe_sync()?;
+
+        let stream: Arc<dyn Stream<String, String>> = Arc::new(VecStream(vec![(
+            ChangeType::Add,
+            "k0".to_string(),
+            "v0-new".to_string(),
+        )]));
+        handle_replace_load(&store.store, &config.store, &stream, &store.notifier)?;
+
+        let events = drain(&mut subscription);
+        assert_eq!(events[0], (ChangeType::Update, "k0".to_string()));
+        let deleted: std::collections::HashSet<_> = events[1..]
+            .iter()
+            .map(|(change_type, key)| {
+                assert_eq!(*change_type, ChangeType::Delete);
+                key.clone()
+            })
+            .collect();
+        assert_eq!(deleted.len(), total - 1);
+        assert_eq!(store.len_sync()?, 1);
+        Ok(())
+    }
 }
@@ -0,0 +1,1067 @@
+use garde::Validate;
+use lru::LruCache;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use smart_default::SmartDefault;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use super::core::{
+    incr_value, IsSyncStore, KvError, SetOptions, Store, AsyncStore, Subscription, SyncStore,
+};
+use super::expiry::{live_keys, scan_live_keys, Entry};
+use super::notify::ChangeNotifier;
+use crate::kv::parser::ChangeType;
+
+/// 淘汰策略
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, SmartDefault, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum EvictionPolicy {
+    /// 淘汰最近最少使用的键
+    #[default]
+    Lru,
+    /// W-TinyLFU：新键先进入窗口 LRU，被窗口淘汰后与主缓存的淘汰候选比较访问频率，频率更高者保留
+    TinyLfu,
+}
+
+/// LruStore 配置结构体
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, SmartDefault, Validate, JsonSchema)]
+#[serde(default)]
+pub struct LruStoreConfig {
+    /// 最大键数量
+    #[default = 10000]
+    #[garde(range(min = 1))]
+    pub max_entries: usize,
+
+    /// 淘汰策略: "lru" 或 "tinylfu"
+    #[garde(skip)]
+    pub policy: EvictionPolicy,
+}
+
+/// 键被移除的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemovalCause {
+    /// 超出容量被淘汰，包括 TinyLFU 拒绝准入的新键
+    Size,
+    /// 读取时发现已过期
+    Expired,
+}
+
+/// LruStore 统计指标
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LruStoreMetrics {
+    /// 命中次数
+    pub hits: u64,
+    /// 未命中次数（包括已过期）
+    pub misses: u64,
+    /// 写入次数
+    pub insertions: u64,
+    /// 超出容量淘汰的键数量
+    pub evictions: u64,
+    /// TinyLFU 拒绝准入的键数量（包含在 evictions 中）
+    pub rejections: u64,
+    /// 读取时删除的过期键数量
+    pub expirations: u64,
+}
+
+type Weigher<K, V> = Arc<dyn Fn(&K, &V) -> u64 + Send + Sync>;
+type EvictionListener<K, V> = Arc<dyn Fn(&K, &V, RemovalCause) + Send + Sync>;
+
+// Count-Min Sketch 的行数
+const SKETCH_DEPTH: usize = 4;
+// 每行使用不同的种子对哈希值再散列，使各行的冲突相互独立
+const SKETCH_SEEDS: [u64; SKETCH_DEPTH] = [
+    0xc3a5_c85c_97cb_3127,
+    0xb492_b66f_be98_f273,
+    0x9ae1_6a3b_2f90_404f,
+    0xcbf2_9ce4_8422_2325,
+];
+
+// 计数上限，4 bit 足以区分冷热
+const SKETCH_MAX_COUNT: u8 = 15;
+
+// SplitMix64 的混合函数
+fn mix64(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// 近似访问频率统计（Count-Min Sketch）
+///
+/// 累计计数达到采样上限后所有计数减半，使历史热度随时间衰减
+struct FrequencySketch {
+    table: Vec<u8>,
+    mask: usize,
+    hasher: RandomState,
+    additions: usize,
+    sample_size: usize,
+}
+
+impl FrequencySketch {
+    fn new(capacity: usize) -> Self {
+        let width = capacity.max(16).next_power_of_two();
+        Self {
+            table: vec![0; width * SKETCH_DEPTH],
+            mask: width - 1,
+            hasher: RandomState::new(),
+            additions: 0,
+            sample_size: width * 10,
+        }
+    }
+
+    fn indexes<K: Hash>(&self, key: &K) -> [usize; SKETCH_DEPTH] {
+        let hash = self.hasher.hash_one(key);
+        let width = self.mask + 1;
+        let mut indexes = [0; SKETCH_DEPTH];
+        for (row, index) in indexes.iter_mut().enumerate() {
+            *index = row * width + (mix64(hash ^ SKETCH_SEEDS[row]) as usize & self.mask);
+        }
+        indexes
+    }
+
+    fn frequency<K: Hash>(&self, key: &K) -> u8 {
+        self.indexes(key)
+            .iter()
+            .map(|&i| self.table[i])
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn increment<K: Hash>(&mut self, key: &K) {
+        let mut added = false;
+        for i in self.indexes(key) {
+            if self.table[i] < SKETCH_MAX_COUNT {
+                self.table[i] += 1;
+                added = true;
+            }
+        }
+
+        if added {
+            self.additions += 1;
+            if self.additions >= self.sample_size {
+                self.table.iter_mut().for_each(|count| *count >>= 1);
+                self.additions /= 2;
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Limits {
+    max_entries: usize,
+    max_weight: Option<u64>,
+    // W-TinyLFU 窗口的键数量
+    window_size: usize,
+}
+
+struct Slot<V> {
+    entry: Entry<V>,
+    weight: u64,
+}
+
+// 被移除的键，释放锁之后再通知监听器
+type Removal<K, V> = (K, V, RemovalCause);
+
+struct State<K, V>
+where
+    K: Hash + Eq,
+{
+    // TinyLFU 策略下新键先进入窗口，LRU 策略下不使用
+    window: LruCache<K, Slot<V>>,
+    main: LruCache<K, Slot<V>>,
+    weight: u64,
+    sketch: Option<FrequencySketch>,
+    metrics: LruStoreMetrics,
+}
+
+impl<K, V> State<K, V>
+where
+    K: Clone + Hash + Eq,
+    V: Clone,
+{
+    // 窗口和主缓存中的所有条目，不调整 LRU 顺序
+    fn entries(&self) -> impl Iterator<Item = (&K, &Entry<V>)> {
+        self.window
+            .iter()
+            .chain(self.main.iter())
+            .map(|(key, slot)| (key, &slot.entry))
+    }
+
+    fn record_access(&mut self, key: &K) {
+        if let Some(sketch) = &mut self.sketch {
+            sketch.increment(key);
+        }
+    }
+
+    // 不调整 LRU 顺序，也不记录访问
+    fn peek_live(&self, key: &K, now: Instant) -> Option<&Entry<V>> {
+        self.window
+            .peek(key)
+            .or_else(|| self.main.peek(key))
+            .map(|slot| &slot.entry)
+            .filter(|entry| !entry.is_expired(now))
+    }
+
+    fn contains_live(&self, key: &K, now: Instant) -> bool {
+        self.peek_live(key, now).is_some()
+    }
+
+    fn get(&mut self, key: &K, now: Instant, removed: &mut Vec<Removal<K, V>>) -> Option<V> {
+        self.record_access(key);
+        let expired = match self.window.get(key).or_else(|| self.main.get(key)) {
+            Some(slot) if !slot.entry.is_expired(now) => {
+                self.metrics.hits += 1;
+                return Some(slot.entry.value.clone());
+            }
+            Some(_) => true,
+            None => false,
+        };
+
+        self.metrics.misses += 1;
+        if expired {
+            if let Some(slot) = self.remove(key) {
+                self.metrics.expirations += 1;
+                removed.push((key.clone(), slot.entry.value, RemovalCause::Expired));
+            }
+        }
+        None
+    }
+
+    fn remove(&mut self, key: &K) -> Option<Slot<V>> {
+        let slot = self.window.pop(key).or_else(|| self.main.pop(key))?;
+        self.weight -= slot.weight;
+        Some(slot)
+    }
+
+    fn insert(&mut self, key: K, slot: Slot<V>, limits: &Limits, removed: &mut Vec<Removal<K, V>>) {
+        self.record_access(&key);
+        self.metrics.insertions += 1;
+        self.weight += slot.weight;
+
+        // 已存在的键原地更新
+        if let Some(existing) = self.window.get_mut(&key).or_else(|| self.main.get_mut(&key)) {
+            let old_weight = existing.weight;
+            *existing = slot;
+            self.weight -= old_weight;
+        } else if self.sketch.is_some() {
+            self.window.push(key, slot);
+        } else {
+            self.main.push(key, slot);
+        }
+
+        self.evict(limits, removed);
+    }
+
+    fn over_capacity(&self, limits: &Limits, extra_entries: usize, extra_weight: u64) -> bool {
+        self.window.len() + self.main.len() + extra_entries > limits.max_entries
+            || limits
+                .max_weight
+                .is_some_and(|max_weight| self.weight + extra_weight > max_weight)
+    }
+
+    fn evict(&mut self, limits: &Limits, removed: &mut Vec<Removal<K, V>>) {
+        if self.sketch.is_some() {
+            while self.window.len() > limits.window_size {
+                match self.window.pop_lru() {
+                    Some((key, slot)) => {
+                        self.weight -= slot.weight;
+                        self.admit(key, slot, limits, removed);
+                    }
+                    None => break,
+                }
+            }
+        }
+
+        while self.over_capacity(limits, 0, 0) {
+            match self.main.pop_lru().or_else(|| self.window.pop_lru()) {
+                Some((key, slot)) => {
+                    self.weight -= slot.weight;
+                    self.evicted(key, slot, removed);
+                }
+                None => break,
+            }
+        }
+    }
+
+    // 窗口淘汰的候选键与主缓存的淘汰候选比较访问频率，频率更高者保留
+    fn admit(&mut self, key: K, slot: Slot<V>, limits: &Limits, removed: &mut Vec<Removal<K, V>>) {
+        while self.over_capacity(limits, 1, slot.weight) {
+            let admitted = match (self.main.peek_lru(), &self.sketch) {
+                (Some((victim, _)), Some(sketch)) => sketch.frequency(&key) > sketch.frequency(victim),
+                _ => break,
+            };
+
+            if !admitted {
+                self.metrics.rejections += 1;
+                self.evicted(key, slot, removed);
+                return;
+            }
+
+            if let Some((victim, victim_slot)) = self.main.pop_lru() {
+                self.weight -= victim_slot.weight;
+                self.evicted(victim, victim_slot, removed);
+            }
+        }
+
+        self.weight += slot.weight;
+        self.main.push(key, slot);
+    }
+
+    fn evicted(&mut self, key: K, slot: Slot<V>, removed: &mut Vec<Removal<K, V>>) {
+        self.metrics.evictions += 1;
+        removed.push((key, slot.entry.value, RemovalCause::Size));
+    }
+}
+
+/// 有容量上限的内存 KV 存储，可作为 RedisStore 前的本地缓存
+///
+/// 按 `max_entries` 限制容量（通过 `new_with_weigher` 创建时同时按总权重限制），超出时按 `policy` 淘汰：
+/// - `lru`: 淘汰最近最少使用的键
+/// - `tinylfu`: W-TinyLFU，新键进入 1% 容量的窗口，被窗口淘汰后只有访问频率高于主缓存淘汰候选时才被保留，
+///   能够抵抗一次性扫描对热点数据的冲刷
+///
+/// 支持 `SetOptions.expiration`，已过期的键在读取时删除，未被读取的过期键随容量淘汰
+///
+/// 支持 `subscribe`：写入和删除时通知订阅者，容量淘汰和过期淘汰不产生事件（可以通过淘汰监听器获取）
+///
+/// 权重函数、总权重和淘汰监听器无法通过配置指定，需要在代码中通过 `new_with_weigher`、`with_eviction_listener` 设置，
+/// 可以配合 `register_instance` 在配置中通过 `$instance` 引用
+pub struct LruStore<K, V>
+where
+    K: Clone + Send + Sync + Eq + Hash,
+    V: Clone + Send + Sync,
+{
+    state: Mutex<State<K, V>>,
+    limits: Limits,
+    weigher: Option<Weigher<K, V>>,
+    listener: Option<EvictionListener<K, V>>,
+    changes: ChangeNotifier<K>,
+}
+
+impl<K, V> LruStore<K, V>
+where
+    K: Clone + Send + Sync + Eq + Hash,
+    V: Clone + Send + Sync,
+{
+    /// 创建新的 LruStore 实例，每个键的权重为 1
+    pub fn new(config: LruStoreConfig) -> Self {
+        Self::build(config, None)
+    }
+
+    /// 使用键的权重函数创建 LruStore 实例，总权重超过 `max_weight` 时同样淘汰
+    ///
+    /// # 示例
+    /// ```ignore
+    /// let store = LruStore::<String, String>::new_with_weigher(
+    ///     LruStoreConfig::default(),
+    ///     64 * 1024 * 1024,
+    ///     |k, v| (k.len() + v.len()) as u64,
+    /// );
+    /// ```
+    pub fn new_with_weigher<F>(config: LruStoreConfig, max_weight: u64, weigher: F) -> Self
+    where
+        F: Fn(&K, &V) -> u64 + Send + Sync + 'static,
+    {
+        Self::build(config, Some((max_weight, Arc::new(weigher))))
+    }
+
+    fn build(config: LruStoreConfig, weight: Option<(u64, Weigher<K, V>)>) -> Self {
+        let max_entries = config.max_entries.max(1);
+        let sketch = match config.policy {
+            EvictionPolicy::Lru => None,
+            EvictionPolicy::TinyLfu => Some(FrequencySketch::new(max_entries)),
+        };
+
+        Self {
+            state: Mutex::new(State {
+                window: LruCache::unbounded(),
+                main: LruCache::unbounded(),
+                weight: 0,
+                sketch,
+                metrics: LruStoreMetrics::default(),
+            }),
+            limits: Limits {
+                max_entries,
+                max_weight: weight.as_ref().map(|(max_weight, _)| *max_weight),
+                window_size: (max_entries / 100).max(1),
+            },
+            weigher: weight.map(|(_, weigher)| weigher),
+            listener: None,
+            changes: ChangeNotifier::new(),
+        }
+    }
+
+    /// 设置淘汰监听器，键因容量或过期被移除时调用（不持有锁，`del` 删除的键不会通知）
+    pub fn with_eviction_listener<F>(mut self, listener: F) -> Self
+    where
+        F: Fn(&K, &V, RemovalCause) + Send + Sync + 'static,
+    {
+        self.listener = Some(Arc::new(listener));
+        self
+    }
+
+    /// 获取统计指标快照
+    pub fn metrics(&self) -> LruStoreMetrics {
+        self.state.lock().unwrap().metrics
+    }
+
+    /// 当前键数量（包括尚未删除的过期键）
+    pub fn entry_count(&self) -> usize {
+        let state = self.state.lock().unwrap();
+        state.window.len() + state.main.len()
+    }
+
+    /// 当前总权重
+    pub fn weighted_size(&self) -> u64 {
+        self.state.lock().unwrap().weight
+    }
+
+    fn new_slot(&self, key: &K, value: &V, options: &SetOptions) -> Slot<V> {
+        let weight = self.weigher.as_ref().map_or(1, |weigher| weigher(key, value));
+        Slot {
+            entry: Entry::new(value.clone(), options),
+            weight,
+        }
+    }
+
+    /// 删除键，删除未过期的键时通知订阅者
+    fn remove(&self, state: &mut State<K, V>, key: &K, now: Instant) {
+        if state
+            .remove(key)
+            .is_some_and(|slot| !slot.entry.is_expired(now))
+        {
+            self.changes.notify(ChangeType::Delete, key);
+        }
+    }
+
+    fn notify(&self, removed: Vec<Removal<K, V>>) {
+        if let Some(listener) = &self.listener {
+            for (key, value, cause) in removed {
+                listener(&key, &value, cause);
+            }
+        }
+    }
+}
+
+impl<K, V> Default for LruStore<K, V>
+where
+    K: Clone + Send + Sync + Eq + Hash,
+    V: Clone + Send + Sync,
+{
+    fn default() -> Self {
+        Self::new(LruStoreConfig::default())
+    }
+}
+
+// 标记为同步存储，自动获得 Store 异步接口
+impl<K, V> IsSyncStore for LruStore<K, V>
+where
+    K: Clone + Send + Sync + Eq + Hash,
+    V: Clone + Send + Sync,
+{
+}
+
+impl<K, V> SyncStore<K, V> for LruStore<K, V>
+where
+    K: Clone + Send + Sync + Eq + Hash,
+    V: Clone + Send + Sync,
+{
+    fn set_sync(&self, key: &K, value: &V, options: &SetOptions) -> Result<(), KvError> {
+        let slot = self.new_slot(key, value, options);
+        let mut removed = Vec::new();
+        let result = {
+            let mut state = self.state.lock().unwrap();
+            let exists = state.contains_live(key, Instant::now());
+            let result = options.check_condition(exists);
+            if result.is_ok() {
+                state.insert(key.clone(), slot, &self.limits, &mut removed);
+                self.changes.notify_set(key, exists);
+            }
+            result
+        };
+
+        self.notify(removed);
+        result
+    }
+
+    fn get_sync(&self, key: &K) -> Result<V, KvError> {
+        let mut removed = Vec::new();
+        let value = self
+            .state
+            .lock()
+            .unwrap()
+            .get(key, Instant::now(), &mut removed);
+
+        self.notify(removed);
+        value.ok_or(KvError::KeyNotFound)
+    }
+
+    fn del_sync(&self, key: &K) -> Result<(), KvError> {
+        let mut state = self.state.lock().unwrap();
+        self.remove(&mut state, key, Instant::now());
+        Ok(())
+    }
+
+    fn batch_set_sync(
+        &self,
+        keys: &[K],
+        vals: &[V],
+        options: &SetOptions,
+    ) -> Result<Vec<Result<(), KvError>>, KvError> {
+        if keys.len() != vals.len() {
+            return Err(KvError::Other(
+                "Keys and values length mismatch".to_string(),
+            ));
+        }
+
+        let slots: Vec<Slot<V>> = keys
+            .iter()
+            .zip(vals.iter())
+            .map(|(key, value)| self.new_slot(key, value, options))
+            .collect();
+        let now = Instant::now();
+        let mut removed = Vec::new();
+        let mut results = Vec::with_capacity(keys.len());
+
+        {
+            let mut state = self.state.lock().unwrap();
+            for (key, slot) in keys.iter().zip(slots) {
+                // 检查 if_not_exist、if_exist 条件
+                let exists = state.contains_live(key, now);
+                if let Err(e) = options.check_condition(exists) {
+                    results.push(Err(e));
+                    continue;
+                }
+
+                state.insert(key.clone(), slot, &self.limits, &mut removed);
+                self.changes.notify_set(key, exists);
+                results.push(Ok(()));
+            }
+        }
+
+        self.notify(removed);
+        Ok(results)
+    }
+
+    fn batch_get_sync(
+        &self,
+        keys: &[K],
+    ) -> Result<(Vec<Option<V>>, Vec<Option<KvError>>), KvError> {
+        let now = Instant::now();
+        let mut removed = Vec::new();
+        let mut values = Vec::with_capacity(keys.len());
+        let mut errors = Vec::with_capacity(keys.len());
+
+        {
+            let mut state = self.state.lock().unwrap();
+            for key in keys {
+                match state.get(key, now, &mut removed) {
+                    Some(value) => {
+                        values.push(Some(value));
+                        errors.push(None);
+                    }
+                    None => {
+                        values.push(None);
+                        errors.push(Some(KvError::KeyNotFound));
+                    }
+                }
+            }
+        }
+
+        self.notify(removed);
+        Ok((values, errors))
+    }
+
+    fn batch_del_sync(&self, keys: &[K]) -> Result<Vec<Result<(), KvError>>, KvError> {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        let mut results = Vec::with_capacity(keys.len());
+
+        for key in keys {
+            self.remove(&mut state, key, now);
+            results.push(Ok(()));
+        }
+
+        Ok(results)
+    }
+
+    fn close_sync(&self) -> Result<(), KvError> {
+        // 不清空数据，只做资源清理
+        Ok(())
+    }
+
+    // 读取会调整 LRU 顺序，遍历期间有读写时可能返回重复的键或遗漏键
+    fn scan_sync(&self, cursor: u64, count: usize) -> Result<(u64, Vec<K>), KvError> {
+        let state = self.state.lock().unwrap();
+        Ok(scan_live_keys(state.entries(), cursor, count, Instant::now()))
+    }
+
+    fn keys_sync(&self) -> Result<Vec<K>, KvError> {
+        let state = self.state.lock().unwrap();
+        Ok(live_keys(state.entries(), Instant::now(), |_| true))
+    }
+
+    fn keys_with_prefix_sync(&self, prefix: &str) -> Result<Vec<K>, KvError>
+    where
+        K: AsRef<str>,
+    {
+        let state = self.state.lock().unwrap();
+        Ok(live_keys(state.entries(), Instant::now(), |key| {
+            key.as_ref().starts_with(prefix)
+        }))
+    }
+
+    fn len_sync(&self) -> Result<usize, KvError> {
+        let now = Instant::now();
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .window
+            .iter()
+            .chain(state.main.iter())
+            .filter(|(_, slot)| !slot.entry.is_expired(now))
+            .count())
+    }
+
+    fn compare_and_swap_sync(
+        &self,
+        key: &K,
+        expected: &V,
+        value: &V,
+        options: &SetOptions,
+    ) -> Result<(), KvError>
+    where
+        V: PartialEq,
+    {
+        let slot = self.new_slot(key, value, options);
+        let mut removed = Vec::new();
+        let result = {
+            let mut state = self.state.lock().unwrap();
+            let matched = state
+                .peek_live(key, Instant::now())
+                .is_some_and(|entry| entry.value == *expected);
+            if matched {
+                state.insert(key.clone(), slot, &self.limits, &mut removed);
+                self.changes.notify(ChangeType::Update, key);
+                Ok(())
+            } else {
+                Err(KvError::ConditionFailed)
+            }
+        };
+
+        self.notify(removed);
+        result
+    }
+
+    fn incr_sync(&self, key: &K, delta: i64) -> Result<i64, KvError>
+    where
+        V: Into<i64> + TryFrom<i64>,
+    {
+        let mut removed = Vec::new();
+        let result = {
+            let mut state = self.state.lock().unwrap();
+            // 保留原有的过期时间，已过期的键从 0 开始
+            let live = state.peek_live(key, Instant::now());
+            let expire_at = live.and_then(|entry| entry.expire_at);
+            let exists = live.is_some();
+            incr_value(live.map(|entry| &entry.value), delta).map(|(next, value)| {
+                let mut slot = self.new_slot(key, &value, &SetOptions::new());
+                slot.entry.expire_at = expire_at;
+                state.insert(key.clone(), slot, &self.limits, &mut removed);
+                self.changes.notify_set(key, exists);
+                next
+            })
+        };
+
+        self.notify(removed);
+        result
+    }
+
+    fn subscribe_sync(&self) -> Result<Subscription<K>, KvError> {
+        Ok(self.changes.subscribe())
+    }
+}
+
+// 为 LruStore 实现 From trait
+impl<K, V> From<LruStoreConfig> for LruStore<K, V>
+where
+    K: Clone + Send + Sync + Eq + Hash + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn from(config: LruStoreConfig) -> Self {
+        LruStore::new(config)
+    }
+}
+
+impl<K, V> From<Box<LruStore<K, V>>> for Box<dyn AsyncStore<K, V>>
+where
+    K: Clone + Send + Sync + Eq + Hash + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn from(source: Box<LruStore<K, V>>) -> Self {
+        source as Box<dyn AsyncStore<K, V>>
+    }
+}
+
+impl<K, V> From<Box<LruStore<K, V>>> for Box<dyn SyncStore<K, V>>
+where
+    K: Clone + Send + Sync + Eq + Hash + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn from(source: Box<LruStore<K, V>>) -> Self {
+        source as Box<dyn SyncStore<K, V>>
+    }
+}
+
+impl<K, V> From<Box<LruStore<K, V>>> for Box<dyn Store<K, V>>
+where
+    K: Clone + Send + Sync + Eq + Hash + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn from(source: Box<LruStore<K, V>>) -> Self {
+        source as Box<dyn Store<K, V>>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kv::store::common_tests::*;
+    use std::time::Duration;
+
+    fn make_store<V: Clone + Send + Sync>(max_entries: usize, policy: EvictionPolicy) -> LruStore<String, V> {
+        LruStore::new(LruStoreConfig {
+            max_entries,
+            policy,
+            ..Default::default()
+        })
+    }
+
+    // ========== 公共测试 ==========
+
+    #[tokio::test]
+    async fn test_store_set() {
+        let store = LruStore::<String, String>::new(LruStoreConfig::default());
+        test_set(store).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_get() {
+        let store = LruStore::<String, String>::new(LruStoreConfig::default());
+        test_get(store).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_del() {
+        let store = LruStore::<String, String>::new(LruStoreConfig::default());
+        test_del(store).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_batch_set() {
+        let store = LruStore::<String, i32>::new(LruStoreConfig::default());
+        test_batch_set(store).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_batch_get() {
+        let store = LruStore::<String, i32>::new(LruStoreConfig::default());
+        test_batch_get(store).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_batch_del() {
+        let store = LruStore::<String, i32>::new(LruStoreConfig::default());
+        test_batch_del(store).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_close() {
+        let store = LruStore::<String, i32>::new(LruStoreConfig::default());
+        test_close(store).await;
+    }
+
+    #[test]
+    fn test_store_set_sync() {
+        let store = LruStore::<String, String>::new(LruStoreConfig::default());
+        test_set_sync(store);
+    }
+
+    #[test]
+    fn test_store_get_sync() {
+        let store = LruStore::<String, String>::new(LruStoreConfig::default());
+        test_get_sync(store);
+    }
+
+    #[test]
+    fn test_store_del_sync() {
+        let store = LruStore::<String, String>::new(LruStoreConfig::default());
+        test_del_sync(store);
+    }
+
+    #[test]
+    fn test_store_batch_set_sync() {
+        let store = LruStore::<String, i32>::new(LruStoreConfig::default());
+        test_batch_set_sync(store);
+    }
+
+    #[test]
+    fn test_store_batch_get_sync() {
+        let store = LruStore::<String, i32>::new(LruStoreConfig::default());
+        test_batch_get_sync(store);
+    }
+
+    #[test]
+    fn test_store_batch_del_sync() {
+        let store = LruStore::<String, i32>::new(LruStoreConfig::default());
+        test_batch_del_sync(store);
+    }
+
+    #[test]
+    fn test_store_close_sync() {
+        let store = LruStore::<String, i32>::new(LruStoreConfig::default());
+        test_close_sync(store);
+    }
+
+    #[tokio::test]
+    async fn test_store_expiration() {
+        let store = LruStore::<String, String>::new(LruStoreConfig::default());
+        test_expiration(store).await;
+    }
+
+    #[test]
+    fn test_store_expiration_sync() {
+        let store = LruStore::<String, String>::new(LruStoreConfig::default());
+        test_expiration_sync(store);
+    }
+
+    #[tokio::test]
+    async fn test_store_scan() {
+        let store = LruStore::<String, String>::new(LruStoreConfig::default());
+        test_scan(store).await;
+    }
+
+    #[test]
+    fn test_store_scan_sync() {
+        let store = LruStore::<String, String>::new(LruStoreConfig::default());
+        test_scan_sync(store);
+    }
+
+    #[tokio::test]
+    async fn test_store_compare_and_swap() {
+        let store = LruStore::<String, String>::new(LruStoreConfig::default());
+        test_compare_and_swap(store).await;
+    }
+
+    #[test]
+    fn test_store_compare_and_swap_sync() {
+        let store = LruStore::<String, String>::new(LruStoreConfig::default());
+        test_compare_and_swap_sync(store);
+    }
+
+    #[tokio::test]
+    async fn test_store_incr() {
+        let store = LruStore::<String, i64>::new(LruStoreConfig::default());
+        test_incr(store).await;
+    }
+
+    #[test]
+    fn test_store_incr_sync() {
+        let store = LruStore::<String, i64>::new(LruStoreConfig::default());
+        test_incr_sync(store);
+    }
+
+    #[tokio::test]
+    async fn test_store_subscribe() {
+        let store = LruStore::<String, String>::new(LruStoreConfig::default());
+        test_subscribe(store).await;
+    }
+
+    #[test]
+    fn test_store_subscribe_sync() {
+        let store = LruStore::<String, String>::new(LruStoreConfig::default());
+        test_subscribe_sync(store);
+    }
+
+    #[test]
+    fn test_tinylfu_store_common() {
+        test_set_sync(make_store::<String>(100, EvictionPolicy::TinyLfu));
+        test_get_sync(make_store::<String>(100, EvictionPolicy::TinyLfu));
+        test_batch_set_sync(make_store::<i32>(100, EvictionPolicy::TinyLfu));
+        test_batch_get_sync(make_store::<i32>(100, EvictionPolicy::TinyLfu));
+        test_expiration_sync(make_store::<String>(100, EvictionPolicy::TinyLfu));
+    }
+
+    // ========== 场景测试 ==========
+
+    #[test]
+    fn test_store_from_json5_config() {
+        let config: LruStoreConfig = json5::from_str(
+            r#"{
+            max_entries: 100,
+            policy: "tinylfu",
+        }"#,
+        )
+        .unwrap();
+        assert_eq!(config.max_entries, 100);
+        assert_eq!(config.policy, EvictionPolicy::TinyLfu);
+
+        let empty_config: LruStoreConfig = json5::from_str("{}").unwrap();
+        assert_eq!(empty_config.max_entries, 10000);
+        assert_eq!(empty_config.policy, EvictionPolicy::Lru);
+
+        let invalid_config = LruStoreConfig {
+            max_entries: 0,
+            ..Default::default()
+        };
+        assert!(invalid_config.validate().is_err());
+    }
+
+    #[test]
+    fn test_lru_eviction_order() {
+        let store = make_store::<i32>(3, EvictionPolicy::Lru);
+        for i in 0..3 {
+            store.set_sync(&format!("key{}", i), &i, &SetOptions::new()).unwrap();
+        }
+
+        // 访问 key0 后，key1 成为最近最少使用的键
+        store.get_sync(&"key0".to_string()).unwrap();
+        store.set_sync(&"key3".to_string(), &3, &SetOptions::new()).unwrap();
+
+        assert_eq!(store.entry_count(), 3);
+        assert!(matches!(store.get_sync(&"key1".to_string()), Err(KvError::KeyNotFound)));
+        assert_eq!(store.get_sync(&"key0".to_string()).unwrap(), 0);
+        assert_eq!(store.get_sync(&"key3".to_string()).unwrap(), 3);
+
+        // 覆盖已存在的键不触发淘汰
+        store.set_sync(&"key3".to_string(), &30, &SetOptions::new()).unwrap();
+        assert_eq!(store.entry_count(), 3);
+        assert_eq!(store.metrics().evictions, 1);
+    }
+
+    #[test]
+    fn test_max_weight() {
+        let store = LruStore::<String, String>::new_with_weigher(
+            LruStoreConfig::default(),
+            10,
+            |_, v| v.len() as u64,
+        );
+
+        store.set_sync(&"a".to_string(), &"1234".to_string(), &SetOptions::new()).unwrap();
+        store.set_sync(&"b".to_string(), &"1234".to_string(), &SetOptions::new()).unwrap();
+        assert_eq!(store.weighted_size(), 8);
+
+        // 超出总权重，淘汰最早写入的 a
+        store.set_sync(&"c".to_string(), &"1234".to_string(), &SetOptions::new()).unwrap();
+        assert_eq!(store.weighted_size(), 8);
+        assert!(store.get_sync(&"a".to_string()).is_err());
+
+        // 覆盖时按新值重新计算权重
+        store.set_sync(&"c".to_string(), &"12".to_string(), &SetOptions::new()).unwrap();
+        assert_eq!(store.weighted_size(), 6);
+
+        // 单个值超过总权重时不保留
+        store.set_sync(&"d".to_string(), &"12345678901".to_string(), &SetOptions::new()).unwrap();
+        assert!(store.get_sync(&"d".to_string()).is_err());
+        assert!(store.weighted_size() <= 10);
+    }
+
+    #[test]
+    fn test_eviction_listener_and_metrics() {
+        let evicted = Arc::new(Mutex::new(Vec::new()));
+        let evicted_clone = Arc::clone(&evicted);
+        let store = make_store::<i32>(2, EvictionPolicy::Lru).with_eviction_listener(move |k, v, cause| {
+            evicted_clone.lock().unwrap().push((k.clone(), *v, cause));
+        });
+
+        store.set_sync(&"key1".to_string(), &1, &SetOptions::new()).unwrap();
+        store
+            .set_sync(
+                &"key2".to_string(),
+                &2,
+                &SetOptions::new().with_expiration(Duration::from_millis(20)),
+            )
+            .unwrap();
+        store.set_sync(&"key3".to_string(), &3, &SetOptions::new()).unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(store.get_sync(&"key2".to_string()).is_err());
+        assert_eq!(store.get_sync(&"key3".to_string()).unwrap(), 3);
+
+        // del 不通知监听器
+        store.del_sync(&"key3".to_string()).unwrap();
+
+        assert_eq!(
+            *evicted.lock().unwrap(),
+            vec![
+                ("key1".to_string(), 1, RemovalCause::Size),
+                ("key2".to_string(), 2, RemovalCause::Expired),
+            ]
+        );
+        assert_eq!(
+            store.metrics(),
+            LruStoreMetrics {
+                hits: 1,
+                misses: 1,
+                insertions: 3,
+                evictions: 1,
+                rejections: 0,
+                expirations: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_tinylfu_resists_scan() {
+        let store = make_store::<i32>(1000, EvictionPolicy::TinyLfu);
+        let hot_keys: Vec<String> = (0..100).map(|i| format!("hot{}", i)).collect();
+
+        // 热点数据被多次访问
+        for key in &hot_keys {
+            store.set_sync(key, &1, &SetOptions::new()).unwrap();
+        }
+        for _ in 0..10 {
+            for key in &hot_keys {
+                store.get_sync(key).unwrap();
+            }
+        }
+
+        // 一次性扫描大量冷数据
+        for i in 0..2000 {
+            store.set_sync(&format!("cold{}", i), &i, &SetOptions::new()).unwrap();
+        }
+
+        assert_eq!(store.entry_count(), 1000);
+        let retained = hot_keys.iter().filter(|key| store.get_sync(key).is_ok()).count();
+        // 频率统计是近似值，冷数据与热点数据哈希冲突时可能挤掉个别热点
+        assert!(retained >= hot_keys.len() * 9 / 10);
+        assert!(store.metrics().rejections > 0);
+
+        // 同样的访问模式下 LRU 会丢失全部热点数据
+        let lru = make_store::<i32>(1000, EvictionPolicy::Lru);
+        for key in &hot_keys {
+            lru.set_sync(key, &1, &SetOptions::new()).unwrap();
+        }
+        for i in 0..2000 {
+            lru.set_sync(&format!("cold{}", i), &i, &SetOptions::new()).unwrap();
+        }
+        assert!(hot_keys.iter().all(|key| lru.get_sync(key).is_err()));
+    }
+
+    #[test]
+    fn test_frequency_sketch_aging() {
+        let mut sketch = FrequencySketch::new(16);
+        for _ in 0..20 {
+            sketch.increment(&"hot");
+        }
+        assert_eq!(sketch.frequency(&"hot"), SKETCH_MAX_COUNT);
+        assert_eq!(sketch.frequency(&"cold"), 0);
+
+        // 达到采样上限后计数减半
+        for i in 0..sketch.sample_size {
+            sketch.increment(&i);
+        }
+        assert!(sketch.frequency(&"hot") < SKETCH_MAX_COUNT);
+    }
+}
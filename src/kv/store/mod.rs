@@ -1,21 +1,35 @@
+pub mod aop_store;
 pub mod core;
 pub mod dash_map_store;
+pub mod disk_store;
+mod expiry;
 pub mod loadable_sync_store;
+pub mod lru_store;
+mod notify;
+pub mod prefixed_store;
 pub mod redis_store;
 pub mod register;
 pub mod rwlock_hash_map_store;
+pub mod tiered_store;
 pub mod unsafe_hash_map_store;
 
 #[cfg(test)]
 pub(crate) mod common_tests;
 
 // 重新导出核心类型和 trait
-pub use core::{IsSyncStore, IsAsyncStore, KvError, SetOptions, Store, AsyncStore, SyncStore};
+pub use core::{
+    IsSyncStore, IsAsyncStore, KvError, SetOptions, Store, AsyncStore, Subscription, SyncStore,
+};
 // 重新导出具体实现
+pub use aop_store::{AopStore, AopStoreConfig};
 pub use dash_map_store::{DashMapStore, DashMapStoreConfig};
+pub use disk_store::{DiskStore, DiskStoreConfig, FsyncPolicy};
 pub use loadable_sync_store::{LoadableSyncStore, LoadableSyncStoreConfig};
+pub use lru_store::{EvictionPolicy, LruStore, LruStoreConfig, LruStoreMetrics, RemovalCause};
+pub use prefixed_store::{PrefixedStore, PrefixedStoreConfig};
 pub use redis_store::{RedisError, RedisStore, RedisStoreConfig};
 pub use rwlock_hash_map_store::{RwLockHashMapStore, RwLockHashMapStoreConfig};
+pub use tiered_store::{TieredStore, TieredStoreConfig};
 pub use unsafe_hash_map_store::{UnsafeHashMapStore, UnsafeHashMapStoreConfig};
 // 重新导出注册函数
-pub use register::{register_hash_stores, register_stores};
+pub use register::{register_aop_stores, register_hash_stores, register_stores};
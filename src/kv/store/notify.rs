@@ -0,0 +1,87 @@
+//! 存储的变更通知
+//!
+//! - `ChangeNotifier`: 内存存储向所有订阅者广播 `(ChangeType, K)` 事件，没有订阅者时不构造事件
+//! - `CHANNEL_CAPACITY`: 订阅 channel 的容量
+
+use tokio::sync::broadcast;
+
+use super::core::Subscription;
+use crate::kv::parser::ChangeType;
+
+/// 每个订阅者最多缓存的事件数量，消费过慢时最旧的事件被丢弃
+pub(crate) const CHANNEL_CAPACITY: usize = 1024;
+
+pub(crate) struct ChangeNotifier<K> {
+    sender: broadcast::Sender<(ChangeType, K)>,
+}
+
+impl<K> ChangeNotifier<K>
+where
+    K: Clone,
+{
+    pub(crate) fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    pub(crate) fn subscribe(&self) -> Subscription<K> {
+        self.sender.subscribe()
+    }
+
+    /// 是否有订阅者，调用方可以据此跳过额外的查询
+    pub(crate) fn is_active(&self) -> bool {
+        self.sender.receiver_count() > 0
+    }
+
+    pub(crate) fn notify(&self, change_type: ChangeType, key: &K) {
+        if self.is_active() {
+            let _ = self.sender.send((change_type, key.clone()));
+        }
+    }
+
+    /// 写入键后通知，`existed` 为写入前键是否存在
+    pub(crate) fn notify_set(&self, key: &K, existed: bool) {
+        let change_type = if existed {
+            ChangeType::Update
+        } else {
+            ChangeType::Add
+        };
+        self.notify(change_type, key);
+    }
+}
+
+impl<K> Default for ChangeNotifier<K>
+where
+    K: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notify() {
+        let notifier = ChangeNotifier::<String>::new();
+        // 没有订阅者时不发送
+        assert!(!notifier.is_active());
+        notifier.notify(ChangeType::Add, &"ignored".to_string());
+
+        let mut subscription = notifier.subscribe();
+        assert!(notifier.is_active());
+        notifier.notify_set(&"key".to_string(), false);
+        notifier.notify_set(&"key".to_string(), true);
+        notifier.notify(ChangeType::Delete, &"key".to_string());
+
+        assert_eq!(subscription.try_recv().unwrap(), (ChangeType::Add, "key".to_string()));
+        assert_eq!(subscription.try_recv().unwrap(), (ChangeType::Update, "key".to_string()));
+        assert_eq!(subscription.try_recv().unwrap(), (ChangeType::Delete, "key".to_string()));
+        assert!(subscription.try_recv().is_err());
+
+        drop(subscription);
+        assert!(!notifier.is_active());
+    }
+}
@@ -0,0 +1,616 @@
+use async_trait::async_trait;
+use garde::Validate;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use smart_default::SmartDefault;
+use std::any::{Any, TypeId};
+use std::sync::Arc;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::cfg::{create_shared_trait_from_type_options, TypeOptions};
+use crate::kv::serializer::Serializer;
+
+use super::core::{AsyncStore, IsAsyncStore, KvError, SetOptions, Store, Subscription, SyncStore};
+use super::notify::CHANNEL_CAPACITY;
+
+/// PrefixedStore 配置
+#[derive(Debug, Clone, Serialize, Deserialize, SmartDefault, Validate, JsonSchema)]
+#[serde(default)]
+pub struct PrefixedStoreConfig {
+    /// 内部存储配置（键类型为 String），支持 `{"$instance": "name"}` 引用共享实例
+    #[garde(skip)]
+    pub store: TypeOptions,
+
+    /// 键前缀（命名空间），原样拼接在键的前面，通常以分隔符结尾，如 "team-a:"
+    #[garde(length(min = 1))]
+    pub prefix: String,
+
+    /// 键序列化器配置，序列化结果需要是 UTF-8（如 JsonSerializer）
+    /// 键类型为 String 时可以不配置，直接拼接在前缀后面
+    #[garde(skip)]
+    pub key_serializer: Option<TypeOptions>,
+}
+
+/// 键和内部存储的键之间的转换
+struct KeyCodec<K> {
+    prefix: String,
+    // 为 None 时 K 一定是 String
+    serializer: Option<Arc<dyn Serializer<K, Vec<u8>>>>,
+}
+
+impl<K> KeyCodec<K>
+where
+    K: Clone + 'static,
+{
+    fn encode(&self, key: &K) -> Result<String, KvError> {
+        let key = match &self.serializer {
+            Some(serializer) => {
+                let bytes = serializer
+                    .serialize(key.clone())
+                    .map_err(|e| KvError::Other(format!("failed to serialize key: {}", e)))?;
+                String::from_utf8(bytes).map_err(|_| {
+                    KvError::Other("key serializer must produce UTF-8 output".to_string())
+                })?
+            }
+            None => (key as &dyn Any)
+                .downcast_ref::<String>()
+                .cloned()
+                .ok_or_else(|| KvError::Other("key_serializer is required".to_string()))?,
+        };
+        Ok(format!("{}{}", self.prefix, key))
+    }
+
+    fn encode_all(&self, keys: &[K]) -> Result<Vec<String>, KvError> {
+        keys.iter().map(|key| self.encode(key)).collect()
+    }
+
+    /// 去掉前缀并还原键，不属于当前命名空间或无法反序列化的键返回 None
+    fn decode(&self, key: &str) -> Option<K> {
+        let key = key.strip_prefix(&self.prefix)?;
+        match &self.serializer {
+            Some(serializer) => serializer.deserialize(key.as_bytes().to_vec()).ok(),
+            None => (Box::new(key.to_string()) as Box<dyn Any>)
+                .downcast::<K>()
+                .ok()
+                .map(|key| *key),
+        }
+    }
+
+    fn decode_all(&self, keys: Vec<String>) -> Vec<K> {
+        keys.iter().filter_map(|key| self.decode(key)).collect()
+    }
+}
+
+/// 为键添加命名空间前缀的存储装饰器
+///
+/// 多个业务共享同一个存储（如同一个 Redis）时，每个 PrefixedStore 只能看到自己前缀下的键：
+/// - 读写：键加上前缀后访问内部存储
+/// - 遍历：`scan`、`keys` 只返回当前前缀下的键并去掉前缀，`len` 为当前前缀下的键数量
+/// - 订阅：只转发当前前缀下的变更
+///
+/// 键不是 String 时，通过 `key_serializer` 序列化后再拼接前缀
+///
+/// `scan` 遍历内部存储的全部键后过滤，每批返回的键可能少于 `count`；
+/// `keys`、`len` 使用内部存储的 `keys_with_prefix`（RedisStore 在服务端按模式过滤）
+pub struct PrefixedStore<K, V>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    inner: Arc<dyn AsyncStore<String, V>>,
+    codec: Arc<KeyCodec<K>>,
+    // 为共享实例时，由实例的所有者负责关闭
+    owns_inner: bool,
+}
+
+impl<K, V> PrefixedStore<K, V>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    pub fn new(config: PrefixedStoreConfig) -> Result<Self, anyhow::Error> {
+        if let Err(errors) = config.validate() {
+            return Err(anyhow::anyhow!(
+                "configuration validation failed: {}",
+                errors
+            ));
+        }
+
+        let serializer = match &config.key_serializer {
+            Some(options) => Some(
+                create_shared_trait_from_type_options::<dyn Serializer<K, Vec<u8>>>(options)
+                    .map_err(|e| anyhow::anyhow!("failed to create key serializer: {}", e))?,
+            ),
+            None if TypeId::of::<K>() == TypeId::of::<String>() => None,
+            None => {
+                return Err(anyhow::anyhow!(
+                    "key_serializer is required when the key type is not String"
+                ))
+            }
+        };
+
+        let inner =
+            create_shared_trait_from_type_options::<dyn AsyncStore<String, V>>(&config.store)
+                .map_err(|e| anyhow::anyhow!("failed to create inner store: {}", e))?;
+
+        Ok(Self {
+            inner,
+            codec: Arc::new(KeyCodec {
+                prefix: config.prefix,
+                serializer,
+            }),
+            owns_inner: config.store.instance_name().is_none(),
+        })
+    }
+}
+
+#[async_trait]
+impl<K, V> AsyncStore<K, V> for PrefixedStore<K, V>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    async fn set(&self, key: &K, value: &V, options: &SetOptions) -> Result<(), KvError> {
+        self.inner
+            .set(&self.codec.encode(key)?, value, options)
+            .await
+    }
+
+    async fn get(&self, key: &K) -> Result<V, KvError> {
+        self.inner.get(&self.codec.encode(key)?).await
+    }
+
+    async fn del(&self, key: &K) -> Result<(), KvError> {
+        self.inner.del(&self.codec.encode(key)?).await
+    }
+
+    async fn batch_set(
+        &self,
+        keys: &[K],
+        vals: &[V],
+        options: &SetOptions,
+    ) -> Result<Vec<Result<(), KvError>>, KvError> {
+        self.inner
+            .batch_set(&self.codec.encode_all(keys)?, vals, options)
+            .await
+    }
+
+    async fn batch_get(
+        &self,
+        keys: &[K],
+    ) -> Result<(Vec<Option<V>>, Vec<Option<KvError>>), KvError> {
+        self.inner.batch_get(&self.codec.encode_all(keys)?).await
+    }
+
+    async fn batch_del(&self, keys: &[K]) -> Result<Vec<Result<(), KvError>>, KvError> {
+        self.inner.batch_del(&self.codec.encode_all(keys)?).await
+    }
+
+    async fn scan(&self, cursor: u64, count: usize) -> Result<(u64, Vec<K>), KvError> {
+        let (next, keys) = self.inner.scan(cursor, count).await?;
+        Ok((next, self.codec.decode_all(keys)))
+    }
+
+    async fn keys(&self) -> Result<Vec<K>, KvError> {
+        let keys = self.inner.keys_with_prefix(&self.codec.prefix).await?;
+        Ok(self.codec.decode_all(keys))
+    }
+
+    async fn keys_with_prefix(&self, prefix: &str) -> Result<Vec<K>, KvError>
+    where
+        K: AsRef<str>,
+    {
+        // 序列化后的键（如 JSON 会加引号）不一定以 prefix 开头，只有直接拼接时才能交给内部存储过滤
+        if self.codec.serializer.is_none() {
+            let keys = self
+                .inner
+                .keys_with_prefix(&format!("{}{}", self.codec.prefix, prefix))
+                .await?;
+            return Ok(self.codec.decode_all(keys));
+        }
+
+        let mut keys = self.keys().await?;
+        keys.retain(|key| key.as_ref().starts_with(prefix));
+        Ok(keys)
+    }
+
+    async fn len(&self) -> Result<usize, KvError> {
+        Ok(self.keys().await?.len())
+    }
+
+    async fn compare_and_swap(
+        &self,
+        key: &K,
+        expected: &V,
+        value: &V,
+        options: &SetOptions,
+    ) -> Result<(), KvError>
+    where
+        V: PartialEq,
+    {
+        self.inner
+            .compare_and_swap(&self.codec.encode(key)?, expected, value, options)
+            .await
+    }
+
+    async fn incr(&self, key: &K, delta: i64) -> Result<i64, KvError>
+    where
+        V: Into<i64> + TryFrom<i64>,
+    {
+        self.inner.incr(&self.codec.encode(key)?, delta).await
+    }
+
+    async fn decr(&self, key: &K, delta: i64) -> Result<i64, KvError>
+    where
+        V: Into<i64> + TryFrom<i64>,
+    {
+        self.inner.decr(&self.codec.encode(key)?, delta).await
+    }
+
+    /// 只转发当前前缀下的变更；内部订阅消费过慢丢弃的事件无法转发为 `Lagged`，直接跳过
+    async fn subscribe(&self) -> Result<Subscription<K>, KvError> {
+        let mut inner = self.inner.subscribe().await?;
+        let (sender, receiver) = broadcast::channel(CHANNEL_CAPACITY);
+        let codec = Arc::clone(&self.codec);
+        tokio::spawn(async move {
+            loop {
+                let event = tokio::select! {
+                    event = inner.recv() => event,
+                    _ = sender.closed() => break,
+                };
+                match event {
+                    Ok((change_type, key)) => {
+                        if let Some(key) = codec.decode(&key) {
+                            let _ = sender.send((change_type, key));
+                        }
+                    }
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+        Ok(receiver)
+    }
+
+    async fn close(&self) -> Result<(), KvError> {
+        if self.owns_inner {
+            self.inner.close().await?;
+        }
+        Ok(())
+    }
+
+    fn is_exclusive(&self) -> bool {
+        self.inner.is_exclusive()
+    }
+}
+
+impl<K, V> From<PrefixedStoreConfig> for PrefixedStore<K, V>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn from(config: PrefixedStoreConfig) -> Self {
+        PrefixedStore::new(config).expect("Failed to create PrefixedStore")
+    }
+}
+
+impl<K, V> From<Box<PrefixedStore<K, V>>> for Box<dyn AsyncStore<K, V>>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn from(source: Box<PrefixedStore<K, V>>) -> Self {
+        source as Box<dyn AsyncStore<K, V>>
+    }
+}
+
+impl<K, V> From<Box<PrefixedStore<K, V>>> for Box<dyn SyncStore<K, V>>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn from(source: Box<PrefixedStore<K, V>>) -> Self {
+        source as Box<dyn SyncStore<K, V>>
+    }
+}
+
+impl<K, V> From<Box<PrefixedStore<K, V>>> for Box<dyn Store<K, V>>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn from(source: Box<PrefixedStore<K, V>>) -> Self {
+        source as Box<dyn Store<K, V>>
+    }
+}
+
+/// 实现 IsAsyncStore 标记，让 PrefixedStore 自动获得 SyncStore 能力
+impl<K, V> IsAsyncStore for PrefixedStore<K, V>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cfg::create_instance;
+    use crate::kv::parser::ChangeType;
+    use crate::kv::serializer::register_serde_serializers;
+    use crate::kv::store::common_tests::*;
+    use crate::kv::store::register_hash_stores;
+    use std::time::Duration;
+
+    type SharedStore = Arc<dyn AsyncStore<String, String>>;
+
+    fn make_config(store: &str, prefix: &str) -> PrefixedStoreConfig {
+        json5::from_str(&format!(
+            r#"{{
+                store: {},
+                prefix: "{}"
+            }}"#,
+            store, prefix
+        ))
+        .unwrap()
+    }
+
+    fn make_store<V>() -> PrefixedStore<String, V>
+    where
+        V: Clone + Send + Sync + 'static,
+    {
+        register_hash_stores::<String, V>().unwrap();
+        PrefixedStore::new(make_config(
+            r#"{ type: "RwLockHashMapStore", options: {} }"#,
+            "test:",
+        ))
+        .unwrap()
+    }
+
+    // 两个前缀不同的 PrefixedStore 共享同一个内部存储
+    fn make_tenants(
+        name: &str,
+    ) -> (
+        PrefixedStore<String, String>,
+        PrefixedStore<String, String>,
+        SharedStore,
+    ) {
+        register_hash_stores::<String, String>().unwrap();
+        let shared = create_instance::<dyn AsyncStore<String, String>>(
+            name,
+            &TypeOptions::from_json(r#"{"type": "RwLockHashMapStore", "options": {}}"#).unwrap(),
+        )
+        .unwrap();
+        let store = format!(r#"{{ "$instance": "{}" }}"#, name);
+        let a = PrefixedStore::new(make_config(&store, "a:")).unwrap();
+        let b = PrefixedStore::new(make_config(&store, "b:")).unwrap();
+        (a, b, shared)
+    }
+
+    // ========== 公共测试 ==========
+
+    #[tokio::test]
+    async fn test_store_set() {
+        test_set(make_store::<String>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_get() {
+        test_get(make_store::<String>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_del() {
+        test_del(make_store::<String>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_batch_set() {
+        test_batch_set(make_store::<i32>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_batch_get() {
+        test_batch_get(make_store::<i32>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_batch_del() {
+        test_batch_del(make_store::<i32>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_close() {
+        test_close(make_store::<i32>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_expiration() {
+        test_expiration(make_store::<String>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_scan() {
+        test_scan(make_store::<String>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_compare_and_swap() {
+        test_compare_and_swap(make_store::<String>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_incr() {
+        test_incr(make_store::<i64>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_subscribe() {
+        test_subscribe(make_store::<String>()).await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_store_set_sync() {
+        test_set_sync(make_store::<String>());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_store_get_sync() {
+        test_get_sync(make_store::<String>());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_store_batch_get_sync() {
+        test_batch_get_sync(make_store::<i32>());
+    }
+
+    // ========== 场景测试 ==========
+
+    #[tokio::test]
+    async fn test_tenants_isolated() {
+        let (a, b, shared) = make_tenants("prefixed-isolated");
+        let key = "key".to_string();
+
+        a.set(&key, &"from-a".to_string(), &SetOptions::new())
+            .await
+            .unwrap();
+        b.set(&key, &"from-b".to_string(), &SetOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(a.get(&key).await.unwrap(), "from-a");
+        assert_eq!(b.get(&key).await.unwrap(), "from-b");
+
+        // 内部存储中的键带有前缀
+        assert_eq!(shared.get(&"a:key".to_string()).await.unwrap(), "from-a");
+        shared
+            .set(&"other".to_string(), &"v".to_string(), &SetOptions::new())
+            .await
+            .unwrap();
+
+        a.set(&"key2".to_string(), &"v2".to_string(), &SetOptions::new())
+            .await
+            .unwrap();
+        let mut keys = a.keys().await.unwrap();
+        keys.sort();
+        assert_eq!(keys, vec!["key".to_string(), "key2".to_string()]);
+        assert_eq!(b.keys().await.unwrap(), vec!["key".to_string()]);
+        assert_eq!(a.len().await.unwrap(), 2);
+        assert_eq!(
+            a.keys_with_prefix("key2").await.unwrap(),
+            vec!["key2".to_string()]
+        );
+
+        // 删除只影响自己的命名空间
+        a.del(&key).await.unwrap();
+        assert!(matches!(a.get(&key).await, Err(KvError::KeyNotFound)));
+        assert_eq!(b.get(&key).await.unwrap(), "from-b");
+
+        // 共享实例由所有者关闭
+        a.close().await.unwrap();
+        assert_eq!(shared.get(&"b:key".to_string()).await.unwrap(), "from-b");
+    }
+
+    #[tokio::test]
+    async fn test_scan_strips_prefix() {
+        let (a, b, _shared) = make_tenants("prefixed-scan");
+        for i in 0..20 {
+            a.set(&format!("a{}", i), &"v".to_string(), &SetOptions::new())
+                .await
+                .unwrap();
+            b.set(&format!("b{}", i), &"v".to_string(), &SetOptions::new())
+                .await
+                .unwrap();
+        }
+
+        let mut keys = Vec::new();
+        let mut cursor = 0;
+        loop {
+            let (next, batch) = a.scan(cursor, 7).await.unwrap();
+            keys.extend(batch);
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+        keys.sort();
+        let mut expected: Vec<String> = (0..20).map(|i| format!("a{}", i)).collect();
+        expected.sort();
+        assert_eq!(keys, expected);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_filters_prefix() {
+        let (a, b, _shared) = make_tenants("prefixed-subscribe");
+        let mut subscription = a.subscribe().await.unwrap();
+
+        b.set(&"key".to_string(), &"v".to_string(), &SetOptions::new())
+            .await
+            .unwrap();
+        a.set(&"key".to_string(), &"v".to_string(), &SetOptions::new())
+            .await
+            .unwrap();
+        a.del(&"key".to_string()).await.unwrap();
+
+        // 其他前缀下的变更被过滤
+        for expected in [ChangeType::Add, ChangeType::Delete] {
+            let event = tokio::time::timeout(Duration::from_secs(1), subscription.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(event, (expected, "key".to_string()));
+        }
+        assert!(subscription.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_non_string_keys() {
+        register_hash_stores::<String, String>().unwrap();
+        register_serde_serializers::<i64>().unwrap();
+
+        let mut config = make_config(r#"{ type: "RwLockHashMapStore", options: {} }"#, "ids:");
+        // 键不是 String 时必须配置序列化器
+        assert!(PrefixedStore::<i64, String>::new(config.clone()).is_err());
+
+        config.key_serializer =
+            Some(TypeOptions::from_json(r#"{"type": "JsonSerializer", "options": {}}"#).unwrap());
+        let store = PrefixedStore::<i64, String>::new(config).unwrap();
+        store
+            .set(&42, &"answer".to_string(), &SetOptions::new())
+            .await
+            .unwrap();
+        store
+            .set(&7, &"seven".to_string(), &SetOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(store.get(&42).await.unwrap(), "answer");
+
+        let mut keys = store.keys().await.unwrap();
+        keys.sort();
+        assert_eq!(keys, vec![7, 42]);
+        assert_eq!(
+            store.inner.get(&"ids:42".to_string()).await.unwrap(),
+            "answer"
+        );
+    }
+
+    #[test]
+    fn test_config_deserialize() {
+        let config: PrefixedStoreConfig = json5::from_str(
+            r#"{
+                store: {
+                    type: "RedisStore",
+                    options: { endpoint: "localhost:6379" }
+                },
+                prefix: "team-a:"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(config.store.type_name, "RedisStore");
+        assert_eq!(config.prefix, "team-a:");
+        assert!(config.key_serializer.is_none());
+
+        // prefix 不能为空
+        let result = PrefixedStore::<String, String>::new(PrefixedStoreConfig::default());
+        assert!(result.is_err());
+    }
+}
@@ -1,12 +1,31 @@
 use async_trait::async_trait;
+use futures::future::try_join_all;
+use futures::StreamExt;
+use redis::aio::{ConnectionLike, ConnectionManager, ConnectionManagerConfig};
+use redis::cluster::ClusterClient;
+use redis::cluster_async::ClusterConnection;
+use redis::cluster_routing::{RoutingInfo, SingleNodeRoutingInfo};
+use redis::sentinel::{SentinelClient, SentinelNodeConnectionInfo, SentinelServerType};
 use redis::AsyncCommands;
+use redis::{ErrorKind, RedisConnectionInfo, RedisFuture, RedisResult, ServerErrorKind};
 use redis::{ExistenceCheck, SetExpiry, SetOptions as RedisSetOptions};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
-
-use super::core::{IsAsyncStore, KvError, SetOptions, Store, AsyncStore, SyncStore};
-use crate::cfg::{create_trait_from_type_options, TypeOptions};
+use tokio::sync::{broadcast, Mutex, OnceCell};
+
+use super::core::{
+    incr_bounds, IsAsyncStore, KvError, SetOptions, Store, AsyncStore, Subscription, SyncStore,
+    SCAN_BATCH_SIZE,
+};
+use super::notify::CHANNEL_CAPACITY;
+use crate::kv::parser::ChangeType;
+use crate::cfg::{create_shared_trait_from_type_options, TypeOptions};
 use crate::kv::serializer::Serializer;
 
 /// Redis 连接错误
@@ -23,7 +42,7 @@ pub enum RedisError {
 }
 
 /// Redis 存储配置（简化版，与 Go 版本对齐）
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, SmartDefault)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, SmartDefault, JsonSchema)]
 #[serde(default)]
 pub struct RedisStoreConfig {
     // ===== 连接配置 =====
@@ -31,8 +50,12 @@ pub struct RedisStoreConfig {
     pub endpoint: Option<String>,
 
     /// 集群模式：节点地址列表（如 ["node1:6379", "node2:6379"]）
+    /// 哨兵模式：哨兵节点地址列表（如 ["sentinel1:26379", "sentinel2:26379"]）
     pub endpoints: Option<Vec<String>>,
 
+    /// 哨兵模式：主节点名称，设置后 endpoints 作为哨兵节点地址，通过哨兵发现主节点
+    pub master_name: Option<String>,
+
     // ===== 认证配置 =====
     /// Redis 6.0+ ACL 用户名
     pub username: Option<String>,
@@ -40,8 +63,11 @@ pub struct RedisStoreConfig {
     /// 密码
     pub password: Option<String>,
 
+    /// 哨兵节点的密码（仅哨兵模式有效），未设置时连接哨兵不使用认证
+    pub sentinel_password: Option<String>,
+
     // ===== 数据库配置 =====
-    /// 数据库编号（默认 0，仅单机模式有效）
+    /// 数据库编号（默认 0，集群模式只支持 0）
     #[default = 0]
     pub db: i64,
 
@@ -50,20 +76,33 @@ pub struct RedisStoreConfig {
     pub default_ttl: u64,
 
     // ===== 超时配置 =====
-    /// 连接超时（秒）
+    /// 连接超时（秒），0 表示不限制
     #[default = 5]
     pub connection_timeout: u64,
 
-    /// 命令执行超时（秒）
+    /// 命令执行超时（秒），0 表示不限制
     #[default = 3]
     pub command_timeout: u64,
 
+    // ===== 重连配置 =====
+    /// 连接断开后的最大重连次数
+    #[default = 6]
+    pub max_retries: usize,
+
+    /// 重连的最小退避时间（毫秒），之后每次重连按指数增长
+    #[default = 100]
+    pub min_retry_backoff: u64,
+
+    /// 重连的最大退避时间（毫秒）
+    #[default = 2000]
+    pub max_retry_backoff: u64,
+
     // ===== 序列化器配置 =====
-    /// 键序列化器配置（使用 TypeOptions 动态创建）
+    /// 键序列化器配置（使用 TypeOptions 动态创建，支持 `{"$instance": "name"}` 引用共享实例）
     /// 默认使用 "JsonSerializer"
     pub key_serializer: Option<TypeOptions>,
 
-    /// 值序列化器配置（使用 TypeOptions 动态创建）
+    /// 值序列化器配置（使用 TypeOptions 动态创建，支持 `{"$instance": "name"}` 引用共享实例）
     /// 默认使用 "JsonSerializer"
     pub val_serializer: Option<TypeOptions>,
 }
@@ -74,6 +113,11 @@ pub struct RedisStoreConfig {
 /// - `K`: 键类型，必须实现 Clone + Send + Sync
 /// - `V`: 值类型，必须实现 Clone + Send + Sync
 ///
+/// # 部署模式
+/// - 单机：设置 `endpoint`
+/// - 集群：设置 `endpoints`，按槽路由，批量操作按槽拆分后并发执行
+/// - 哨兵：设置 `endpoints`（哨兵地址）和 `master_name`，通过哨兵发现主节点
+///
 /// # 示例
 /// ```ignore
 /// use rustx::kv::store::{RedisStore, RedisStoreConfig, Store, SetOptions};
@@ -98,13 +142,172 @@ pub struct RedisStoreConfig {
 /// });
 /// ```
 pub struct RedisStore<K, V> {
-    client: redis::Client,
-    key_serializer: Box<dyn Serializer<K, Vec<u8>>>,
-    val_serializer: Box<dyn Serializer<V, Vec<u8>>>,
+    client: RedisClient,
+    key_serializer: Arc<dyn Serializer<K, Vec<u8>>>,
+    val_serializer: Arc<dyn Serializer<V, Vec<u8>>>,
     default_ttl: Duration,
+    db: i64,
     _phantom: std::marker::PhantomData<(K, V)>,
 }
 
+/// 不同部署模式的客户端，连接在首次使用时建立并复用
+enum RedisClient {
+    /// 单机模式，ConnectionManager 断线后按退避策略自动重连
+    Single {
+        client: redis::Client,
+        manager: OnceCell<ConnectionManager>,
+        config: ConnectionManagerConfig,
+    },
+
+    /// 集群模式，集群连接按槽路由并处理 MOVED/ASK 重定向
+    Cluster {
+        client: ClusterClient,
+        connection: OnceCell<ClusterConnection>,
+    },
+
+    /// 哨兵模式，复用到当前主节点的 ConnectionManager；连接断开或主节点变为只读（主从切换）后，
+    /// 下一次获取连接时重新通过哨兵查询主节点
+    Sentinel {
+        state: Mutex<SentinelState>,
+        stale: Arc<AtomicBool>,
+        config: ConnectionManagerConfig,
+    },
+}
+
+struct SentinelState {
+    sentinel: SentinelClient,
+    master: Option<ConnectionManager>,
+}
+
+impl RedisClient {
+    /// 获取异步连接
+    async fn connection(&self) -> Result<RedisConnection, KvError> {
+        let connect_err = |e: redis::RedisError| KvError::Other(format!("Failed to get connection: {}", e));
+        match self {
+            RedisClient::Single {
+                client,
+                manager,
+                config,
+            } => manager
+                .get_or_try_init(|| client.get_connection_manager_with_config(config.clone()))
+                .await
+                .map(|manager| RedisConnection::Single(manager.clone()))
+                .map_err(connect_err),
+            RedisClient::Cluster { client, connection } => connection
+                .get_or_try_init(|| client.get_async_connection())
+                .await
+                .map(|connection| RedisConnection::Cluster(connection.clone()))
+                .map_err(connect_err),
+            RedisClient::Sentinel {
+                state,
+                stale,
+                config,
+            } => {
+                let mut state = state.lock().await;
+                if stale.swap(false, Ordering::SeqCst) {
+                    state.master = None;
+                }
+                let master = match &state.master {
+                    Some(master) => master.clone(),
+                    None => {
+                        let client = state.sentinel.async_get_client().await.map_err(connect_err)?;
+                        let master = client
+                            .get_connection_manager_with_config(config.clone())
+                            .await
+                            .map_err(connect_err)?;
+                        state.master = Some(master.clone());
+                        master
+                    }
+                };
+                Ok(RedisConnection::Sentinel(master, Arc::clone(stale)))
+            }
+        }
+    }
+
+    /// 获取 pub/sub 连接，集群模式的 keyspace 通知分散在各个节点上，不支持
+    async fn pubsub(&self) -> Result<redis::aio::PubSub, KvError> {
+        let client = match self {
+            RedisClient::Single { client, .. } => client.clone(),
+            RedisClient::Cluster { .. } => {
+                return Err(KvError::Other(
+                    "subscribe is not supported in cluster mode".to_string(),
+                ))
+            }
+            RedisClient::Sentinel { state, .. } => state
+                .lock()
+                .await
+                .sentinel
+                .async_get_client()
+                .await
+                .map_err(|e| KvError::Other(format!("Failed to get master: {}", e)))?,
+        };
+        client
+            .get_async_pubsub()
+            .await
+            .map_err(|e| KvError::Other(format!("Failed to get pubsub connection: {}", e)))
+    }
+
+    fn is_cluster(&self) -> bool {
+        matches!(self, RedisClient::Cluster { .. })
+    }
+}
+
+/// 单机、集群或哨兵模式下主节点的异步连接，命令、Pipeline 和脚本可以统一执行
+#[derive(Clone)]
+enum RedisConnection {
+    Single(ConnectionManager),
+    Cluster(ClusterConnection),
+    /// 到主节点的连接，出现需要重新查询主节点的错误时设置 stale 标记
+    Sentinel(ConnectionManager, Arc<AtomicBool>),
+}
+
+/// 连接断开或写入只读节点（主从切换后旧的主节点变为从节点）时需要重新查询主节点
+fn is_failover_error(e: &redis::RedisError) -> bool {
+    e.is_unrecoverable_error() || e.kind() == ErrorKind::Server(ServerErrorKind::ReadOnly)
+}
+
+fn mark_stale<T>(result: RedisResult<T>, stale: &AtomicBool) -> RedisResult<T> {
+    if result.as_ref().is_err_and(is_failover_error) {
+        stale.store(true, Ordering::SeqCst);
+    }
+    result
+}
+
+impl ConnectionLike for RedisConnection {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a redis::Cmd) -> RedisFuture<'a, redis::Value> {
+        match self {
+            RedisConnection::Single(con) => con.req_packed_command(cmd),
+            RedisConnection::Cluster(con) => con.req_packed_command(cmd),
+            RedisConnection::Sentinel(con, stale) => {
+                Box::pin(async move { mark_stale(con.req_packed_command(cmd).await, stale) })
+            }
+        }
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        cmd: &'a redis::Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<redis::Value>> {
+        match self {
+            RedisConnection::Single(con) => con.req_packed_commands(cmd, offset, count),
+            RedisConnection::Cluster(con) => con.req_packed_commands(cmd, offset, count),
+            RedisConnection::Sentinel(con, stale) => Box::pin(async move {
+                mark_stale(con.req_packed_commands(cmd, offset, count).await, stale)
+            }),
+        }
+    }
+
+    fn get_db(&self) -> i64 {
+        match self {
+            RedisConnection::Single(con) => con.get_db(),
+            RedisConnection::Cluster(con) => con.get_db(),
+            RedisConnection::Sentinel(con, _) => con.get_db(),
+        }
+    }
+}
+
 impl<K, V> RedisStore<K, V>
 where
     K: Clone + Send + Sync + 'static,
@@ -112,6 +315,8 @@ where
 {
     /// 创建 Redis 存储的唯一方法
     ///
+    /// 只设置 endpoint 为单机模式；只设置 endpoints 为集群模式；设置 endpoints 和 master_name 为哨兵模式
+    ///
     /// # 参数
     /// * `config` - Redis 存储配置
     ///
@@ -121,6 +326,7 @@ where
         // 1. 验证配置
         let is_cluster = config.endpoints.as_ref().is_some_and(|e| !e.is_empty());
         let is_single = config.endpoint.as_ref().is_some_and(|e| !e.is_empty());
+        let is_sentinel = config.master_name.as_ref().is_some_and(|m| !m.is_empty());
 
         if !is_cluster && !is_single {
             return Err(RedisError::InvalidConfig(
@@ -134,29 +340,34 @@ where
             ));
         }
 
+        if is_sentinel && !is_cluster {
+            return Err(RedisError::InvalidConfig(
+                "master_name requires endpoints of sentinels".to_string(),
+            ));
+        }
+
+        if is_cluster && !is_sentinel && config.db != 0 {
+            return Err(RedisError::InvalidConfig(
+                "db must be 0 in cluster mode".to_string(),
+            ));
+        }
+
         // 2. 创建序列化器（默认使用 JsonSerializer）
         let key_serializer =
             Self::create_serializer::<K>(config.key_serializer.clone(), "JsonSerializer")?;
         let val_serializer =
             Self::create_serializer::<V>(config.val_serializer.clone(), "JsonSerializer")?;
 
-        // 3. 构建 Redis 连接信息
+        // 3. 构建客户端并测试连接（使用同步方式）
         let client = if is_single {
             Self::create_single_client(&config)?
+        } else if is_sentinel {
+            Self::create_sentinel_client(&config)?
         } else {
             Self::create_cluster_client(&config)?
         };
 
-        // 4. 测试连接（使用同步方式）
-        let mut con = client
-            .get_connection()
-            .map_err(|e| RedisError::ConnectionFailed(e.to_string()))?;
-
-        redis::cmd("PING")
-            .query::<String>(&mut con)
-            .map_err(|e| RedisError::ConnectionFailed(format!("PING failed: {}", e)))?;
-
-        // 5. 转换 TTL
+        // 4. 转换 TTL
         let default_ttl = Duration::from_secs(config.default_ttl);
 
         Ok(Self {
@@ -164,12 +375,13 @@ where
             key_serializer,
             val_serializer,
             default_ttl,
+            db: config.db,
             _phantom: std::marker::PhantomData,
         })
     }
 
     /// 创建单机模式客户端
-    fn create_single_client(config: &RedisStoreConfig) -> Result<redis::Client, RedisError> {
+    fn create_single_client(config: &RedisStoreConfig) -> Result<RedisClient, RedisError> {
         let endpoint = config.endpoint.as_ref().unwrap();
 
         // 构建 Redis 连接 URL
@@ -183,26 +395,110 @@ where
             format!("redis://{}/{}", endpoint, config.db)
         };
 
-        redis::Client::open(url)
-            .map_err(|e| RedisError::InvalidConfig(format!("Invalid connection URL: {}", e)))
+        let client = redis::Client::open(url)
+            .map_err(|e| RedisError::InvalidConfig(format!("Invalid connection URL: {}", e)))?;
+
+        let mut con = match timeout_secs(config.connection_timeout) {
+            Some(timeout) => client.get_connection_with_timeout(timeout),
+            None => client.get_connection(),
+        }
+        .map_err(|e| RedisError::ConnectionFailed(e.to_string()))?;
+        Self::ping(&mut con)?;
+
+        Ok(RedisClient::Single {
+            client,
+            manager: OnceCell::new(),
+            config: connection_manager_config(config),
+        })
     }
 
-    /// 创建集群模式客户端
-    fn create_cluster_client(config: &RedisStoreConfig) -> Result<redis::Client, RedisError> {
+    /// 创建集群模式客户端，endpoints 中的任意可用节点都可以作为入口，启动时从入口节点获取槽分布
+    fn create_cluster_client(config: &RedisStoreConfig) -> Result<RedisClient, RedisError> {
         let endpoints = config.endpoints.as_ref().unwrap();
 
-        // 集群模式使用第一个节点作为入口
-        let endpoint = &endpoints[0];
+        let nodes = endpoints.iter().map(|endpoint| format!("redis://{}", endpoint));
+        let mut builder = ClusterClient::builder(nodes)
+            .retries(config.max_retries as u32)
+            .min_retry_wait(config.min_retry_backoff)
+            .max_retry_wait(config.max_retry_backoff);
+        if let Some(timeout) = timeout_secs(config.connection_timeout) {
+            builder = builder.connection_timeout(timeout);
+        }
+        if let Some(timeout) = timeout_secs(config.command_timeout) {
+            builder = builder.response_timeout(timeout);
+        }
+        if let Some(password) = &config.password {
+            builder = builder.password(password);
+        }
+        if let Some(username) = &config.username {
+            builder = builder.username(username);
+        }
 
-        let url = if let Some(password) = &config.password {
-            let username = config.username.as_deref().unwrap_or("default");
-            format!("redis://{}:{}@{}", username, password, endpoint)
-        } else {
-            format!("redis://{}", endpoint)
-        };
+        let client = builder
+            .build()
+            .map_err(|e| RedisError::InvalidConfig(format!("Invalid cluster config: {}", e)))?;
+
+        let mut con = client
+            .get_connection()
+            .map_err(|e| RedisError::ConnectionFailed(e.to_string()))?;
+        Self::ping(&mut con)?;
+
+        Ok(RedisClient::Cluster {
+            client,
+            connection: OnceCell::new(),
+        })
+    }
+
+    /// 创建哨兵模式客户端，endpoints 为哨兵节点地址，username、password、db 用于连接主节点
+    fn create_sentinel_client(config: &RedisStoreConfig) -> Result<RedisClient, RedisError> {
+        let endpoints = config.endpoints.as_ref().unwrap();
+        let master_name = config.master_name.clone().unwrap();
+
+        let sentinels: Vec<String> = endpoints
+            .iter()
+            .map(|endpoint| match &config.sentinel_password {
+                Some(password) => format!("redis://:{}@{}", password, endpoint),
+                None => format!("redis://{}", endpoint),
+            })
+            .collect();
+
+        let mut master_info = RedisConnectionInfo::default().set_db(config.db);
+        if let Some(password) = &config.password {
+            master_info = master_info.set_password(password);
+        }
+        if let Some(username) = &config.username {
+            master_info = master_info.set_username(username);
+        }
+        let node_info = SentinelNodeConnectionInfo::default().set_redis_connection_info(master_info);
+
+        let mut sentinel = SentinelClient::build(
+            sentinels,
+            master_name,
+            Some(node_info),
+            SentinelServerType::Master,
+        )
+        .map_err(|e| RedisError::InvalidConfig(format!("Invalid sentinel config: {}", e)))?;
+
+        let mut con = sentinel
+            .get_connection()
+            .map_err(|e| RedisError::ConnectionFailed(e.to_string()))?;
+        Self::ping(&mut con)?;
 
-        redis::Client::open(url)
-            .map_err(|e| RedisError::InvalidConfig(format!("Invalid cluster URL: {}", e)))
+        Ok(RedisClient::Sentinel {
+            state: Mutex::new(SentinelState {
+                sentinel,
+                master: None,
+            }),
+            stale: Arc::new(AtomicBool::new(false)),
+            config: connection_manager_config(config),
+        })
+    }
+
+    fn ping(con: &mut impl redis::ConnectionLike) -> Result<(), RedisError> {
+        redis::cmd("PING")
+            .query::<String>(con)
+            .map_err(|e| RedisError::ConnectionFailed(format!("PING failed: {}", e)))?;
+        Ok(())
     }
 
     /// 创建序列化器（支持动态配置）
@@ -212,12 +508,12 @@ where
     fn create_serializer<T>(
         type_options: Option<TypeOptions>,
         default_type: &str,
-    ) -> Result<Box<dyn Serializer<T, Vec<u8>>>, RedisError>
+    ) -> Result<Arc<dyn Serializer<T, Vec<u8>>>, RedisError>
     where
         T: Clone + Send + Sync + 'static,
     {
         match type_options {
-            Some(opts) => create_trait_from_type_options(&opts).map_err(|e| {
+            Some(opts) => create_shared_trait_from_type_options(&opts).map_err(|e| {
                 RedisError::InvalidConfig(format!("Failed to create serializer: {}", e))
             }),
             None => {
@@ -226,7 +522,7 @@ where
                     type_name: default_type.to_string(),
                     options: serde_json::json!({}),
                 };
-                create_trait_from_type_options(&default_opts).map_err(|e| {
+                create_shared_trait_from_type_options(&default_opts).map_err(|e| {
                     RedisError::InvalidConfig(format!(
                         "Failed to create default serializer ({}): {}. \
                          Make sure to register the serializer first using register_serde_serializers::<T>()?",
@@ -241,6 +537,253 @@ where
     pub fn default_ttl(&self) -> Duration {
         self.default_ttl
     }
+
+    /// 执行 SCAN 命令，无法反序列化的键（如同一个 db 中其他程序写入的键）会被跳过
+    ///
+    /// 集群模式依次遍历每个主节点，cursor 的高 16 位为主节点的序号，低 48 位为该节点上的 cursor
+    async fn scan_match(
+        &self,
+        cursor: u64,
+        count: usize,
+        pattern: Option<&str>,
+    ) -> Result<(u64, Vec<K>), KvError> {
+        let (next, raw_keys): (u64, Vec<Vec<u8>>) = match self.client.connection().await? {
+            RedisConnection::Cluster(mut con) => {
+                cluster_scan(&mut con, cursor, count, pattern).await?
+            }
+            mut con => scan_cmd(cursor, count, pattern)
+                .query_async(&mut con)
+                .await
+                .map_err(|e| KvError::Other(format!("SCAN failed: {}", e)))?,
+        };
+
+        let keys = raw_keys
+            .into_iter()
+            .filter_map(|key_bytes| self.key_serializer.deserialize(key_bytes).ok())
+            .collect();
+        Ok((next, keys))
+    }
+
+    /// 批量命令的分组：集群模式按槽分组，每组的键在同一个节点上；其他模式所有键为一组
+    fn batch_groups<'a>(&self, key_strs: impl Iterator<Item = &'a str>) -> Vec<Vec<usize>> {
+        if self.client.is_cluster() {
+            group_by_slot(key_strs)
+        } else {
+            vec![(0..key_strs.count()).collect()]
+        }
+    }
+}
+
+/// 秒数转换为超时时间，0 表示不限制
+fn timeout_secs(secs: u64) -> Option<Duration> {
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
+/// 单机和哨兵模式 ConnectionManager 的重连和超时配置
+fn connection_manager_config(config: &RedisStoreConfig) -> ConnectionManagerConfig {
+    ConnectionManagerConfig::new()
+        .set_number_of_retries(config.max_retries)
+        .set_min_delay(Duration::from_millis(config.min_retry_backoff))
+        .set_max_delay(Duration::from_millis(config.max_retry_backoff))
+        .set_connection_timeout(timeout_secs(config.connection_timeout))
+        .set_response_timeout(timeout_secs(config.command_timeout))
+}
+
+/// 集群的槽数量
+const CLUSTER_SLOTS: u16 = 16384;
+
+/// 集群模式 SCAN 的 cursor 中，节点上的 cursor 所占的位数
+const NODE_CURSOR_BITS: u32 = 48;
+
+fn scan_cmd(cursor: u64, count: usize, pattern: Option<&str>) -> redis::Cmd {
+    let mut cmd = redis::cmd("SCAN");
+    cmd.arg(cursor);
+    if let Some(pattern) = pattern {
+        cmd.arg("MATCH").arg(pattern);
+    }
+    cmd.arg("COUNT").arg(count.max(1));
+    cmd
+}
+
+/// 在集群的第 `cursor >> NODE_CURSOR_BITS` 个主节点上执行 SCAN，该节点遍历完后 cursor 指向下一个主节点
+async fn cluster_scan(
+    con: &mut ClusterConnection,
+    cursor: u64,
+    count: usize,
+    pattern: Option<&str>,
+) -> Result<(u64, Vec<Vec<u8>>), KvError> {
+    let nodes: String = redis::cmd("CLUSTER")
+        .arg("NODES")
+        .query_async(con)
+        .await
+        .map_err(|e| KvError::Other(format!("CLUSTER NODES failed: {}", e)))?;
+    let masters = cluster_masters(&nodes);
+
+    let index = (cursor >> NODE_CURSOR_BITS) as usize;
+    let node_cursor = cursor & ((1 << NODE_CURSOR_BITS) - 1);
+    let Some((host, port)) = masters.get(index) else {
+        return Ok((0, vec![]));
+    };
+
+    let routing = RoutingInfo::SingleNode(SingleNodeRoutingInfo::ByAddress {
+        host: host.clone(),
+        port: *port,
+    });
+    let (next, keys): (u64, Vec<Vec<u8>>) = con
+        .route_command(scan_cmd(node_cursor, count, pattern), routing)
+        .await
+        .and_then(|value| Ok(redis::from_redis_value(value)?))
+        .map_err(|e| KvError::Other(format!("SCAN failed: {}", e)))?;
+
+    let next = if next >= 1 << NODE_CURSOR_BITS {
+        return Err(KvError::Other(format!("SCAN cursor {} out of range", next)));
+    } else if next != 0 {
+        ((index as u64) << NODE_CURSOR_BITS) | next
+    } else if index + 1 < masters.len() {
+        ((index + 1) as u64) << NODE_CURSOR_BITS
+    } else {
+        0
+    };
+    Ok((next, keys))
+}
+
+/// 从 CLUSTER NODES 的输出中解析负责槽的主节点地址，按地址排序保证多次 SCAN 之间的顺序一致
+fn cluster_masters(nodes: &str) -> Vec<(String, u16)> {
+    let mut masters: Vec<(String, u16)> = nodes
+        .lines()
+        .filter_map(|line| {
+            // <id> <ip:port@cport[,hostname]> <flags> <master> <ping-sent> <pong-recv> <config-epoch> <link-state> <slot> ...
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let flags = fields.get(2)?;
+            if fields.len() < 9 || !flags.split(',').any(|flag| flag == "master") || flags.contains("fail") {
+                return None;
+            }
+            let addr = fields[1].split(['@', ',']).next()?;
+            let (host, port) = addr.rsplit_once(':')?;
+            Some((host.to_string(), port.parse().ok()?))
+        })
+        .collect();
+    masters.sort();
+    masters
+}
+
+/// 计算键所在的槽：CRC16 (XMODEM) 对 16384 取模，键中包含非空的 hash tag（如 `{user}:1`）时只计算 tag
+fn key_slot(key: &[u8]) -> u16 {
+    let key = key
+        .iter()
+        .position(|&b| b == b'{')
+        .and_then(|start| {
+            let tag = &key[start + 1..];
+            tag.iter()
+                .position(|&b| b == b'}')
+                .filter(|&end| end > 0)
+                .map(|end| &tag[..end])
+        })
+        .unwrap_or(key);
+
+    let mut crc: u16 = 0;
+    for &byte in key {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc % CLUSTER_SLOTS
+}
+
+/// 按槽对键分组，返回每组的键在原列表中的下标，组按首次出现的顺序排列
+fn group_by_slot<'a>(keys: impl Iterator<Item = &'a str>) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut slot_groups = HashMap::new();
+    for (i, key) in keys.enumerate() {
+        let group = *slot_groups.entry(key_slot(key.as_bytes())).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[group].push(i);
+    }
+    groups
+}
+
+// 比较并交换：当前值等于 ARGV[1] 时设置为 ARGV[2]，ARGV[3] 为过期时间（毫秒），0 表示不过期
+static COMPARE_AND_SWAP_SCRIPT: Lazy<redis::Script> = Lazy::new(|| {
+    redis::Script::new(
+        r"
+        if redis.call('GET', KEYS[1]) ~= ARGV[1] then
+            return 0
+        end
+        if tonumber(ARGV[3]) > 0 then
+            redis.call('SET', KEYS[1], ARGV[2], 'PX', ARGV[3])
+        else
+            redis.call('SET', KEYS[1], ARGV[2])
+        end
+        return 1
+        ",
+    )
+});
+
+// 自增 ARGV[1]，键不存在时按 ARGV[2]（毫秒）设置过期时间，0 表示不过期
+// 结果超出 [ARGV[3], ARGV[4]] 时恢复原值（包括过期时间），返回 {0, 结果}，否则返回 {1, 结果}
+static INCR_SCRIPT: Lazy<redis::Script> = Lazy::new(|| {
+    redis::Script::new(
+        r"
+        local old = redis.call('GET', KEYS[1])
+        local value = redis.call('INCRBY', KEYS[1], ARGV[1])
+        if value < tonumber(ARGV[3]) or value > tonumber(ARGV[4]) then
+            if not old then
+                redis.call('DEL', KEYS[1])
+            else
+                local ttl = redis.call('PTTL', KEYS[1])
+                if ttl > 0 then
+                    redis.call('SET', KEYS[1], old, 'PX', ttl)
+                else
+                    redis.call('SET', KEYS[1], old)
+                end
+            end
+            return {0, value}
+        end
+        if not old and tonumber(ARGV[2]) > 0 then
+            redis.call('PEXPIRE', KEYS[1], ARGV[2])
+        end
+        return {1, value}
+        ",
+    )
+});
+
+/// keyspace 通知的事件对应的变更类型，与键的值无关的事件（如 expire、persist）返回 None
+fn keyspace_change_type(event: &str) -> Option<ChangeType> {
+    match event {
+        "del" | "expired" | "evicted" | "rename_from" => Some(ChangeType::Delete),
+        "set" | "incrby" | "incrbyfloat" | "append" | "setrange" | "rename_to" | "copy_to"
+        | "restore" => Some(ChangeType::Update),
+        _ => None,
+    }
+}
+
+/// 将 if_not_exist、if_exist 转换为 SET 的 NX、XX，两者同时设置时条件总是不满足
+fn existence_check(options: &SetOptions) -> Result<Option<ExistenceCheck>, KvError> {
+    match (options.if_not_exist, options.if_exist) {
+        (true, true) => Err(KvError::ConditionFailed),
+        (true, false) => Ok(Some(ExistenceCheck::NX)),
+        (false, true) => Ok(Some(ExistenceCheck::XX)),
+        (false, false) => Ok(None),
+    }
+}
+
+/// 转义 Redis glob 模式中的特殊字符
+fn escape_glob(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
 }
 
 // 实现 AsyncStore trait
@@ -269,34 +812,31 @@ where
         // 3. 构建 Redis SET 命令选项
         let mut redis_set_opts = RedisSetOptions::default();
 
-        // 设置条件检查（NX）
-        if options.if_not_exist {
-            redis_set_opts = redis_set_opts.conditional_set(ExistenceCheck::NX);
+        // 设置条件检查（NX / XX）
+        let existence_check = existence_check(options)?;
+        if let Some(check) = existence_check {
+            redis_set_opts = redis_set_opts.conditional_set(check);
         }
 
         // 设置过期时间
         let expiration = options.expiration.unwrap_or(self.default_ttl);
         if expiration > Duration::ZERO {
-            redis_set_opts = redis_set_opts.with_expiration(SetExpiry::EX(expiration.as_secs()));
+            redis_set_opts = redis_set_opts.with_expiration(SetExpiry::PX(expiration.as_millis() as u64));
         }
 
         // 4. 执行 SET 命令
-        let mut con = self
-            .client
-            .get_multiplexed_async_connection()
-            .await
-            .map_err(|e| KvError::Other(format!("Failed to get connection: {}", e)))?;
+        let mut con = self.client.connection().await?;
 
         // 使用 set_options 命令，返回值是 Option<String>：
         // - Some(_) 表示设置成功
-        // - None 表示设置失败（key 已存在且设置了 NX 选项）
+        // - None 表示设置失败（设置了 NX 且 key 已存在，或设置了 XX 且 key 不存在）
         let result: Option<String> = con
             .set_options(&key_str, val_bytes.as_slice(), redis_set_opts)
             .await
             .map_err(|e| KvError::Other(format!("SET failed: {}", e)))?;
 
-        // 如果使用了 NX 选项但 key 已存在，返回 ConditionFailed
-        if options.if_not_exist && result.is_none() {
+        // 条件不满足，返回 ConditionFailed
+        if existence_check.is_some() && result.is_none() {
             return Err(KvError::ConditionFailed);
         }
 
@@ -314,11 +854,7 @@ where
             .map_err(|e| KvError::Other(format!("Invalid key UTF-8: {}", e)))?;
 
         // 2. 执行 GET 命令
-        let mut con = self
-            .client
-            .get_multiplexed_async_connection()
-            .await
-            .map_err(|e| KvError::Other(format!("Failed to get connection: {}", e)))?;
+        let mut con = self.client.connection().await?;
 
         let result: Option<Vec<u8>> = con
             .get(&key_str)
@@ -347,11 +883,7 @@ where
             .map_err(|e| KvError::Other(format!("Invalid key UTF-8: {}", e)))?;
 
         // 2. 执行 DEL 命令
-        let mut con = self
-            .client
-            .get_multiplexed_async_connection()
-            .await
-            .map_err(|e| KvError::Other(format!("Failed to get connection: {}", e)))?;
+        let mut con = self.client.connection().await?;
 
         let _: () = con
             .del(&key_str)
@@ -397,46 +929,45 @@ where
 
         // 3. 构建 Redis SET 命令选项（所有键共享相同的选项）
         let mut redis_set_opts = RedisSetOptions::default();
-        if options.if_not_exist {
-            redis_set_opts = redis_set_opts.conditional_set(ExistenceCheck::NX);
+        let existence_check = match existence_check(options) {
+            Ok(check) => check,
+            Err(_) => return Ok(keys.iter().map(|_| Err(KvError::ConditionFailed)).collect()),
+        };
+        if let Some(check) = existence_check {
+            redis_set_opts = redis_set_opts.conditional_set(check);
         }
         if expiration > Duration::ZERO {
-            redis_set_opts = redis_set_opts.with_expiration(SetExpiry::EX(expiration.as_secs()));
-        }
-
-        // 4. 使用 Pipeline 批量执行
-        let mut con = self
-            .client
-            .get_multiplexed_async_connection()
-            .await
-            .map_err(|e| KvError::Other(format!("Failed to get connection: {}", e)))?;
-
-        let mut pipe = redis::pipe();
-        // 注意：不使用 atomic()，因为我们需要每个命令独立执行并收集结果
-
-        for (key_str, val_bytes) in &items {
-            pipe.set_options(key_str, val_bytes.as_slice(), redis_set_opts.clone());
+            redis_set_opts = redis_set_opts.with_expiration(SetExpiry::PX(expiration.as_millis() as u64));
         }
 
-        // 5. 执行 Pipeline 并收集返回值
-        // set_options 返回 Option<String>，所以 pipeline 返回 Vec<Option<String>>
-        let results: Vec<Option<String>> = pipe
-            .query_async(&mut con)
-            .await
-            .map_err(|e| KvError::Other(format!("Pipeline SET failed: {}", e)))?;
-
-        // 6. 将 Redis 返回值转换为 KvError 结果
-        let final_results: Vec<Result<(), KvError>> = results
-            .into_iter()
-            .map(|result| {
-                // 如果使用了 NX 选项且返回 None，表示 key 已存在
-                if options.if_not_exist && result.is_none() {
-                    Err(KvError::ConditionFailed)
-                } else {
-                    Ok(())
+        // 4. 使用 Pipeline 批量执行，集群模式按槽拆分为多个 Pipeline 并发执行
+        let con = self.client.connection().await?;
+        let groups = self.batch_groups(items.iter().map(|(key_str, _)| key_str.as_str()));
+
+        let group_results = try_join_all(groups.iter().map(|group| {
+            let mut con = con.clone();
+            let mut pipe = redis::pipe();
+            // 注意：不使用 atomic()，因为我们需要每个命令独立执行并收集结果
+            for &i in group {
+                let (key_str, val_bytes) = &items[i];
+                pipe.set_options(key_str, val_bytes.as_slice(), redis_set_opts.clone());
+            }
+            // set_options 返回 Option<String>，所以 pipeline 返回 Vec<Option<String>>
+            async move { pipe.query_async::<Vec<Option<String>>>(&mut con).await }
+        }))
+        .await
+        .map_err(|e| KvError::Other(format!("Pipeline SET failed: {}", e)))?;
+
+        // 5. 将 Redis 返回值按键的原始顺序转换为 KvError 结果
+        let mut final_results: Vec<Result<(), KvError>> = items.iter().map(|_| Ok(())).collect();
+        for (group, results) in groups.iter().zip(group_results) {
+            for (&i, result) in group.iter().zip(results) {
+                // 设置了 NX / XX 且返回 None，表示条件不满足
+                if existence_check.is_some() && result.is_none() {
+                    final_results[i] = Err(KvError::ConditionFailed);
                 }
-            })
-            .collect();
+            }
+        }
 
         Ok(final_results)
     }
@@ -463,19 +994,24 @@ where
             key_strs.push(key_str);
         }
 
-        // 2. 使用 MGET 批量获取
-        let mut con = self
-            .client
-            .get_multiplexed_async_connection()
-            .await
-            .map_err(|e| KvError::Other(format!("Failed to get connection: {}", e)))?;
-
-        let results: Option<Vec<Option<Vec<u8>>>> = con
-            .mget(&key_strs)
-            .await
-            .map_err(|e| KvError::Other(format!("MGET failed: {}", e)))?;
-
-        let results = results.unwrap_or_default();
+        // 2. 使用 MGET 批量获取，集群模式按槽拆分为多个 MGET 并发执行
+        let con = self.client.connection().await?;
+        let groups = self.batch_groups(key_strs.iter().map(String::as_str));
+
+        let group_results = try_join_all(groups.iter().map(|group| {
+            let mut con = con.clone();
+            let group_keys: Vec<&String> = group.iter().map(|&i| &key_strs[i]).collect();
+            async move { con.mget::<_, Vec<Option<Vec<u8>>>>(group_keys).await }
+        }))
+        .await
+        .map_err(|e| KvError::Other(format!("MGET failed: {}", e)))?;
+
+        let mut results = vec![None; key_strs.len()];
+        for (group, values) in groups.iter().zip(group_results) {
+            for (&i, value) in group.iter().zip(values) {
+                results[i] = value;
+            }
+        }
 
         // 3. 反序列化结果
         let mut values = Vec::with_capacity(results.len());
@@ -525,25 +1061,22 @@ where
             key_strs.push(key_str);
         }
 
-        // 2. 使用 Pipeline 批量删除
-        let mut con = self
-            .client
-            .get_multiplexed_async_connection()
-            .await
-            .map_err(|e| KvError::Other(format!("Failed to get connection: {}", e)))?;
-
-        let mut pipe = redis::pipe();
-        pipe.atomic();
-
-        for key_str in &key_strs {
-            pipe.del(key_str);
-        }
+        // 2. 使用 Pipeline 批量删除，集群模式按槽拆分，同一个槽的键在同一个节点上，可以使用事务
+        let con = self.client.connection().await?;
+        let groups = self.batch_groups(key_strs.iter().map(String::as_str));
 
         // 3. 执行 Pipeline
-        let _: () = pipe
-            .query_async(&mut con)
-            .await
-            .map_err(|e| KvError::Other(format!("Pipeline DEL failed: {}", e)))?;
+        try_join_all(groups.iter().map(|group| {
+            let mut con = con.clone();
+            let mut pipe = redis::pipe();
+            pipe.atomic();
+            for &i in group {
+                pipe.del(&key_strs[i]);
+            }
+            async move { pipe.query_async::<()>(&mut con).await }
+        }))
+        .await
+        .map_err(|e| KvError::Other(format!("Pipeline DEL failed: {}", e)))?;
 
         // 4. 返回成功结果
         Ok((0..key_strs.len()).map(|_| Ok(())).collect())
@@ -553,6 +1086,174 @@ where
         // Redis Client 会自动管理连接，这里不需要特殊处理
         Ok(())
     }
+
+    async fn scan(&self, cursor: u64, count: usize) -> Result<(u64, Vec<K>), KvError> {
+        self.scan_match(cursor, count, None).await
+    }
+
+    async fn keys_with_prefix(&self, prefix: &str) -> Result<Vec<K>, KvError>
+    where
+        K: AsRef<str>,
+    {
+        // 键经过序列化（如 JSON 会加引号），服务端用包含 prefix 的模式粗筛，再按反序列化后的键精确过滤
+        let pattern = format!("*{}*", escape_glob(prefix));
+        let mut keys = Vec::new();
+        let mut cursor = 0;
+        loop {
+            let (next, batch) = self
+                .scan_match(cursor, SCAN_BATCH_SIZE, Some(&pattern))
+                .await?;
+            keys.extend(batch.into_iter().filter(|key| key.as_ref().starts_with(prefix)));
+            if next == 0 {
+                return Ok(keys);
+            }
+            cursor = next;
+        }
+    }
+
+    /// 返回 DBSIZE，即当前 db 中所有键的数量，集群模式为所有主节点的键数量之和
+    async fn len(&self) -> Result<usize, KvError> {
+        let mut con = self.client.connection().await?;
+
+        redis::cmd("DBSIZE")
+            .query_async(&mut con)
+            .await
+            .map_err(|e| KvError::Other(format!("DBSIZE failed: {}", e)))
+    }
+
+    /// 使用 Lua 脚本比较序列化后的值，比较和设置在服务端原子执行
+    async fn compare_and_swap(
+        &self,
+        key: &K,
+        expected: &V,
+        value: &V,
+        options: &SetOptions,
+    ) -> Result<(), KvError>
+    where
+        V: PartialEq,
+    {
+        let key_bytes = self
+            .key_serializer
+            .serialize(key.clone())
+            .map_err(|e| KvError::Other(format!("Key serialization failed: {}", e)))?;
+
+        let key_str = String::from_utf8(key_bytes)
+            .map_err(|e| KvError::Other(format!("Invalid key UTF-8: {}", e)))?;
+
+        let expected_bytes = self
+            .val_serializer
+            .serialize(expected.clone())
+            .map_err(|e| KvError::Other(format!("Value serialization failed: {}", e)))?;
+
+        let val_bytes = self
+            .val_serializer
+            .serialize(value.clone())
+            .map_err(|e| KvError::Other(format!("Value serialization failed: {}", e)))?;
+
+        let expiration = options.expiration.unwrap_or(self.default_ttl);
+
+        let mut con = self.client.connection().await?;
+
+        let swapped: i64 = COMPARE_AND_SWAP_SCRIPT
+            .key(&key_str)
+            .arg(expected_bytes)
+            .arg(val_bytes)
+            .arg(expiration.as_millis() as u64)
+            .invoke_async(&mut con)
+            .await
+            .map_err(|e| KvError::Other(format!("compare_and_swap failed: {}", e)))?;
+
+        if swapped == 0 {
+            return Err(KvError::ConditionFailed);
+        }
+        Ok(())
+    }
+
+    /// 使用 INCRBY，值必须被序列化为十进制整数（如默认的 JsonSerializer），新建的键使用 default_ttl
+    ///
+    /// 结果超出 V 的范围时在脚本中恢复原值并返回错误，不会写入
+    async fn incr(&self, key: &K, delta: i64) -> Result<i64, KvError>
+    where
+        V: Into<i64> + TryFrom<i64>,
+    {
+        let key_bytes = self
+            .key_serializer
+            .serialize(key.clone())
+            .map_err(|e| KvError::Other(format!("Key serialization failed: {}", e)))?;
+
+        let key_str = String::from_utf8(key_bytes)
+            .map_err(|e| KvError::Other(format!("Invalid key UTF-8: {}", e)))?;
+
+        let (min, max) = incr_bounds::<V>();
+
+        let mut con = self.client.connection().await?;
+
+        let (ok, value): (i64, i64) = INCR_SCRIPT
+            .key(&key_str)
+            .arg(delta)
+            .arg(self.default_ttl.as_millis() as u64)
+            .arg(min)
+            .arg(max)
+            .invoke_async(&mut con)
+            .await
+            .map_err(|e| KvError::Other(format!("INCRBY failed: {}", e)))?;
+
+        if ok == 0 {
+            return Err(KvError::Other(format!("incr result {} out of range", value)));
+        }
+        Ok(value)
+    }
+
+    /// 通过 keyspace 通知订阅当前 db 的变更，需要服务端开启 `notify-keyspace-events`（如 `Kg$xe`）
+    ///
+    /// keyspace 通知无法区分新增和更新，写入类事件均为 `ChangeType::Update`，del、expired、evicted 为 `ChangeType::Delete`；
+    /// 无法反序列化的键会被跳过。每次订阅使用一个独立的 pub/sub 连接，所有订阅者释放后连接关闭；
+    /// 哨兵模式订阅建立订阅时的主节点，集群模式不支持订阅
+    async fn subscribe(&self) -> Result<Subscription<K>, KvError> {
+        let mut pubsub = self.client.pubsub().await?;
+
+        let prefix = format!("__keyspace@{}__:", self.db);
+        pubsub
+            .psubscribe(format!("{}*", prefix))
+            .await
+            .map_err(|e| KvError::Other(format!("PSUBSCRIBE failed: {}", e)))?;
+
+        let (sender, receiver) = broadcast::channel(CHANNEL_CAPACITY);
+        let key_serializer = Arc::clone(&self.key_serializer);
+        tokio::spawn(async move {
+            let mut messages = pubsub.into_on_message();
+            loop {
+                let message = tokio::select! {
+                    message = messages.next() => message,
+                    _ = sender.closed() => break,
+                };
+                let Some(message) = message else {
+                    break;
+                };
+
+                let Ok(channel) = message.get_channel::<Vec<u8>>() else {
+                    continue;
+                };
+                let Some(change_type) = message
+                    .get_payload::<String>()
+                    .ok()
+                    .and_then(|event| keyspace_change_type(&event))
+                else {
+                    continue;
+                };
+                let Some(key_bytes) = channel.strip_prefix(prefix.as_bytes()) else {
+                    continue;
+                };
+                if let Ok(key) = key_serializer.deserialize(key_bytes.to_vec()) {
+                    if sender.send((change_type, key)).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(receiver)
+    }
 }
 
 // 实现 cfg 模块要求的 From trait
@@ -655,6 +1356,22 @@ mod tests {
         store
     }
 
+    // 辅助函数：创建用于计数器测试的 RedisStore<String, i64>
+    async fn make_store_i64() -> RedisStore<String, i64> {
+        register_serde_serializers::<String>().unwrap();
+        register_serde_serializers::<i64>().unwrap();
+        let config = RedisStoreConfig {
+            endpoint: Some("localhost:6379".to_string()),
+            ..Default::default()
+        };
+        let store = RedisStore::<String, i64>::new(config).unwrap();
+
+        // 清理可能的测试残留数据
+        cleanup_test_keys(&store, vec!["counter_key"]).await;
+
+        store
+    }
+
     // ========== 公共测试 ==========
 
     #[tokio::test]
@@ -769,8 +1486,161 @@ mod tests {
         test_close_sync(store);
     }
 
+    #[tokio::test]
+    #[ignore]
+    #[serial]
+    async fn test_store_expiration() {
+        let store = make_store_string().await;
+        cleanup_test_keys(&store, vec!["expire_key", "persist_key", "batch_key1", "batch_key2"]).await;
+        test_expiration(store).await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[ignore]
+    #[serial]
+    async fn test_store_expiration_sync() {
+        let store = make_store_string().await;
+        cleanup_test_keys(&store, vec!["expire_key", "persist_key", "batch_key1", "batch_key2"]).await;
+        test_expiration_sync(store);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    #[serial]
+    async fn test_store_scan() {
+        let store = make_store_string().await;
+        let keys = store.keys_with_prefix("scan_").await.unwrap();
+        store.batch_del(&keys).await.unwrap();
+        test_scan(store).await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[ignore]
+    #[serial]
+    async fn test_store_scan_sync() {
+        let store = make_store_string().await;
+        let keys = store.keys_with_prefix("scan_").await.unwrap();
+        store.batch_del(&keys).await.unwrap();
+        test_scan_sync(store);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    #[serial]
+    async fn test_store_compare_and_swap() {
+        let store = make_store_string().await;
+        cleanup_test_keys(&store, vec!["cas_key", "cas_missing_key"]).await;
+        test_compare_and_swap(store).await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[ignore]
+    #[serial]
+    async fn test_store_compare_and_swap_sync() {
+        let store = make_store_string().await;
+        cleanup_test_keys(&store, vec!["cas_key", "cas_missing_key"]).await;
+        test_compare_and_swap_sync(store);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    #[serial]
+    async fn test_store_incr() {
+        let store = make_store_i64().await;
+        test_incr(store).await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[ignore]
+    #[serial]
+    async fn test_store_incr_sync() {
+        let store = make_store_i64().await;
+        test_incr_sync(store);
+    }
+
     // ========== 场景测试 ==========
 
+    #[test]
+    fn test_keyspace_change_type() {
+        assert_eq!(keyspace_change_type("set"), Some(ChangeType::Update));
+        assert_eq!(keyspace_change_type("incrby"), Some(ChangeType::Update));
+        assert_eq!(keyspace_change_type("del"), Some(ChangeType::Delete));
+        assert_eq!(keyspace_change_type("expired"), Some(ChangeType::Delete));
+        assert_eq!(keyspace_change_type("expire"), None);
+    }
+
+    #[test]
+    fn test_incr_bounds() {
+        assert_eq!(incr_bounds::<i8>(), (i8::MIN as i64, i8::MAX as i64));
+        assert_eq!(incr_bounds::<u16>(), (0, u16::MAX as i64));
+        assert_eq!(incr_bounds::<i32>(), (i32::MIN as i64, i32::MAX as i64));
+        assert_eq!(incr_bounds::<u32>(), (0, u32::MAX as i64));
+        assert_eq!(incr_bounds::<i64>(), (i64::MIN, i64::MAX));
+    }
+
+    #[tokio::test]
+    #[ignore]
+    #[serial]
+    async fn test_incr_out_of_range() {
+        register_serde_serializers::<String>().unwrap();
+        register_serde_serializers::<i8>().unwrap();
+        let store = RedisStore::<String, i8>::new(RedisStoreConfig {
+            endpoint: Some("localhost:6379".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        let key = "counter_key".to_string();
+        store.del(&key).await.unwrap();
+
+        // 超出范围时返回错误，不创建键
+        let result = store.incr(&key, 200).await;
+        assert!(matches!(result, Err(KvError::Other(msg)) if msg.contains("out of range")));
+        assert!(matches!(store.get(&key).await, Err(KvError::KeyNotFound)));
+
+        // 超出范围时保留原值
+        store.set(&key, &100, &SetOptions::new()).await.unwrap();
+        assert!(store.incr(&key, 100).await.is_err());
+        assert_eq!(store.get(&key).await.unwrap(), 100);
+        assert_eq!(store.incr(&key, 27).await.unwrap(), 127);
+
+        store.del(&key).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore]
+    #[serial]
+    async fn test_subscribe_keyspace_notifications() {
+        let store = make_store_string().await;
+        let key = "subscribe_key".to_string();
+        cleanup_test_keys(&store, vec!["subscribe_key"]).await;
+
+        // 开启 keyspace 通知
+        let mut con = store.client.connection().await.unwrap();
+        let _: () = redis::cmd("CONFIG")
+            .arg("SET")
+            .arg("notify-keyspace-events")
+            .arg("Kg$xe")
+            .query_async(&mut con)
+            .await
+            .unwrap();
+
+        let mut subscription = store.subscribe().await.unwrap();
+        store.set(&key, &"v1".to_string(), &SetOptions::new()).await.unwrap();
+        store.del(&key).await.unwrap();
+
+        let timeout = Duration::from_secs(2);
+        let event = tokio::time::timeout(timeout, subscription.recv()).await.unwrap().unwrap();
+        assert_eq!(event, (ChangeType::Update, key.clone()));
+        let event = tokio::time::timeout(timeout, subscription.recv()).await.unwrap().unwrap();
+        assert_eq!(event, (ChangeType::Delete, key.clone()));
+    }
+
+    #[test]
+    fn test_escape_glob() {
+        assert_eq!(escape_glob("user:"), "user:");
+        assert_eq!(escape_glob("a*b?[c]\\"), "a\\*b\\?\\[c\\]\\\\");
+    }
+
     #[test]
     fn test_redis_store_config_default() {
         let config = RedisStoreConfig::default();
@@ -780,6 +1650,72 @@ mod tests {
         assert_eq!(config.default_ttl, 0);
         assert_eq!(config.connection_timeout, 5);
         assert_eq!(config.command_timeout, 3);
+        assert_eq!(config.max_retries, 6);
+        assert_eq!(config.min_retry_backoff, 100);
+        assert_eq!(config.max_retry_backoff, 2000);
+    }
+
+    #[test]
+    fn test_connection_manager_config() {
+        let config = RedisStoreConfig {
+            connection_timeout: 2,
+            command_timeout: 0,
+            max_retries: 3,
+            min_retry_backoff: 50,
+            max_retry_backoff: 500,
+            ..Default::default()
+        };
+        let manager_config = connection_manager_config(&config);
+        assert_eq!(manager_config.number_of_retries(), 3);
+        assert_eq!(manager_config.min_delay(), Duration::from_millis(50));
+        assert_eq!(manager_config.max_delay(), Some(Duration::from_millis(500)));
+        assert_eq!(manager_config.connection_timeout(), Some(Duration::from_secs(2)));
+        // 0 表示不限制
+        assert_eq!(manager_config.response_timeout(), None);
+    }
+
+    #[test]
+    fn test_is_failover_error() {
+        let readonly = redis::RedisError::from((
+            ErrorKind::Server(ServerErrorKind::ReadOnly),
+            "You can't write against a read only replica.",
+        ));
+        assert!(is_failover_error(&readonly));
+
+        let dropped = redis::RedisError::from(std::io::Error::from(
+            std::io::ErrorKind::ConnectionReset,
+        ));
+        assert!(is_failover_error(&dropped));
+
+        let stale = AtomicBool::new(false);
+        let _ = mark_stale::<()>(Err(readonly), &stale);
+        assert!(stale.load(Ordering::SeqCst));
+
+        let stale = AtomicBool::new(false);
+        let type_error = redis::RedisError::from((
+            ErrorKind::Server(ServerErrorKind::ResponseError),
+            "WRONGTYPE",
+        ));
+        assert!(!is_failover_error(&type_error));
+        let _ = mark_stale::<()>(Err(type_error), &stale);
+        assert!(!stale.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    #[ignore]
+    #[serial]
+    async fn test_store_reuses_connection() {
+        let store = make_store_string().await;
+
+        // 多次操作复用同一个连接
+        let mut con = store.client.connection().await.unwrap();
+        let id: i64 = redis::cmd("CLIENT").arg("ID").query_async(&mut con).await.unwrap();
+        for _ in 0..10 {
+            store.get(&"test_key".to_string()).await.ok();
+        }
+        let mut con = store.client.connection().await.unwrap();
+        let id_after: i64 = redis::cmd("CLIENT").arg("ID").query_async(&mut con).await.unwrap();
+        assert_eq!(id, id_after);
     }
 
     #[test]
@@ -973,4 +1909,298 @@ mod tests {
         let retrieved = store.get_sync(&user_key).unwrap();
         assert_eq!(retrieved, user);
     }
+
+    #[test]
+    fn test_key_slot() {
+        assert_eq!(key_slot(b"123456789"), 12739);
+        assert_eq!(key_slot(b"foo"), 12182);
+        // 只计算 hash tag
+        assert_eq!(key_slot(b"{user1000}.following"), key_slot(b"user1000"));
+        assert_eq!(key_slot(b"{user1000}.following"), key_slot(b"{user1000}.followers"));
+        // 空的 hash tag 计算整个键，只有第一个 { 之后的内容作为 tag
+        assert_eq!(key_slot(b"foo{}{bar}"), 8363);
+        assert_eq!(key_slot(b"foo{{bar}}zap"), key_slot(b"{bar"));
+    }
+
+    #[test]
+    fn test_group_by_slot() {
+        let keys = ["{a}1", "{b}1", "{a}2", "{c}1", "{b}2"];
+        let groups = group_by_slot(keys.into_iter());
+        assert_eq!(groups, vec![vec![0, 2], vec![1, 4], vec![3]]);
+    }
+
+    #[test]
+    fn test_cluster_masters() {
+        let nodes = "\
+07c37dfeb235213a872192d90877d0cd55635b91 127.0.0.1:30004@31004,host4 slave e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca 0 1426238317239 4 connected
+67ed2db8d677e59ec4a4cefb06858cf2a1a89fa1 127.0.0.1:30002@31002 master - 0 1426238316232 2 connected 5461-10922
+292f8b365bb7edb5e285caf0b7e6ddc7265d2f4f 127.0.0.1:30003@31003 master - 0 1426238318243 3 connected 10923-16383
+6ec23923021cf3ffec47632106199cb7f496ce01 127.0.0.1:30005@31005 master,fail - 1426238316232 0 5 disconnected 0-5460
+824fe116063bc5fcf9f4ffd895bc17aee7731ac3 127.0.0.1:30006@31006 master - 0 1426238316232 6 connected
+e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca 127.0.0.1:30001@31001 myself,master - 0 0 1 connected 0-5460
+";
+        assert_eq!(
+            cluster_masters(nodes),
+            vec![
+                ("127.0.0.1".to_string(), 30001),
+                ("127.0.0.1".to_string(), 30002),
+                ("127.0.0.1".to_string(), 30003),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_redis_store_config_mode_validation() {
+        // master_name 需要 endpoints
+        let config = RedisStoreConfig {
+            endpoint: Some("localhost:6379".to_string()),
+            master_name: Some("mymaster".to_string()),
+            ..Default::default()
+        };
+        let result = RedisStore::<String, String>::new(config);
+        assert!(matches!(result, Err(RedisError::InvalidConfig(_))));
+
+        // 集群模式只支持 db 0
+        let config = RedisStoreConfig {
+            endpoints: Some(vec!["node1:6379".to_string()]),
+            db: 1,
+            ..Default::default()
+        };
+        let result = RedisStore::<String, String>::new(config);
+        if let Err(RedisError::InvalidConfig(msg)) = result {
+            assert!(msg.contains("db must be 0 in cluster mode"));
+        } else {
+            panic!("Expected InvalidConfig error");
+        }
+    }
+
+    // ========== 集群和哨兵测试（需要本地安装 redis-server） ==========
+
+    // 本地启动的 redis-server 进程，drop 时结束进程
+    struct RedisServers(Vec<std::process::Child>);
+
+    impl Drop for RedisServers {
+        fn drop(&mut self) {
+            for child in &mut self.0 {
+                let _ = child.kill();
+                let _ = child.wait();
+            }
+        }
+    }
+
+    fn spawn_redis_server(args: &[String]) -> std::process::Child {
+        std::process::Command::new("redis-server")
+            .args(args)
+            .stdout(std::process::Stdio::null())
+            .spawn()
+            .expect("failed to spawn redis-server")
+    }
+
+    // 等待节点可以响应 PING
+    fn wait_ready(port: u16) -> redis::Connection {
+        let client = redis::Client::open(format!("redis://127.0.0.1:{}", port)).unwrap();
+        for _ in 0..50 {
+            if let Ok(mut con) = client.get_connection() {
+                if redis::cmd("PING").query::<String>(&mut con).is_ok() {
+                    return con;
+                }
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        panic!("redis-server on port {} is not ready", port);
+    }
+
+    // 启动 3 个主节点的集群，槽平均分配到各个节点
+    fn spawn_cluster(ports: &[u16]) -> RedisServers {
+        let servers = RedisServers(
+            ports
+                .iter()
+                .map(|port| {
+                    let config_file =
+                        std::env::temp_dir().join(format!("rustx-redis-cluster-{}.conf", port));
+                    let _ = std::fs::remove_file(&config_file);
+                    spawn_redis_server(&[
+                        "--port".to_string(),
+                        port.to_string(),
+                        "--cluster-enabled".to_string(),
+                        "yes".to_string(),
+                        "--cluster-config-file".to_string(),
+                        config_file.display().to_string(),
+                        "--save".to_string(),
+                        "".to_string(),
+                    ])
+                })
+                .collect(),
+        );
+
+        let mut cons: Vec<redis::Connection> = ports.iter().map(|&port| wait_ready(port)).collect();
+        let slots_per_node = CLUSTER_SLOTS.div_ceil(ports.len() as u16);
+        for (i, con) in cons.iter_mut().enumerate() {
+            let start = i as u16 * slots_per_node;
+            let end = (start + slots_per_node).min(CLUSTER_SLOTS) - 1;
+            let _: () = redis::cmd("CLUSTER")
+                .arg("ADDSLOTSRANGE")
+                .arg(start)
+                .arg(end)
+                .query(con)
+                .unwrap();
+            if i > 0 {
+                let _: () = redis::cmd("CLUSTER")
+                    .arg("MEET")
+                    .arg("127.0.0.1")
+                    .arg(ports[0])
+                    .query(con)
+                    .unwrap();
+            }
+        }
+
+        // 等待所有节点都认为集群可用
+        for con in &mut cons {
+            let ready = (0..100).any(|_| {
+                let info: String = redis::cmd("CLUSTER").arg("INFO").query(con).unwrap();
+                let ok = info.contains("cluster_state:ok");
+                if !ok {
+                    std::thread::sleep(Duration::from_millis(100));
+                }
+                ok
+            });
+            assert!(ready, "cluster is not ready");
+        }
+        servers
+    }
+
+    // 启动一个主节点和一个监控它的哨兵，主节点名称为 mymaster
+    fn spawn_sentinel(master_port: u16, sentinel_port: u16) -> RedisServers {
+        let master = spawn_redis_server(&[
+            "--port".to_string(),
+            master_port.to_string(),
+            "--save".to_string(),
+            "".to_string(),
+        ]);
+        let mut servers = RedisServers(vec![master]);
+        wait_ready(master_port);
+
+        let config_file =
+            std::env::temp_dir().join(format!("rustx-redis-sentinel-{}.conf", sentinel_port));
+        std::fs::write(
+            &config_file,
+            format!(
+                "port {}\nsentinel monitor mymaster 127.0.0.1 {} 1\n",
+                sentinel_port, master_port
+            ),
+        )
+        .unwrap();
+        servers.0.push(spawn_redis_server(&[
+            config_file.display().to_string(),
+            "--sentinel".to_string(),
+        ]));
+        wait_ready(sentinel_port);
+        servers
+    }
+
+    fn make_cluster_store<V>() -> RedisStore<String, V>
+    where
+        V: Clone + Send + Sync + 'static,
+    {
+        let config = RedisStoreConfig {
+            endpoints: Some(vec!["127.0.0.1:7101".to_string(), "127.0.0.1:7102".to_string()]),
+            ..Default::default()
+        };
+        RedisStore::new(config).unwrap()
+    }
+
+    fn make_sentinel_store<V>() -> RedisStore<String, V>
+    where
+        V: Clone + Send + Sync + 'static,
+    {
+        let config = RedisStoreConfig {
+            endpoints: Some(vec!["127.0.0.1:7211".to_string()]),
+            master_name: Some("mymaster".to_string()),
+            db: 1,
+            ..Default::default()
+        };
+        RedisStore::new(config).unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore]
+    #[serial]
+    async fn test_cluster_store() {
+        register_serde_serializers::<String>().unwrap();
+        register_serde_serializers::<i32>().unwrap();
+        register_serde_serializers::<i64>().unwrap();
+        let _servers = spawn_cluster(&[7101, 7102, 7103]);
+
+        test_set(make_cluster_store::<String>()).await;
+        test_batch_set(make_cluster_store::<i32>()).await;
+        test_batch_get(make_cluster_store::<i32>()).await;
+        test_batch_del(make_cluster_store::<i32>()).await;
+        test_scan(make_cluster_store::<String>()).await;
+        test_compare_and_swap(make_cluster_store::<String>()).await;
+        test_incr(make_cluster_store::<i64>()).await;
+
+        let store = make_cluster_store::<String>();
+        assert!(store.subscribe().await.is_err());
+    }
+
+    #[tokio::test]
+    #[ignore]
+    #[serial]
+    async fn test_cluster_store_cross_slot_batch() {
+        register_serde_serializers::<String>().unwrap();
+        register_serde_serializers::<i32>().unwrap();
+        let _servers = spawn_cluster(&[7101, 7102, 7103]);
+        let store = make_cluster_store::<i32>();
+
+        let keys: Vec<String> = (0..100).map(|i| format!("cross_slot:{}", i)).collect();
+        let vals: Vec<i32> = (0..100).collect();
+        let key_strs: Vec<String> = keys.iter().map(|key| format!("\"{}\"", key)).collect();
+        assert!(group_by_slot(key_strs.iter().map(String::as_str)).len() > 1);
+
+        let results = store.batch_set(&keys, &vals, &SetOptions::new()).await.unwrap();
+        assert!(results.iter().all(|result| result.is_ok()));
+
+        // if_not_exist 的结果按键的原始顺序返回
+        let mut mixed_keys = keys[..50].to_vec();
+        mixed_keys.push("cross_slot:new".to_string());
+        let mixed_vals = vec![0; mixed_keys.len()];
+        let results = store
+            .batch_set(&mixed_keys, &mixed_vals, &SetOptions::new().with_if_not_exist())
+            .await
+            .unwrap();
+        assert!(results[..50].iter().all(|r| matches!(r, Err(KvError::ConditionFailed))));
+        assert!(results[50].is_ok());
+
+        let (values, errors) = store.batch_get(&keys).await.unwrap();
+        assert_eq!(values, vals.iter().map(|&v| Some(v)).collect::<Vec<_>>());
+        assert!(errors.iter().all(|e| e.is_none()));
+        assert_eq!(store.len().await.unwrap(), 101);
+
+        store.batch_del(&keys).await.unwrap();
+        let (values, _) = store.batch_get(&keys).await.unwrap();
+        assert!(values.iter().all(|v| v.is_none()));
+        assert_eq!(store.keys().await.unwrap(), vec!["cross_slot:new".to_string()]);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    #[serial]
+    async fn test_sentinel_store() {
+        register_serde_serializers::<String>().unwrap();
+        register_serde_serializers::<i32>().unwrap();
+        register_serde_serializers::<i64>().unwrap();
+        let _servers = spawn_sentinel(7201, 7211);
+
+        test_set(make_sentinel_store::<String>()).await;
+        test_batch_set(make_sentinel_store::<i32>()).await;
+        test_batch_get(make_sentinel_store::<i32>()).await;
+        test_scan(make_sentinel_store::<String>()).await;
+        test_incr(make_sentinel_store::<i64>()).await;
+
+        // 数据写入主节点的 db 1
+        let mut con = wait_ready(7201);
+        let _: () = redis::cmd("SELECT").arg(1).query(&mut con).unwrap();
+        let value: Vec<u8> = redis::cmd("GET").arg("\"test_key\"").query(&mut con).unwrap();
+        assert_eq!(value, b"\"test_value\"");
+    }
 }
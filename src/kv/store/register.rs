@@ -1,12 +1,15 @@
 use anyhow::Result;
+use std::fmt::Debug;
 use std::hash::Hash;
 
-use crate::cfg::register_trait;
+use crate::cfg::{register_trait_fallible_with_schema, register_trait_with_schema};
 
 use super::{
-    DashMapStore, DashMapStoreConfig, LoadableSyncStore, LoadableSyncStoreConfig,
-    UnsafeHashMapStore, UnsafeHashMapStoreConfig, RedisStore,
+    AopStore, AopStoreConfig, DashMapStore, DashMapStoreConfig, DiskStore, DiskStoreConfig,
+    LoadableSyncStore, LoadableSyncStoreConfig, LruStore, LruStoreConfig, PrefixedStore,
+    PrefixedStoreConfig, UnsafeHashMapStore, UnsafeHashMapStoreConfig, RedisStore,
     RedisStoreConfig, RwLockHashMapStore, RwLockHashMapStoreConfig, Store, AsyncStore, SyncStore,
+    TieredStore, TieredStoreConfig,
 };
 
 /// 注册所有内存存储实现到 cfg 注册表（统一接口）
@@ -27,7 +30,9 @@ use super::{
 /// - `UnsafeHashMapStore` - 基于 HashMap 的非线程安全实现
 /// - `RwLockHashMapStore` - 基于 RwLock + HashMap 的线程安全实现
 /// - `DashMapStore` - 基于 DashMap 的线程安全实现（高并发性能更好）
+/// - `LruStore` - 有容量上限的 LRU / W-TinyLFU 缓存
 /// - `LoadableSyncStore` - 可加载数据的同步存储装饰器
+/// - `DiskStore` - 基于追加日志的持久化存储，使用前需注册键值的序列化器（如 `register_serde_serializers`）
 ///
 /// # 示例
 /// ```ignore
@@ -57,40 +62,55 @@ where
     V: Clone + Send + Sync + 'static,
 {
     // 注册统一接口 Store
-    register_trait::<UnsafeHashMapStore<K, V>, dyn Store<K, V>, UnsafeHashMapStoreConfig>("UnsafeHashMapStore")?;
-    register_trait::<RwLockHashMapStore<K, V>, dyn Store<K, V>, RwLockHashMapStoreConfig>(
+    register_trait_with_schema::<UnsafeHashMapStore<K, V>, dyn Store<K, V>, UnsafeHashMapStoreConfig>("UnsafeHashMapStore")?;
+    register_trait_with_schema::<RwLockHashMapStore<K, V>, dyn Store<K, V>, RwLockHashMapStoreConfig>(
         "RwLockHashMapStore",
     )?;
-    register_trait::<DashMapStore<K, V>, dyn Store<K, V>, DashMapStoreConfig>(
+    register_trait_with_schema::<DashMapStore<K, V>, dyn Store<K, V>, DashMapStoreConfig>(
         "DashMapStore",
     )?;
-    register_trait::<LoadableSyncStore<K, V>, dyn Store<K, V>, LoadableSyncStoreConfig>(
+    register_trait_with_schema::<LruStore<K, V>, dyn Store<K, V>, LruStoreConfig>("LruStore")?;
+    register_trait_with_schema::<LoadableSyncStore<K, V>, dyn Store<K, V>, LoadableSyncStoreConfig>(
         "LoadableSyncStore",
     )?;
+    register_trait_fallible_with_schema::<DiskStore<K, V>, dyn Store<K, V>, DiskStoreConfig, _>(
+        "DiskStore",
+        |config| Ok(DiskStore::new(config)?),
+    )?;
 
     // 注册纯同步接口 SyncStore
-    register_trait::<UnsafeHashMapStore<K, V>, dyn SyncStore<K, V>, UnsafeHashMapStoreConfig>("UnsafeHashMapStore")?;
-    register_trait::<RwLockHashMapStore<K, V>, dyn SyncStore<K, V>, RwLockHashMapStoreConfig>(
+    register_trait_with_schema::<UnsafeHashMapStore<K, V>, dyn SyncStore<K, V>, UnsafeHashMapStoreConfig>("UnsafeHashMapStore")?;
+    register_trait_with_schema::<RwLockHashMapStore<K, V>, dyn SyncStore<K, V>, RwLockHashMapStoreConfig>(
         "RwLockHashMapStore",
     )?;
-    register_trait::<DashMapStore<K, V>, dyn SyncStore<K, V>, DashMapStoreConfig>(
+    register_trait_with_schema::<DashMapStore<K, V>, dyn SyncStore<K, V>, DashMapStoreConfig>(
         "DashMapStore",
     )?;
-    register_trait::<LoadableSyncStore<K, V>, dyn SyncStore<K, V>, LoadableSyncStoreConfig>(
+    register_trait_with_schema::<LruStore<K, V>, dyn SyncStore<K, V>, LruStoreConfig>("LruStore")?;
+    register_trait_with_schema::<LoadableSyncStore<K, V>, dyn SyncStore<K, V>, LoadableSyncStoreConfig>(
         "LoadableSyncStore",
     )?;
+    register_trait_fallible_with_schema::<DiskStore<K, V>, dyn SyncStore<K, V>, DiskStoreConfig, _>(
+        "DiskStore",
+        |config| Ok(DiskStore::new(config)?),
+    )?;
 
     // 注册纯异步接口 AsyncStore
-    register_trait::<UnsafeHashMapStore<K, V>, dyn AsyncStore<K, V>, UnsafeHashMapStoreConfig>("UnsafeHashMapStore")?;
-    register_trait::<RwLockHashMapStore<K, V>, dyn AsyncStore<K, V>, RwLockHashMapStoreConfig>(
+    register_trait_with_schema::<UnsafeHashMapStore<K, V>, dyn AsyncStore<K, V>, UnsafeHashMapStoreConfig>("UnsafeHashMapStore")?;
+    register_trait_with_schema::<RwLockHashMapStore<K, V>, dyn AsyncStore<K, V>, RwLockHashMapStoreConfig>(
         "RwLockHashMapStore",
     )?;
-    register_trait::<DashMapStore<K, V>, dyn AsyncStore<K, V>, DashMapStoreConfig>(
+    register_trait_with_schema::<DashMapStore<K, V>, dyn AsyncStore<K, V>, DashMapStoreConfig>(
         "DashMapStore",
     )?;
-    register_trait::<LoadableSyncStore<K, V>, dyn AsyncStore<K, V>, LoadableSyncStoreConfig>(
+    register_trait_with_schema::<LruStore<K, V>, dyn AsyncStore<K, V>, LruStoreConfig>("LruStore")?;
+    register_trait_with_schema::<LoadableSyncStore<K, V>, dyn AsyncStore<K, V>, LoadableSyncStoreConfig>(
         "LoadableSyncStore",
     )?;
+    register_trait_fallible_with_schema::<DiskStore<K, V>, dyn AsyncStore<K, V>, DiskStoreConfig, _>(
+        "DiskStore",
+        |config| Ok(DiskStore::new(config)?),
+    )?;
 
     Ok(())
 }
@@ -111,6 +131,8 @@ where
 ///
 /// # 注册的类型
 /// - `RedisStore` - 基于 Redis 的分布式存储实现
+/// - `TieredStore` - 两级缓存存储，L1、L2 需要分别注册（如 `register_hash_stores`）
+/// - `PrefixedStore` - 为键添加命名空间前缀的装饰器，内部 Store 的键类型为 String
 ///
 /// # 前置条件
 /// 在调用此函数之前，必须先注册键和值类型的序列化器：
@@ -163,13 +185,75 @@ where
     V: Clone + Send + Sync + 'static,
 {
     // 注册统一接口 Store
-    register_trait::<RedisStore<K, V>, dyn Store<K, V>, RedisStoreConfig>("RedisStore")?;
+    register_trait_fallible_with_schema::<RedisStore<K, V>, dyn Store<K, V>, RedisStoreConfig, _>(
+        "RedisStore",
+        |config| Ok(RedisStore::new(config)?),
+    )?;
+    register_trait_fallible_with_schema::<TieredStore<K, V>, dyn Store<K, V>, TieredStoreConfig, _>(
+        "TieredStore",
+        TieredStore::new,
+    )?;
+    register_trait_fallible_with_schema::<PrefixedStore<K, V>, dyn Store<K, V>, PrefixedStoreConfig, _>(
+        "PrefixedStore",
+        PrefixedStore::new,
+    )?;
 
     // 注册纯同步接口 SyncStore
-    register_trait::<RedisStore<K, V>, dyn SyncStore<K, V>, RedisStoreConfig>("RedisStore")?;
+    register_trait_fallible_with_schema::<RedisStore<K, V>, dyn SyncStore<K, V>, RedisStoreConfig, _>(
+        "RedisStore",
+        |config| Ok(RedisStore::new(config)?),
+    )?;
+    register_trait_fallible_with_schema::<TieredStore<K, V>, dyn SyncStore<K, V>, TieredStoreConfig, _>(
+        "TieredStore",
+        TieredStore::new,
+    )?;
+    register_trait_fallible_with_schema::<PrefixedStore<K, V>, dyn SyncStore<K, V>, PrefixedStoreConfig, _>(
+        "PrefixedStore",
+        PrefixedStore::new,
+    )?;
 
     // 注册纯异步接口 AsyncStore
-    register_trait::<RedisStore<K, V>, dyn AsyncStore<K, V>, RedisStoreConfig>("RedisStore")?;
+    register_trait_fallible_with_schema::<RedisStore<K, V>, dyn AsyncStore<K, V>, RedisStoreConfig, _>(
+        "RedisStore",
+        |config| Ok(RedisStore::new(config)?),
+    )?;
+    register_trait_fallible_with_schema::<TieredStore<K, V>, dyn AsyncStore<K, V>, TieredStoreConfig, _>(
+        "TieredStore",
+        TieredStore::new,
+    )?;
+    register_trait_fallible_with_schema::<PrefixedStore<K, V>, dyn AsyncStore<K, V>, PrefixedStoreConfig, _>(
+        "PrefixedStore",
+        PrefixedStore::new,
+    )?;
+
+    Ok(())
+}
+
+/// 注册 AopStore 到 cfg 注册表（统一接口）
+///
+/// AopStore 在日志中会输出调用参数，因此要求键和值类型实现 `Debug`，
+/// 单独注册以免给 `register_stores` 增加额外约束。
+/// 内部 Store 需要通过对应的注册函数（如 `register_hash_stores`、`register_stores`）另行注册。
+///
+/// # 类型参数
+/// - `K`: 键类型，需要满足 `Clone + Send + Sync + Debug + 'static`
+/// - `V`: 值类型，需要满足 `Clone + Send + Sync + Debug + 'static`
+///
+/// # 示例
+/// ```ignore
+/// use rustx::kv::store::{register_aop_stores, register_hash_stores};
+///
+/// register_hash_stores::<String, String>()?;
+/// register_aop_stores::<String, String>()?;
+/// ```
+pub fn register_aop_stores<K, V>() -> Result<()>
+where
+    K: Clone + Send + Sync + Debug + 'static,
+    V: Clone + Send + Sync + Debug + 'static,
+{
+    register_trait_with_schema::<AopStore<K, V>, dyn Store<K, V>, AopStoreConfig>("AopStore")?;
+    register_trait_with_schema::<AopStore<K, V>, dyn SyncStore<K, V>, AopStoreConfig>("AopStore")?;
+    register_trait_with_schema::<AopStore<K, V>, dyn AsyncStore<K, V>, AopStoreConfig>("AopStore")?;
 
     Ok(())
 }
@@ -253,6 +337,30 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_register_lru_store() -> Result<()> {
+        register_hash_stores::<String, i32>()?;
+
+        // 测试 LruStore
+        let opts = TypeOptions::from_json(
+            r#"{
+            "type": "LruStore",
+            "options": { "max_entries": 1, "policy": "tinylfu" }
+        }"#,
+        )?;
+
+        let store: Box<dyn SyncStore<String, i32>> = create_trait_from_type_options(&opts)?;
+
+        store.set_sync(&"a".to_string(), &1, &SetOptions::new()).unwrap();
+        store.set_sync(&"b".to_string(), &2, &SetOptions::new()).unwrap();
+        let (values, _) = store
+            .batch_get_sync(&["a".to_string(), "b".to_string()])
+            .unwrap();
+        assert_eq!(values.iter().filter(|v| v.is_some()).count(), 1);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_register_multiple_type_combinations() -> Result<()> {
         // 注册多种类型组合
@@ -329,6 +437,128 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_register_tiered_store() -> Result<()> {
+        register_hash_stores::<String, String>()?;
+        register_stores::<String, String>()?;
+
+        let opts = TypeOptions::from_json(
+            r#"{
+            "type": "TieredStore",
+            "options": {
+                "l1": { "type": "LruStore", "options": { "max_entries": 100 } },
+                "l2": { "type": "DashMapStore", "options": {} },
+                "backfill_ttl": "10s"
+            }
+        }"#,
+        )?;
+
+        let store: Box<dyn AsyncStore<String, String>> = create_trait_from_type_options(&opts)?;
+        store
+            .set(&"key".to_string(), &"value".to_string(), &SetOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(store.get(&"key".to_string()).await.unwrap(), "value");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_tiered_store_invalid_config() -> Result<()> {
+        register_hash_stores::<String, String>()?;
+        register_stores::<String, String>()?;
+
+        // 内部 store 创建失败时返回错误而不是 panic
+        let opts = TypeOptions::from_json(
+            r#"{
+            "type": "TieredStore",
+            "options": {
+                "l1": { "type": "DashMapStore", "options": {} },
+                "l2": { "type": "NotExistStore", "options": {} }
+            }
+        }"#,
+        )?;
+
+        let result = create_trait_from_type_options::<dyn Store<String, String>>(&opts);
+        assert!(result.err().unwrap().to_string().contains("l2"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_register_aop_store() -> Result<()> {
+        register_hash_stores::<String, String>()?;
+        register_aop_stores::<String, String>()?;
+
+        let opts = TypeOptions::from_json(
+            r#"{
+            "type": "AopStore",
+            "options": {
+                "store": { "type": "DashMapStore", "options": {} },
+                "aop": { "retry": { "max_times": 2, "strategy": "constant", "delay": "1ms" } }
+            }
+        }"#,
+        )?;
+
+        let store: Box<dyn AsyncStore<String, String>> = create_trait_from_type_options(&opts)?;
+        store
+            .set(&"key".to_string(), &"value".to_string(), &SetOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(store.get(&"key".to_string()).await.unwrap(), "value");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_register_prefixed_store() -> Result<()> {
+        register_hash_stores::<String, String>()?;
+        register_stores::<String, String>()?;
+
+        let opts = TypeOptions::from_json(
+            r#"{
+            "type": "PrefixedStore",
+            "options": {
+                "store": { "type": "DashMapStore", "options": {} },
+                "prefix": "team-a:"
+            }
+        }"#,
+        )?;
+
+        let store: Box<dyn AsyncStore<String, String>> = create_trait_from_type_options(&opts)?;
+        store
+            .set(&"key".to_string(), &"value".to_string(), &SetOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(store.get(&"key".to_string()).await.unwrap(), "value");
+        assert_eq!(store.keys().await.unwrap(), vec!["key".to_string()]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_prefixed_store_invalid_config() -> Result<()> {
+        register_hash_stores::<String, String>()?;
+        register_stores::<String, String>()?;
+
+        // 前缀为空时校验失败，返回错误而不是 panic
+        let opts = TypeOptions::from_json(
+            r#"{
+            "type": "PrefixedStore",
+            "options": {
+                "store": { "type": "DashMapStore", "options": {} },
+                "prefix": ""
+            }
+        }"#,
+        )?;
+
+        let result = create_trait_from_type_options::<dyn Store<String, String>>(&opts);
+        let err = result.err().unwrap();
+        assert!(err.to_string().contains("configuration validation failed"));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_register_redis_stores_unified() -> Result<()> {
         use crate::kv::serializer::register_serde_serializers;
@@ -356,4 +586,26 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_register_redis_store_invalid_config() -> Result<()> {
+        register_stores::<String, String>()?;
+
+        // 既没有 endpoint 也没有 endpoints，构造失败应以错误返回而不是 panic
+        let opts = TypeOptions::from_json(
+            r#"{
+            "type": "RedisStore",
+            "options": {}
+        }"#,
+        )?;
+
+        let result = create_trait_from_type_options::<dyn Store<String, String>>(&opts);
+        let err = result.err().expect("misconfigured RedisStore should fail");
+        assert!(err.to_string().contains("Either endpoint or endpoints must be set"));
+
+        let result = create_trait_from_type_options::<dyn AsyncStore<String, String>>(&opts);
+        assert!(result.is_err());
+
+        Ok(())
+    }
 }
@@ -1,28 +1,50 @@
 use garde::Validate;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
 use std::collections::HashMap;
 use std::hash::Hash;
-use std::sync::RwLock;
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Duration, Instant};
 
-use super::core::{IsSyncStore, KvError, SetOptions, Store, AsyncStore, SyncStore};
+use super::core::{
+    incr_value, IsSyncStore, KvError, SetOptions, Store, AsyncStore, Subscription, SyncStore,
+};
+use super::expiry::{live_keys, scan_live_keys, Entry, Sweeper};
+use super::notify::ChangeNotifier;
+use crate::kv::parser::ChangeType;
+use crate::cfg::serde_duration::{serde_as, HumanDur};
 
 /// MapStore 配置结构体
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, SmartDefault, Validate)]
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, SmartDefault, Validate, JsonSchema)]
 #[serde(default)]
 pub struct RwLockHashMapStoreConfig {
     /// 初始容量（可选）
     #[garde(skip)]
     pub initial_capacity: Option<usize>,
+
+    /// 过期键的后台清理间隔，首次设置带过期时间的键时启动清理线程
+    #[serde_as(as = "HumanDur")]
+    #[default(Duration::from_secs(60))]
+    #[garde(skip)]
+    pub sweep_interval: Duration,
 }
 
 /// 基于内存 HashMap 的 KV 存储实现（对应 Golang MapStore）
+///
+/// 支持 `SetOptions.expiration`：读取时惰性淘汰已过期的键，并由后台线程按 `sweep_interval` 定期清理
+///
+/// 支持 `subscribe`：写入和删除时通知订阅者，过期淘汰不产生事件
 pub struct RwLockHashMapStore<K, V>
 where
     K: Clone + Send + Sync + Eq + Hash,
     V: Clone + Send + Sync,
 {
-    map: RwLock<HashMap<K, V>>,
+    map: Arc<RwLock<HashMap<K, Entry<V>>>>,
+    sweep_interval: Duration,
+    sweeper: OnceLock<Sweeper>,
+    notifier: ChangeNotifier<K>,
 }
 
 impl<K, V> RwLockHashMapStore<K, V>
@@ -38,11 +60,48 @@ where
         };
 
         Self {
-            map: RwLock::new(initial_map),
+            map: Arc::new(RwLock::new(initial_map)),
+            sweep_interval: config.sweep_interval,
+            sweeper: OnceLock::new(),
+            notifier: ChangeNotifier::new(),
+        }
+    }
+
+    /// 删除已过期的键（读取时惰性淘汰）
+    fn evict_expired(&self, keys: &[&K], now: Instant) {
+        let mut map = self.map.write().unwrap();
+        for key in keys {
+            if map.get(*key).is_some_and(|entry| entry.is_expired(now)) {
+                map.remove(*key);
+            }
         }
     }
 }
 
+impl<K, V> RwLockHashMapStore<K, V>
+where
+    K: Clone + Send + Sync + Eq + Hash + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    /// 设置带过期时间的键时启动后台清理线程
+    fn ensure_sweeper(&self, options: &SetOptions) {
+        if options.expiration.is_none() {
+            return;
+        }
+        self.sweeper.get_or_init(|| {
+            let map = Arc::downgrade(&self.map);
+            Sweeper::spawn(self.sweep_interval, move || match map.upgrade() {
+                Some(map) => {
+                    let now = Instant::now();
+                    map.write().unwrap().retain(|_, entry| !entry.is_expired(now));
+                    true
+                }
+                None => false,
+            })
+        });
+    }
+}
+
 impl<K, V> Default for RwLockHashMapStore<K, V>
 where
     K: Clone + Send + Sync + Eq + Hash,
@@ -63,34 +122,44 @@ where
 
 impl<K, V> SyncStore<K, V> for RwLockHashMapStore<K, V>
 where
-    K: Clone + Send + Sync + Eq + Hash,
-    V: Clone + Send + Sync,
+    K: Clone + Send + Sync + Eq + Hash + 'static,
+    V: Clone + Send + Sync + 'static,
 {
     fn set_sync(&self, key: &K, value: &V, options: &SetOptions) -> Result<(), KvError> {
+        self.ensure_sweeper(options);
+        let now = Instant::now();
         let mut map = self.map.write().unwrap();
 
-        // 检查 if_not_exist 条件
-        if options.if_not_exist && map.contains_key(key) {
-            return Err(KvError::ConditionFailed);
-        }
+        // 检查 if_not_exist、if_exist 条件，已过期的键视为不存在
+        let exists = map.get(key).is_some_and(|entry| !entry.is_expired(now));
+        options.check_condition(exists)?;
 
-        // 注意：当前实现忽略了 expiration，因为基本的 HashMap 不支持 TTL
-        // 在实际生产环境中，可以考虑使用支持 TTL 的数据结构
-        map.insert(key.clone(), value.clone());
+        map.insert(key.clone(), Entry::new(value.clone(), options));
+        self.notifier.notify_set(key, exists);
         Ok(())
     }
 
     fn get_sync(&self, key: &K) -> Result<V, KvError> {
-        let map = self.map.read().unwrap();
-        match map.get(key) {
-            Some(value) => Ok(value.clone()),
-            None => Err(KvError::KeyNotFound),
+        let now = Instant::now();
+        {
+            let map = self.map.read().unwrap();
+            match map.get(key) {
+                Some(entry) if !entry.is_expired(now) => return Ok(entry.value.clone()),
+                Some(_) => {}
+                None => return Err(KvError::KeyNotFound),
+            }
         }
+
+        self.evict_expired(&[key], now);
+        Err(KvError::KeyNotFound)
     }
 
     fn del_sync(&self, key: &K) -> Result<(), KvError> {
+        let now = Instant::now();
         let mut map = self.map.write().unwrap();
-        map.remove(key);
+        if map.remove(key).is_some_and(|entry| !entry.is_expired(now)) {
+            self.notifier.notify(ChangeType::Delete, key);
+        }
         Ok(())
     }
 
@@ -106,17 +175,21 @@ where
             ));
         }
 
+        self.ensure_sweeper(options);
+        let now = Instant::now();
         let mut map = self.map.write().unwrap();
         let mut results = Vec::with_capacity(keys.len());
 
         for (key, value) in keys.iter().zip(vals.iter()) {
-            // 检查 if_not_exist 条件
-            if options.if_not_exist && map.contains_key(key) {
-                results.push(Err(KvError::ConditionFailed));
+            // 检查 if_not_exist、if_exist 条件
+            let exists = map.get(key).is_some_and(|entry| !entry.is_expired(now));
+            if let Err(e) = options.check_condition(exists) {
+                results.push(Err(e));
                 continue;
             }
 
-            map.insert(key.clone(), value.clone());
+            map.insert(key.clone(), Entry::new(value.clone(), options));
+            self.notifier.notify_set(key, exists);
             results.push(Ok(()));
         }
 
@@ -127,32 +200,46 @@ where
         &self,
         keys: &[K],
     ) -> Result<(Vec<Option<V>>, Vec<Option<KvError>>), KvError> {
-        let map = self.map.read().unwrap();
+        let now = Instant::now();
         let mut values = Vec::with_capacity(keys.len());
         let mut errors = Vec::with_capacity(keys.len());
-
-        for key in keys {
-            match map.get(key) {
-                Some(value) => {
-                    values.push(Some(value.clone()));
-                    errors.push(None);
-                }
-                None => {
-                    values.push(None);
-                    errors.push(Some(KvError::KeyNotFound));
+        let mut expired = Vec::new();
+
+        {
+            let map = self.map.read().unwrap();
+            for key in keys {
+                match map.get(key) {
+                    Some(entry) if !entry.is_expired(now) => {
+                        values.push(Some(entry.value.clone()));
+                        errors.push(None);
+                    }
+                    other => {
+                        if other.is_some() {
+                            expired.push(key);
+                        }
+                        values.push(None);
+                        errors.push(Some(KvError::KeyNotFound));
+                    }
                 }
             }
         }
 
+        if !expired.is_empty() {
+            self.evict_expired(&expired, now);
+        }
+
         Ok((values, errors))
     }
 
     fn batch_del_sync(&self, keys: &[K]) -> Result<Vec<Result<(), KvError>>, KvError> {
+        let now = Instant::now();
         let mut map = self.map.write().unwrap();
         let mut results = Vec::with_capacity(keys.len());
 
         for key in keys {
-            map.remove(key);
+            if map.remove(key).is_some_and(|entry| !entry.is_expired(now)) {
+                self.notifier.notify(ChangeType::Delete, key);
+            }
             results.push(Ok(()));
         }
 
@@ -163,6 +250,77 @@ where
         // 不清空数据，只做资源清理
         Ok(())
     }
+
+    fn scan_sync(&self, cursor: u64, count: usize) -> Result<(u64, Vec<K>), KvError> {
+        let map = self.map.read().unwrap();
+        Ok(scan_live_keys(map.iter(), cursor, count, Instant::now()))
+    }
+
+    fn keys_sync(&self) -> Result<Vec<K>, KvError> {
+        let map = self.map.read().unwrap();
+        Ok(live_keys(map.iter(), Instant::now(), |_| true))
+    }
+
+    fn keys_with_prefix_sync(&self, prefix: &str) -> Result<Vec<K>, KvError>
+    where
+        K: AsRef<str>,
+    {
+        let map = self.map.read().unwrap();
+        Ok(live_keys(map.iter(), Instant::now(), |key| {
+            key.as_ref().starts_with(prefix)
+        }))
+    }
+
+    fn len_sync(&self) -> Result<usize, KvError> {
+        let now = Instant::now();
+        let map = self.map.read().unwrap();
+        Ok(map.values().filter(|entry| !entry.is_expired(now)).count())
+    }
+
+    fn compare_and_swap_sync(
+        &self,
+        key: &K,
+        expected: &V,
+        value: &V,
+        options: &SetOptions,
+    ) -> Result<(), KvError>
+    where
+        V: PartialEq,
+    {
+        self.ensure_sweeper(options);
+        let now = Instant::now();
+        let mut map = self.map.write().unwrap();
+
+        match map.get(key).and_then(|entry| entry.live_value(now)) {
+            Some(current) if current == expected => {
+                map.insert(key.clone(), Entry::new(value.clone(), options));
+                self.notifier.notify(ChangeType::Update, key);
+                Ok(())
+            }
+            _ => Err(KvError::ConditionFailed),
+        }
+    }
+
+    fn incr_sync(&self, key: &K, delta: i64) -> Result<i64, KvError>
+    where
+        V: Into<i64> + TryFrom<i64>,
+    {
+        let now = Instant::now();
+        let mut map = self.map.write().unwrap();
+
+        // 保留原有的过期时间，已过期的键从 0 开始
+        let live = map.get(key).filter(|entry| !entry.is_expired(now));
+        let (next, value) = incr_value(live.map(|entry| &entry.value), delta)?;
+        let expire_at = live.and_then(|entry| entry.expire_at);
+        let exists = live.is_some();
+        map.insert(key.clone(), Entry { value, expire_at });
+        self.notifier.notify_set(key, exists);
+        Ok(next)
+    }
+
+    fn subscribe_sync(&self) -> Result<Subscription<K>, KvError> {
+        Ok(self.notifier.subscribe())
+    }
 }
 
 // 为 RwLockHashMapStore 实现 From trait - 使用标准库 trait
@@ -297,6 +455,66 @@ mod tests {
         test_close_sync(store);
     }
 
+    #[tokio::test]
+    async fn test_store_expiration() {
+        let store = RwLockHashMapStore::<String, String>::new(RwLockHashMapStoreConfig::default());
+        test_expiration(store).await;
+    }
+
+    #[test]
+    fn test_store_expiration_sync() {
+        let store = RwLockHashMapStore::<String, String>::new(RwLockHashMapStoreConfig::default());
+        test_expiration_sync(store);
+    }
+
+    #[tokio::test]
+    async fn test_store_scan() {
+        let store = RwLockHashMapStore::<String, String>::new(RwLockHashMapStoreConfig::default());
+        test_scan(store).await;
+    }
+
+    #[test]
+    fn test_store_scan_sync() {
+        let store = RwLockHashMapStore::<String, String>::new(RwLockHashMapStoreConfig::default());
+        test_scan_sync(store);
+    }
+
+    #[tokio::test]
+    async fn test_store_compare_and_swap() {
+        let store = RwLockHashMapStore::<String, String>::new(RwLockHashMapStoreConfig::default());
+        test_compare_and_swap(store).await;
+    }
+
+    #[test]
+    fn test_store_compare_and_swap_sync() {
+        let store = RwLockHashMapStore::<String, String>::new(RwLockHashMapStoreConfig::default());
+        test_compare_and_swap_sync(store);
+    }
+
+    #[tokio::test]
+    async fn test_store_incr() {
+        let store = RwLockHashMapStore::<String, i64>::new(RwLockHashMapStoreConfig::default());
+        test_incr(store).await;
+    }
+
+    #[test]
+    fn test_store_incr_sync() {
+        let store = RwLockHashMapStore::<String, i64>::new(RwLockHashMapStoreConfig::default());
+        test_incr_sync(store);
+    }
+
+    #[tokio::test]
+    async fn test_store_subscribe() {
+        let store = RwLockHashMapStore::<String, String>::new(RwLockHashMapStoreConfig::default());
+        test_subscribe(store).await;
+    }
+
+    #[test]
+    fn test_store_subscribe_sync() {
+        let store = RwLockHashMapStore::<String, String>::new(RwLockHashMapStoreConfig::default());
+        test_subscribe_sync(store);
+    }
+
     // ========== 场景测试 ==========
 
     #[tokio::test]
@@ -438,4 +656,74 @@ mod tests {
         let final_value = store.get(&"final_test".to_string()).await.unwrap();
         assert_eq!(final_value, 999);
     }
+
+    #[test]
+    fn test_expired_keys_swept_in_background() {
+        let store = RwLockHashMapStore::<String, i32>::new(RwLockHashMapStoreConfig {
+            sweep_interval: Duration::from_millis(50),
+            ..Default::default()
+        });
+        let ttl = SetOptions::new().with_expiration(Duration::from_millis(20));
+        for i in 0..10 {
+            store.set_sync(&format!("key{}", i), &i, &ttl).unwrap();
+        }
+        store.set_sync(&"persist".to_string(), &0, &SetOptions::new()).unwrap();
+        assert_eq!(store.map.read().unwrap().len(), 11);
+
+        // 未读取的过期键由后台线程清理
+        std::thread::sleep(Duration::from_millis(200));
+        assert_eq!(store.map.read().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_concurrent_incr_and_compare_and_swap() {
+        let store = Arc::new(RwLockHashMapStore::<String, i64>::new(RwLockHashMapStoreConfig::default()));
+        let key = "counter".to_string();
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let store = Arc::clone(&store);
+                let key = key.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..500 {
+                        store.incr_sync(&key, 1).unwrap();
+
+                        // 读取后交换，冲突时重试
+                        loop {
+                            let current = store.get_sync(&key).unwrap();
+                            match store.compare_and_swap_sync(&key, &current, &(current + 1), &SetOptions::new()) {
+                                Ok(()) => break,
+                                Err(KvError::ConditionFailed) => continue,
+                                Err(e) => panic!("unexpected error: {}", e),
+                            }
+                        }
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        // 没有丢失的更新
+        assert_eq!(store.get_sync(&key).unwrap(), 8 * 500 * 2);
+    }
+
+    #[test]
+    fn test_incr_keeps_expiration() {
+        let store = RwLockHashMapStore::<String, i64>::new(RwLockHashMapStoreConfig::default());
+        let key = "counter".to_string();
+        store
+            .set_sync(&key, &1, &SetOptions::new().with_expiration(Duration::from_millis(100)))
+            .unwrap();
+        assert_eq!(store.incr_sync(&key, 1).unwrap(), 2);
+
+        std::thread::sleep(Duration::from_millis(150));
+        assert!(matches!(store.get_sync(&key), Err(KvError::KeyNotFound)));
+
+        // 过期后从 0 开始，且不再过期
+        assert_eq!(store.incr_sync(&key, 1).unwrap(), 1);
+        std::thread::sleep(Duration::from_millis(150));
+        assert_eq!(store.get_sync(&key).unwrap(), 1);
+    }
 }
@@ -0,0 +1,547 @@
+use async_trait::async_trait;
+use garde::Validate;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use smart_default::SmartDefault;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::cfg::serde_duration::{serde_as, HumanDur};
+use crate::cfg::{create_shared_trait_from_type_options, TypeOptions};
+
+use super::core::{AsyncStore, IsAsyncStore, KvError, SetOptions, Store, Subscription, SyncStore};
+
+/// TieredStore 配置
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, SmartDefault, Validate, JsonSchema)]
+#[serde(default)]
+pub struct TieredStoreConfig {
+    /// L1 存储配置（通常为本地内存存储），支持 `{"$instance": "name"}` 引用共享实例
+    #[garde(skip)]
+    pub l1: TypeOptions,
+
+    /// L2 存储配置（通常为 RedisStore），支持 `{"$instance": "name"}` 引用共享实例
+    #[garde(skip)]
+    pub l2: TypeOptions,
+
+    /// 写入 L1 的最长过期时间，从 L2 回填的键使用该过期时间，0 表示不过期
+    #[serde_as(as = "HumanDur")]
+    #[default(Duration::from_secs(60))]
+    #[garde(skip)]
+    pub backfill_ttl: Duration,
+}
+
+/// 两级缓存存储
+///
+/// - 读：先读 L1，未命中时读 L2 并回填 L1；`batch_get` 只从 L2 读取 L1 未命中的键
+/// - 写：先写 L2，成功后再写 L1，`if_not_exist`、`if_exist` 只在 L2 上判断
+/// - 删：同时删除 L2 和 L1
+///
+/// L2 是数据的来源，L1 的读写失败不影响结果；写入 L1 的过期时间不超过 `backfill_ttl`，
+/// 以限制其他实例直接更新 L2 时 L1 数据过期的时长
+pub struct TieredStore<K, V>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    l1: Arc<dyn AsyncStore<K, V>>,
+    l2: Arc<dyn AsyncStore<K, V>>,
+    backfill_ttl: Duration,
+    // 为共享实例时，由实例的所有者负责关闭
+    owns_l1: bool,
+    owns_l2: bool,
+}
+
+impl<K, V> TieredStore<K, V>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    pub fn new(config: TieredStoreConfig) -> Result<Self, anyhow::Error> {
+        let l1 = create_shared_trait_from_type_options::<dyn AsyncStore<K, V>>(&config.l1)
+            .map_err(|e| anyhow::anyhow!("failed to create l1 store: {}", e))?;
+        let l2 = create_shared_trait_from_type_options::<dyn AsyncStore<K, V>>(&config.l2)
+            .map_err(|e| anyhow::anyhow!("failed to create l2 store: {}", e))?;
+
+        Ok(Self {
+            l1,
+            l2,
+            backfill_ttl: config.backfill_ttl,
+            owns_l1: config.l1.instance_name().is_none(),
+            owns_l2: config.l2.instance_name().is_none(),
+        })
+    }
+
+    /// 写入 L1 的选项：不做条件判断，过期时间不超过 backfill_ttl
+    fn l1_options(&self, options: &SetOptions) -> SetOptions {
+        let expiration = match (options.expiration, self.backfill_ttl) {
+            (expiration, Duration::ZERO) => expiration,
+            (Some(expiration), ttl) => Some(expiration.min(ttl)),
+            (None, ttl) => Some(ttl),
+        };
+        SetOptions {
+            expiration,
+            ..SetOptions::default()
+        }
+    }
+
+    async fn backfill(&self, keys: &[K], vals: &[V]) {
+        if keys.is_empty() {
+            return;
+        }
+        let options = self.l1_options(&SetOptions::new());
+        let _ = self.l1.batch_set(keys, vals, &options).await;
+    }
+}
+
+#[async_trait]
+impl<K, V> AsyncStore<K, V> for TieredStore<K, V>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    async fn set(&self, key: &K, value: &V, options: &SetOptions) -> Result<(), KvError> {
+        self.l2.set(key, value, options).await?;
+
+        // L1 写入失败时删除旧值，避免读到过期数据
+        if self.l1.set(key, value, &self.l1_options(options)).await.is_err() {
+            let _ = self.l1.del(key).await;
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &K) -> Result<V, KvError> {
+        if let Ok(value) = self.l1.get(key).await {
+            return Ok(value);
+        }
+
+        let value = self.l2.get(key).await?;
+        self.backfill(std::slice::from_ref(key), std::slice::from_ref(&value))
+            .await;
+        Ok(value)
+    }
+
+    async fn del(&self, key: &K) -> Result<(), KvError> {
+        self.l2.del(key).await?;
+        let _ = self.l1.del(key).await;
+        Ok(())
+    }
+
+    async fn batch_set(
+        &self,
+        keys: &[K],
+        vals: &[V],
+        options: &SetOptions,
+    ) -> Result<Vec<Result<(), KvError>>, KvError> {
+        let results = self.l2.batch_set(keys, vals, options).await?;
+
+        // 只把 L2 写入成功的键写入 L1
+        let (l1_keys, l1_vals): (Vec<K>, Vec<V>) = keys
+            .iter()
+            .zip(vals.iter())
+            .zip(results.iter())
+            .filter(|(_, result)| result.is_ok())
+            .map(|((key, value), _)| (key.clone(), value.clone()))
+            .unzip();
+        if !l1_keys.is_empty() {
+            let l1_options = self.l1_options(options);
+            if self.l1.batch_set(&l1_keys, &l1_vals, &l1_options).await.is_err() {
+                let _ = self.l1.batch_del(&l1_keys).await;
+            }
+        }
+
+        Ok(results)
+    }
+
+    async fn batch_get(
+        &self,
+        keys: &[K],
+    ) -> Result<(Vec<Option<V>>, Vec<Option<KvError>>), KvError> {
+        let (mut values, mut errors) = match self.l1.batch_get(keys).await {
+            Ok(result) => result,
+            Err(_) => (vec![None; keys.len()], (0..keys.len()).map(|_| None).collect()),
+        };
+
+        let misses: Vec<usize> = (0..keys.len()).filter(|&i| values[i].is_none()).collect();
+        if misses.is_empty() {
+            return Ok((values, errors));
+        }
+
+        let miss_keys: Vec<K> = misses.iter().map(|&i| keys[i].clone()).collect();
+        let (l2_values, l2_errors) = self.l2.batch_get(&miss_keys).await?;
+
+        let mut backfill_keys = Vec::new();
+        let mut backfill_vals = Vec::new();
+        for ((index, value), error) in misses.into_iter().zip(l2_values).zip(l2_errors) {
+            if let Some(value) = &value {
+                backfill_keys.push(keys[index].clone());
+                backfill_vals.push(value.clone());
+            }
+            values[index] = value;
+            errors[index] = error;
+        }
+        self.backfill(&backfill_keys, &backfill_vals).await;
+
+        Ok((values, errors))
+    }
+
+    async fn batch_del(&self, keys: &[K]) -> Result<Vec<Result<(), KvError>>, KvError> {
+        let results = self.l2.batch_del(keys).await?;
+        let _ = self.l1.batch_del(keys).await;
+        Ok(results)
+    }
+
+    // 遍历以 L2 为准
+    async fn scan(&self, cursor: u64, count: usize) -> Result<(u64, Vec<K>), KvError> {
+        self.l2.scan(cursor, count).await
+    }
+
+    async fn keys(&self) -> Result<Vec<K>, KvError> {
+        self.l2.keys().await
+    }
+
+    async fn keys_with_prefix(&self, prefix: &str) -> Result<Vec<K>, KvError>
+    where
+        K: AsRef<str>,
+    {
+        self.l2.keys_with_prefix(prefix).await
+    }
+
+    async fn len(&self) -> Result<usize, KvError> {
+        self.l2.len().await
+    }
+
+    // 订阅 L2 的变更
+    async fn subscribe(&self) -> Result<Subscription<K>, KvError> {
+        self.l2.subscribe().await
+    }
+
+    // 原子操作在 L2 上执行，成功后删除 L1 中的旧值
+    async fn compare_and_swap(
+        &self,
+        key: &K,
+        expected: &V,
+        value: &V,
+        options: &SetOptions,
+    ) -> Result<(), KvError>
+    where
+        V: PartialEq,
+    {
+        self.l2.compare_and_swap(key, expected, value, options).await?;
+        let _ = self.l1.del(key).await;
+        Ok(())
+    }
+
+    async fn incr(&self, key: &K, delta: i64) -> Result<i64, KvError>
+    where
+        V: Into<i64> + TryFrom<i64>,
+    {
+        let value = self.l2.incr(key, delta).await?;
+        let _ = self.l1.del(key).await;
+        Ok(value)
+    }
+
+    async fn close(&self) -> Result<(), KvError> {
+        if self.owns_l1 {
+            self.l1.close().await?;
+        }
+        if self.owns_l2 {
+            self.l2.close().await?;
+        }
+        Ok(())
+    }
+
+    fn is_exclusive(&self) -> bool {
+        self.l1.is_exclusive() || self.l2.is_exclusive()
+    }
+}
+
+impl<K, V> From<TieredStoreConfig> for TieredStore<K, V>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn from(config: TieredStoreConfig) -> Self {
+        TieredStore::new(config).expect("Failed to create TieredStore")
+    }
+}
+
+impl<K, V> From<Box<TieredStore<K, V>>> for Box<dyn AsyncStore<K, V>>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn from(source: Box<TieredStore<K, V>>) -> Self {
+        source as Box<dyn AsyncStore<K, V>>
+    }
+}
+
+impl<K, V> From<Box<TieredStore<K, V>>> for Box<dyn SyncStore<K, V>>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn from(source: Box<TieredStore<K, V>>) -> Self {
+        source as Box<dyn SyncStore<K, V>>
+    }
+}
+
+impl<K, V> From<Box<TieredStore<K, V>>> for Box<dyn Store<K, V>>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn from(source: Box<TieredStore<K, V>>) -> Self {
+        source as Box<dyn Store<K, V>>
+    }
+}
+
+/// 实现 IsAsyncStore 标记，让 TieredStore 自动获得 SyncStore 能力
+impl<K, V> IsAsyncStore for TieredStore<K, V>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cfg::register_instance;
+    use crate::kv::store::common_tests::*;
+    use crate::kv::store::{register_hash_stores, DashMapStore, DashMapStoreConfig};
+
+    fn make_config(backfill_ttl: &str) -> TieredStoreConfig {
+        serde_json::from_value(serde_json::json!({
+            "l1": {"type": "DashMapStore", "options": {}},
+            "l2": {"type": "RwLockHashMapStore", "options": {}},
+            "backfill_ttl": backfill_ttl
+        }))
+        .unwrap()
+    }
+
+    fn make_store<V>() -> TieredStore<String, V>
+    where
+        V: Clone + Send + Sync + 'static,
+    {
+        register_hash_stores::<String, V>().unwrap();
+        TieredStore::new(make_config("1m")).unwrap()
+    }
+
+    type SharedStore = Arc<dyn AsyncStore<String, String>>;
+
+    // 使用命名实例作为 L1 和 L2，便于在测试中直接检查两级存储
+    fn make_store_with_instances(
+        name: &str,
+        backfill_ttl: &str,
+    ) -> (TieredStore<String, String>, SharedStore, SharedStore) {
+        register_hash_stores::<String, String>().unwrap();
+        let l1: SharedStore = Arc::new(DashMapStore::new(DashMapStoreConfig::default()));
+        let l2: SharedStore = Arc::new(DashMapStore::new(DashMapStoreConfig::default()));
+        let l1_name = format!("{}-l1", name);
+        let l2_name = format!("{}-l2", name);
+        register_instance::<dyn AsyncStore<String, String>>(&l1_name, l1.clone()).unwrap();
+        register_instance::<dyn AsyncStore<String, String>>(&l2_name, l2.clone()).unwrap();
+
+        let config: TieredStoreConfig = serde_json::from_value(serde_json::json!({
+            "l1": {"$instance": l1_name},
+            "l2": {"$instance": l2_name},
+            "backfill_ttl": backfill_ttl
+        }))
+        .unwrap();
+        (TieredStore::new(config).unwrap(), l1, l2)
+    }
+
+    // ========== 公共测试 ==========
+
+    #[tokio::test]
+    async fn test_store_set() {
+        test_set(make_store::<String>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_get() {
+        test_get(make_store::<String>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_del() {
+        test_del(make_store::<String>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_batch_set() {
+        test_batch_set(make_store::<i32>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_batch_get() {
+        test_batch_get(make_store::<i32>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_batch_del() {
+        test_batch_del(make_store::<i32>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_close() {
+        test_close(make_store::<i32>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_expiration() {
+        test_expiration(make_store::<String>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_scan() {
+        test_scan(make_store::<String>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_compare_and_swap() {
+        test_compare_and_swap(make_store::<String>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_incr() {
+        test_incr(make_store::<i64>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_subscribe() {
+        test_subscribe(make_store::<String>()).await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_store_set_sync() {
+        test_set_sync(make_store::<String>());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_store_get_sync() {
+        test_get_sync(make_store::<String>());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_store_batch_get_sync() {
+        test_batch_get_sync(make_store::<i32>());
+    }
+
+    // ========== 场景测试 ==========
+
+    #[tokio::test]
+    async fn test_read_through_backfill() {
+        let (store, l1, l2) = make_store_with_instances("tiered-read-through", "1m");
+        let key = "key".to_string();
+        l2.set(&key, &"l2_value".to_string(), &SetOptions::new())
+            .await
+            .unwrap();
+        assert!(matches!(l1.get(&key).await, Err(KvError::KeyNotFound)));
+
+        // L1 未命中时读 L2 并回填 L1
+        assert_eq!(store.get(&key).await.unwrap(), "l2_value");
+        assert_eq!(l1.get(&key).await.unwrap(), "l2_value");
+
+        // L1 命中时不再读 L2
+        l2.set(&key, &"updated".to_string(), &SetOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(store.get(&key).await.unwrap(), "l2_value");
+
+        // 两级都不存在
+        let result = store.get(&"missing".to_string()).await;
+        assert!(matches!(result, Err(KvError::KeyNotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_write_through() {
+        let (store, l1, l2) = make_store_with_instances("tiered-write-through", "1m");
+        let key = "key".to_string();
+        store
+            .set(&key, &"value".to_string(), &SetOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(l1.get(&key).await.unwrap(), "value");
+        assert_eq!(l2.get(&key).await.unwrap(), "value");
+
+        // if_not_exist 以 L2 为准
+        l1.del(&key).await.unwrap();
+        let result = store
+            .set(&key, &"other".to_string(), &SetOptions::new().with_if_not_exist())
+            .await;
+        assert!(matches!(result, Err(KvError::ConditionFailed)));
+        assert!(l1.get(&key).await.is_err());
+
+        store.del(&key).await.unwrap();
+        assert!(l1.get(&key).await.is_err());
+        assert!(l2.get(&key).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_batch_get_fetches_misses_from_l2() {
+        let (store, l1, l2) = make_store_with_instances("tiered-batch-get", "1m");
+        let keys: Vec<String> = (0..4).map(|i| format!("key{}", i)).collect();
+        for key in &keys {
+            l2.set(key, &format!("l2_{}", key), &SetOptions::new())
+                .await
+                .unwrap();
+        }
+        l1.set(&keys[0], &"l1_key0".to_string(), &SetOptions::new())
+            .await
+            .unwrap();
+        l2.del(&keys[3]).await.unwrap();
+
+        let (values, errors) = store.batch_get(&keys).await.unwrap();
+        assert_eq!(
+            values,
+            vec![
+                Some("l1_key0".to_string()),
+                Some("l2_key1".to_string()),
+                Some("l2_key2".to_string()),
+                None,
+            ]
+        );
+        assert!(matches!(errors[3], Some(KvError::KeyNotFound)));
+
+        // 只有从 L2 读到的键被回填
+        assert_eq!(l1.get(&keys[1]).await.unwrap(), "l2_key1");
+        assert_eq!(l1.get(&keys[2]).await.unwrap(), "l2_key2");
+        assert!(l1.get(&keys[3]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_backfill_ttl() {
+        let (store, l1, l2) = make_store_with_instances("tiered-backfill-ttl", "100ms");
+        let key = "key".to_string();
+        store
+            .set(&key, &"value".to_string(), &SetOptions::new())
+            .await
+            .unwrap();
+
+        // L1 中的键在 backfill_ttl 后过期，L2 不受影响
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert!(l1.get(&key).await.is_err());
+        assert_eq!(l2.get(&key).await.unwrap(), "value");
+
+        // 再次读取时重新回填
+        l2.set(&key, &"updated".to_string(), &SetOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(store.get(&key).await.unwrap(), "updated");
+        assert_eq!(l1.get(&key).await.unwrap(), "updated");
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        register_hash_stores::<String, String>().unwrap();
+        let config: TieredStoreConfig = serde_json::from_value(serde_json::json!({
+            "l1": {"type": "DashMapStore", "options": {}},
+            "l2": {"type": "NotExistStore", "options": {}}
+        }))
+        .unwrap();
+        let err = TieredStore::<String, String>::new(config).err().unwrap();
+        assert!(err.to_string().contains("l2"));
+    }
+}
@@ -1,25 +1,49 @@
 use garde::Validate;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
-use std::cell::UnsafeCell;
+use std::cell::{Cell, UnsafeCell};
 use std::collections::HashMap;
 use std::hash::Hash;
-
-use super::core::{IsSyncStore, KvError, SetOptions, Store, AsyncStore, SyncStore};
-
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, SmartDefault, Validate)]
+use std::time::{Duration, Instant};
+
+use super::core::{
+    incr_value, IsSyncStore, KvError, SetOptions, Store, AsyncStore, Subscription, SyncStore,
+};
+use super::expiry::{live_keys, scan_live_keys, Entry};
+use super::notify::ChangeNotifier;
+use crate::kv::parser::ChangeType;
+use crate::cfg::serde_duration::{serde_as, HumanDur};
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, SmartDefault, Validate, JsonSchema)]
 #[serde(default)]
 pub struct UnsafeHashMapStoreConfig {
     #[garde(skip)]
     pub initial_capacity: Option<usize>,
+
+    /// 过期键的清理间隔，写入时距上次清理超过该间隔则清理一次
+    #[serde_as(as = "HumanDur")]
+    #[default(Duration::from_secs(60))]
+    #[garde(skip)]
+    pub sweep_interval: Duration,
 }
 
+/// 无锁的 HashMap 存储，只能在单线程或只读场景下使用
+///
+/// 支持 `SetOptions.expiration`：读取时已过期的键视为不存在（不修改 map）；
+/// 没有后台清理线程，已过期的键在写入时按 `sweep_interval` 顺带清理
+///
+/// 支持 `subscribe`：写入和删除时通知订阅者，过期清理不产生事件
 pub struct UnsafeHashMapStore<K, V>
 where
     K: Clone + Send + Sync + Eq + Hash,
     V: Clone + Send + Sync,
 {
-    map: UnsafeCell<HashMap<K, V>>,
+    map: UnsafeCell<HashMap<K, Entry<V>>>,
+    sweep_interval: Duration,
+    last_sweep: Cell<Instant>,
+    notifier: ChangeNotifier<K>,
 }
 
 unsafe impl<K, V> Send for UnsafeHashMapStore<K, V>
@@ -49,17 +73,28 @@ where
 
         Self {
             map: UnsafeCell::new(initial_map),
+            sweep_interval: config.sweep_interval,
+            last_sweep: Cell::new(Instant::now()),
+            notifier: ChangeNotifier::new(),
         }
     }
 
-    unsafe fn get_map(&self) -> &HashMap<K, V> {
+    unsafe fn get_map(&self) -> &HashMap<K, Entry<V>> {
         &*self.map.get()
     }
 
     #[allow(clippy::mut_from_ref)]
-    unsafe fn get_map_mut(&self) -> &mut HashMap<K, V> {
+    unsafe fn get_map_mut(&self) -> &mut HashMap<K, Entry<V>> {
         &mut *self.map.get()
     }
+
+    fn sweep_expired(&self, map: &mut HashMap<K, Entry<V>>, now: Instant) {
+        if now.duration_since(self.last_sweep.get()) < self.sweep_interval {
+            return;
+        }
+        map.retain(|_, entry| !entry.is_expired(now));
+        self.last_sweep.set(now);
+    }
 }
 
 impl<K, V> Default for UnsafeHashMapStore<K, V>
@@ -88,12 +123,14 @@ where
     fn set_sync(&self, key: &K, value: &V, options: &SetOptions) -> Result<(), KvError> {
         unsafe {
             let map = self.get_map_mut();
+            let now = Instant::now();
+            self.sweep_expired(map, now);
 
-            if options.if_not_exist && map.contains_key(key) {
-                return Err(KvError::ConditionFailed);
-            }
+            let exists = map.get(key).is_some_and(|entry| !entry.is_expired(now));
+            options.check_condition(exists)?;
 
-            map.insert(key.clone(), value.clone());
+            map.insert(key.clone(), Entry::new(value.clone(), options));
+            self.notifier.notify_set(key, exists);
             Ok(())
         }
     }
@@ -101,17 +138,19 @@ where
     fn get_sync(&self, key: &K) -> Result<V, KvError> {
         unsafe {
             let map = self.get_map();
-            match map.get(key) {
-                Some(value) => Ok(value.clone()),
-                None => Err(KvError::KeyNotFound),
-            }
+            map.get(key)
+                .and_then(|entry| entry.live_value(Instant::now()))
+                .cloned()
+                .ok_or(KvError::KeyNotFound)
         }
     }
 
     fn del_sync(&self, key: &K) -> Result<(), KvError> {
         unsafe {
             let map = self.get_map_mut();
-            map.remove(key);
+            if map.remove(key).is_some_and(|entry| !entry.is_expired(Instant::now())) {
+                self.notifier.notify(ChangeType::Delete, key);
+            }
             Ok(())
         }
     }
@@ -130,15 +169,19 @@ where
 
         unsafe {
             let map = self.get_map_mut();
+            let now = Instant::now();
+            self.sweep_expired(map, now);
             let mut results = Vec::with_capacity(keys.len());
 
             for (key, value) in keys.iter().zip(vals.iter()) {
-                if options.if_not_exist && map.contains_key(key) {
-                    results.push(Err(KvError::ConditionFailed));
+                let exists = map.get(key).is_some_and(|entry| !entry.is_expired(now));
+                if let Err(e) = options.check_condition(exists) {
+                    results.push(Err(e));
                     continue;
                 }
 
-                map.insert(key.clone(), value.clone());
+                map.insert(key.clone(), Entry::new(value.clone(), options));
+                self.notifier.notify_set(key, exists);
                 results.push(Ok(()));
             }
 
@@ -152,11 +195,12 @@ where
     ) -> Result<(Vec<Option<V>>, Vec<Option<KvError>>), KvError> {
         unsafe {
             let map = self.get_map();
+            let now = Instant::now();
             let mut values = Vec::with_capacity(keys.len());
             let mut errors = Vec::with_capacity(keys.len());
 
             for key in keys {
-                match map.get(key) {
+                match map.get(key).and_then(|entry| entry.live_value(now)) {
                     Some(value) => {
                         values.push(Some(value.clone()));
                         errors.push(None);
@@ -175,10 +219,13 @@ where
     fn batch_del_sync(&self, keys: &[K]) -> Result<Vec<Result<(), KvError>>, KvError> {
         unsafe {
             let map = self.get_map_mut();
+            let now = Instant::now();
             let mut results = Vec::with_capacity(keys.len());
 
             for key in keys {
-                map.remove(key);
+                if map.remove(key).is_some_and(|entry| !entry.is_expired(now)) {
+                    self.notifier.notify(ChangeType::Delete, key);
+                }
                 results.push(Ok(()));
             }
 
@@ -190,6 +237,89 @@ where
         // 不清空数据，只做资源清理
         Ok(())
     }
+
+    fn scan_sync(&self, cursor: u64, count: usize) -> Result<(u64, Vec<K>), KvError> {
+        unsafe {
+            let map = self.get_map();
+            Ok(scan_live_keys(map.iter(), cursor, count, Instant::now()))
+        }
+    }
+
+    fn keys_sync(&self) -> Result<Vec<K>, KvError> {
+        unsafe {
+            let map = self.get_map();
+            Ok(live_keys(map.iter(), Instant::now(), |_| true))
+        }
+    }
+
+    fn keys_with_prefix_sync(&self, prefix: &str) -> Result<Vec<K>, KvError>
+    where
+        K: AsRef<str>,
+    {
+        unsafe {
+            let map = self.get_map();
+            Ok(live_keys(map.iter(), Instant::now(), |key| {
+                key.as_ref().starts_with(prefix)
+            }))
+        }
+    }
+
+    fn len_sync(&self) -> Result<usize, KvError> {
+        unsafe {
+            let now = Instant::now();
+            let map = self.get_map();
+            Ok(map.values().filter(|entry| !entry.is_expired(now)).count())
+        }
+    }
+
+    fn compare_and_swap_sync(
+        &self,
+        key: &K,
+        expected: &V,
+        value: &V,
+        options: &SetOptions,
+    ) -> Result<(), KvError>
+    where
+        V: PartialEq,
+    {
+        unsafe {
+            let map = self.get_map_mut();
+            let now = Instant::now();
+            self.sweep_expired(map, now);
+
+            if map.get(key).and_then(|entry| entry.live_value(now)) != Some(expected) {
+                return Err(KvError::ConditionFailed);
+            }
+
+            map.insert(key.clone(), Entry::new(value.clone(), options));
+            self.notifier.notify(ChangeType::Update, key);
+            Ok(())
+        }
+    }
+
+    fn incr_sync(&self, key: &K, delta: i64) -> Result<i64, KvError>
+    where
+        V: Into<i64> + TryFrom<i64>,
+    {
+        unsafe {
+            let map = self.get_map_mut();
+            let now = Instant::now();
+            self.sweep_expired(map, now);
+
+            // 保留原有的过期时间，已过期的键从 0 开始
+            let live = map.get(key).filter(|entry| !entry.is_expired(now));
+            let (next, value) = incr_value(live.map(|entry| &entry.value), delta)?;
+            let expire_at = live.and_then(|entry| entry.expire_at);
+            let exists = live.is_some();
+            map.insert(key.clone(), Entry { value, expire_at });
+            self.notifier.notify_set(key, exists);
+            Ok(next)
+        }
+    }
+
+    fn subscribe_sync(&self) -> Result<Subscription<K>, KvError> {
+        Ok(self.notifier.subscribe())
+    }
 }
 
 impl<K, V> From<UnsafeHashMapStoreConfig> for UnsafeHashMapStore<K, V>
@@ -323,6 +453,66 @@ mod tests {
         test_close_sync(store);
     }
 
+    #[tokio::test]
+    async fn test_store_expiration() {
+        let store = UnsafeHashMapStore::<String, String>::new(UnsafeHashMapStoreConfig::default());
+        test_expiration(store).await;
+    }
+
+    #[test]
+    fn test_store_expiration_sync() {
+        let store = UnsafeHashMapStore::<String, String>::new(UnsafeHashMapStoreConfig::default());
+        test_expiration_sync(store);
+    }
+
+    #[tokio::test]
+    async fn test_store_scan() {
+        let store = UnsafeHashMapStore::<String, String>::new(UnsafeHashMapStoreConfig::default());
+        test_scan(store).await;
+    }
+
+    #[test]
+    fn test_store_scan_sync() {
+        let store = UnsafeHashMapStore::<String, String>::new(UnsafeHashMapStoreConfig::default());
+        test_scan_sync(store);
+    }
+
+    #[tokio::test]
+    async fn test_store_compare_and_swap() {
+        let store = UnsafeHashMapStore::<String, String>::new(UnsafeHashMapStoreConfig::default());
+        test_compare_and_swap(store).await;
+    }
+
+    #[test]
+    fn test_store_compare_and_swap_sync() {
+        let store = UnsafeHashMapStore::<String, String>::new(UnsafeHashMapStoreConfig::default());
+        test_compare_and_swap_sync(store);
+    }
+
+    #[tokio::test]
+    async fn test_store_incr() {
+        let store = UnsafeHashMapStore::<String, i64>::new(UnsafeHashMapStoreConfig::default());
+        test_incr(store).await;
+    }
+
+    #[test]
+    fn test_store_incr_sync() {
+        let store = UnsafeHashMapStore::<String, i64>::new(UnsafeHashMapStoreConfig::default());
+        test_incr_sync(store);
+    }
+
+    #[tokio::test]
+    async fn test_store_subscribe() {
+        let store = UnsafeHashMapStore::<String, String>::new(UnsafeHashMapStoreConfig::default());
+        test_subscribe(store).await;
+    }
+
+    #[test]
+    fn test_store_subscribe_sync() {
+        let store = UnsafeHashMapStore::<String, String>::new(UnsafeHashMapStoreConfig::default());
+        test_subscribe_sync(store);
+    }
+
     // ========== 场景测试 ==========
 
     #[tokio::test]
@@ -509,4 +699,24 @@ mod tests {
 
         println!("Final integrity check passed!");
     }
+
+    #[test]
+    fn test_expired_keys_swept_on_write() {
+        let store = UnsafeHashMapStore::<String, i32>::new(UnsafeHashMapStoreConfig {
+            sweep_interval: Duration::from_millis(50),
+            ..Default::default()
+        });
+        let ttl = SetOptions::new().with_expiration(Duration::from_millis(20));
+        for i in 0..10 {
+            store.set_sync(&format!("key{}", i), &i, &ttl).unwrap();
+        }
+        assert_eq!(unsafe { store.get_map() }.len(), 10);
+
+        // 读取不修改 map，过期键在超过清理间隔后的写入时清理
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(store.get_sync(&"key0".to_string()).is_err());
+        assert_eq!(unsafe { store.get_map() }.len(), 10);
+        store.set_sync(&"persist".to_string(), &0, &SetOptions::new()).unwrap();
+        assert_eq!(unsafe { store.get_map() }.len(), 1);
+    }
 }
@@ -1,11 +1,12 @@
 use crate::log::appender::LogAppender;
 use anyhow::Result;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use smart_default::SmartDefault;
 use std::io::{self, Write};
 
 /// 输出目标
-#[derive(Debug, Clone, Deserialize, PartialEq, SmartDefault)]
+#[derive(Debug, Clone, Deserialize, PartialEq, SmartDefault, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum Target {
     /// 标准输出
@@ -16,7 +17,7 @@ pub enum Target {
 }
 
 /// ConsoleAppender 配置
-#[derive(Debug, Clone, Deserialize, SmartDefault)]
+#[derive(Debug, Clone, Deserialize, SmartDefault, JsonSchema)]
 #[serde(default)]
 pub struct ConsoleAppenderConfig {
     /// 输出目标: "stdout" 或 "stderr"
@@ -1,5 +1,6 @@
 use crate::log::appender::LogAppender;
 use anyhow::Result;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use smart_default::SmartDefault;
 use std::path::PathBuf;
@@ -8,7 +9,7 @@ use tokio::sync::Mutex;
 use std::sync::Mutex as StdMutex;
 
 /// FileAppender 配置
-#[derive(Debug, Clone, Deserialize, SmartDefault)]
+#[derive(Debug, Clone, Deserialize, SmartDefault, JsonSchema)]
 pub struct FileAppenderConfig {
     /// 日志文件路径
     #[default = "app.log"]
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use crate::cfg::register_trait;
+use crate::cfg::register_trait_with_schema;
 use crate::log::appender::LogAppender;
 use crate::log::appender::{
     console_appender::{ConsoleAppender, ConsoleAppenderConfig},
@@ -9,9 +9,9 @@ use crate::log::appender::{
 
 /// 注册所有 Appender 实现
 pub fn register_appenders() -> Result<()> {
-    register_trait::<ConsoleAppender, dyn LogAppender, ConsoleAppenderConfig>("ConsoleAppender")?;
-    register_trait::<FileAppender, dyn LogAppender, FileAppenderConfig>("FileAppender")?;
-    register_trait::<RollingFileAppender, dyn LogAppender, RollingFileAppenderConfig>("RollingFileAppender")?;
+    register_trait_with_schema::<ConsoleAppender, dyn LogAppender, ConsoleAppenderConfig>("ConsoleAppender")?;
+    register_trait_with_schema::<FileAppender, dyn LogAppender, FileAppenderConfig>("FileAppender")?;
+    register_trait_with_schema::<RollingFileAppender, dyn LogAppender, RollingFileAppenderConfig>("RollingFileAppender")?;
     Ok(())
 }
 
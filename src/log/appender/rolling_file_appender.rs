@@ -1,4 +1,5 @@
 use crate::log::appender::LogAppender;
+use schemars::JsonSchema;
 use anyhow::Result;
 use chrono::{DateTime, Datelike, Local, Timelike, Utc};
 use smart_default::SmartDefault;
@@ -7,7 +8,7 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 
 /// 时间切分策略
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum TimePolicy {
     /// 按分钟切分
@@ -62,7 +63,7 @@ impl TimePolicy {
 }
 
 /// RollingFileAppender 配置
-#[derive(Debug, Clone, serde::Deserialize, SmartDefault)]
+#[derive(Debug, Clone, serde::Deserialize, SmartDefault, JsonSchema)]
 #[serde(default)]
 pub struct RollingFileAppenderConfig {
     // ========== 基本信息 ==========
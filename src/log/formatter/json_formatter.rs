@@ -1,11 +1,12 @@
 use crate::log::formatter::LogFormatter;
 use crate::log::log_record::LogRecord;
 use anyhow::Result;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use smart_default::SmartDefault;
 
 /// JsonFormatter 配置（保留扩展性）
-#[derive(Debug, Clone, Deserialize, PartialEq, SmartDefault)]
+#[derive(Debug, Clone, Deserialize, PartialEq, SmartDefault, JsonSchema)]
 #[serde(default)]
 pub struct JsonFormatterConfig {}
 
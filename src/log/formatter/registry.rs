@@ -1,5 +1,5 @@
 use anyhow::Result;
-use crate::cfg::register_trait;
+use crate::cfg::register_trait_with_schema;
 use crate::log::formatter::LogFormatter;
 use crate::log::formatter::{
     text_formatter::{TextFormatter, TextFormatterConfig},
@@ -8,8 +8,8 @@ use crate::log::formatter::{
 
 /// 注册所有 Formatter 实现
 pub fn register_formatters() -> Result<()> {
-    register_trait::<TextFormatter, dyn LogFormatter, TextFormatterConfig>("TextFormatter")?;
-    register_trait::<JsonFormatter, dyn LogFormatter, JsonFormatterConfig>("JsonFormatter")?;
+    register_trait_with_schema::<TextFormatter, dyn LogFormatter, TextFormatterConfig>("TextFormatter")?;
+    register_trait_with_schema::<JsonFormatter, dyn LogFormatter, JsonFormatterConfig>("JsonFormatter")?;
     Ok(())
 }
 
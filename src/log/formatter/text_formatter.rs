@@ -1,11 +1,12 @@
 use crate::log::formatter::LogFormatter;
 use crate::log::log_record::LogRecord;
 use anyhow::Result;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use smart_default::SmartDefault;
 
 /// TextFormatter 配置
-#[derive(Debug, Clone, Deserialize, SmartDefault)]
+#[derive(Debug, Clone, Deserialize, SmartDefault, JsonSchema)]
 #[serde(default)]
 pub struct TextFormatterConfig {
     /// 是否启用颜色输出
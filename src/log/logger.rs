@@ -1,26 +1,27 @@
-use crate::cfg::{create_trait_from_type_options, TypeOptions};
+use crate::cfg::{create_shared_trait_from_type_options, TypeOptions};
 use crate::log::{
     appender::LogAppender, formatter::LogFormatter, log_record::LogLevel, log_record::LogRecord,
 };
 use anyhow::Result;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
 use std::sync::{Arc, Once};
 use tokio::sync::RwLock;
 
 /// Logger 创建配置（用于创建新的 Logger 实例）
-#[derive(Debug, Clone, Deserialize, Serialize, SmartDefault, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, SmartDefault, PartialEq, JsonSchema)]
 #[serde(default)]
 pub struct LoggerCreateConfig {
     /// 日志级别
     #[default = "info"]
     pub level: String,
 
-    /// Formatter 配置
+    /// Formatter 配置，支持 `{"$instance": "name"}` 引用共享实例
     #[default(TypeOptions { type_name: "TextFormatter".to_string(), options: serde_json::json!({}) })]
     pub formatter: TypeOptions,
 
-    /// Appender 配置
+    /// Appender 配置，支持 `{"$instance": "name"}` 引用共享实例
     #[default(TypeOptions { type_name: "ConsoleAppender".to_string(), options: serde_json::json!({}) })]
     pub appender: TypeOptions,
 }
@@ -30,7 +31,7 @@ pub struct LoggerCreateConfig {
 /// 支持两种模式：
 /// - Reference: 引用已存在的 logger 实例（通过 $instance 字段）
 /// - Create: 创建新的 logger 实例
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema)]
 #[serde(untagged)]
 pub enum LoggerConfig {
     /// 引用一个已存在的 logger 实例
@@ -75,12 +76,12 @@ impl Logger {
         let level = config.level.parse::<LogLevel>().unwrap_or(LogLevel::Info);
 
         // 创建 formatter
-        let formatter_box = create_trait_from_type_options(&config.formatter)?;
-        let formatter: Arc<dyn LogFormatter> = Arc::from(formatter_box);
+        let formatter: Arc<dyn LogFormatter> =
+            create_shared_trait_from_type_options(&config.formatter)?;
 
         // 创建 appender
-        let appender_box = create_trait_from_type_options(&config.appender)?;
-        let appender: Arc<dyn LogAppender> = Arc::from(appender_box);
+        let appender: Arc<dyn LogAppender> =
+            create_shared_trait_from_type_options(&config.appender)?;
 
         Ok(Self {
             level: Arc::new(RwLock::new(level)),
@@ -498,6 +499,30 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_logger_from_config_with_instance() -> Result<()> {
+        crate::log::register_appenders()?;
+        let appender: Arc<dyn LogAppender> = create_shared_trait_from_type_options(
+            &TypeOptions::from_json(r#"{"type": "ConsoleAppender", "options": {}}"#)?,
+        )?;
+        crate::cfg::register_instance::<dyn LogAppender>("logger_test_console", appender)?;
+
+        // formatter 和 appender 可以引用共享实例
+        let config: LoggerCreateConfig = json5::from_str(
+            r#"
+            {
+                level: "info",
+                appender: { "$instance": "logger_test_console" }
+            }
+            "#,
+        )?;
+
+        let logger = Logger::new(config)?;
+        logger.info("shared appender").await?;
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_logger_logm_with_metadata() -> Result<()> {
         let logger = create_test_logger("debug");
@@ -5,6 +5,7 @@
 
 use async_trait::async_trait;
 use bytes::Bytes;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
 use chrono::{DateTime, Utc};
@@ -124,7 +125,7 @@ where
 /// 2. 环境变量 `ALIBABA_CLOUD_ACCESS_KEY_ID` 和 `ALIBABA_CLOUD_ACCESS_KEY_SECRET`
 ///    （也支持旧版环境变量 `ALIYUN_OSS_ACCESS_KEY_ID` 和 `ALIYUN_OSS_ACCESS_KEY_SECRET`）
 /// 3. ECS 实例角色 - 通过元数据服务 `http://100.100.100.200/latest/meta-data/ram/security-credentials/` 获取
-#[derive(Deserialize, Serialize, SmartDefault, Clone, Validate)]
+#[derive(Deserialize, Serialize, SmartDefault, Clone, Validate, JsonSchema)]
 #[serde(default)]
 pub struct AliOssObjectStoreConfig {
     /// 存储桶名称
@@ -5,6 +5,7 @@
 use async_trait::async_trait;
 use bytes::Bytes;
 use garde::Validate;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use smart_default::SmartDefault;
 use std::path::Path;
@@ -12,7 +13,7 @@ use std::sync::Arc;
 use tokio::io::{AsyncRead, AsyncWrite};
 
 use crate::aop::{Aop, AopConfig};
-use crate::cfg::{create_trait_from_type_options, TypeOptions};
+use crate::cfg::{create_shared_trait_from_type_options, TypeOptions};
 use crate::oss::{
     DirectoryTransferResult, GetDirectoryOptions, GetFileOptions, GetObjectOptions,
     GetStreamOptions, ObjectMeta, ObjectStore, ObjectStoreError, PutDirectoryOptions,
@@ -21,10 +22,10 @@ use crate::oss::{
 use crate::{aop, impl_box_from};
 
 /// AopObjectStore 配置
-#[derive(Debug, Clone, Deserialize, SmartDefault, Validate)]
+#[derive(Debug, Clone, Deserialize, SmartDefault, Validate, JsonSchema)]
 #[serde(default)]
 pub struct AopObjectStoreConfig {
-    /// 内部 ObjectStore 配置
+    /// 内部 ObjectStore 配置，支持 `{"$instance": "name"}` 引用共享实例
     #[garde(skip)]
     pub object_store: TypeOptions,
 
@@ -38,7 +39,7 @@ pub struct AopObjectStoreConfig {
 /// 包装一个 ObjectStore 实现，为其所有方法添加 AOP 功能（日志记录和重试）。
 pub struct AopObjectStore {
     /// 内部 ObjectStore 实例
-    inner: Box<dyn ObjectStore>,
+    inner: Arc<dyn ObjectStore>,
 
     /// AOP 配置（日志和重试）
     aop: Option<Arc<Aop>>,
@@ -62,7 +63,7 @@ impl AopObjectStore {
         }
 
         // 创建内部 ObjectStore
-        let inner = create_trait_from_type_options::<dyn ObjectStore>(&config.object_store)
+        let inner = create_shared_trait_from_type_options::<dyn ObjectStore>(&config.object_store)
             .map_err(|e| ObjectStoreError::Configuration(format!("{}", e)))?;
 
         // 解析 AOP 配置
@@ -84,7 +85,10 @@ impl AopObjectStore {
     /// - `inner`: 内部 ObjectStore 实例
     /// - `aop`: AOP 实例（可选）
     pub fn from_parts(inner: Box<dyn ObjectStore>, aop: Option<Arc<Aop>>) -> Self {
-        Self { inner, aop }
+        Self {
+            inner: inner.into(),
+            aop,
+        }
     }
 }
 
@@ -4,6 +4,7 @@ use aws_credential_types::Credentials;
 use aws_config::Region;
 use async_trait::async_trait;
 use bytes::Bytes;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
 use std::sync::Arc;
@@ -25,7 +26,7 @@ use crate::oss::{
 ///    - 共享配置文件 `~/.aws/config`
 ///    - ECS 容器凭证（在 ECS 中运行时通过 `AWS_CONTAINER_CREDENTIALS_RELATIVE_URI`）
 ///    - EC2 实例元数据服务 IMDS（在 EC2 中运行时）
-#[derive(Deserialize, Serialize, SmartDefault, Clone, Validate)]
+#[derive(Deserialize, Serialize, SmartDefault, Clone, Validate, JsonSchema)]
 #[serde(default)]
 pub struct AwsS3ObjectStoreConfig {
     /// 存储桶名称
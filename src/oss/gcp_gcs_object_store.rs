@@ -5,6 +5,7 @@
 
 use async_trait::async_trait;
 use bytes::Bytes;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
 use std::sync::Arc;
@@ -26,7 +27,7 @@ use crate::oss::{
 ///    - 环境变量 `GOOGLE_APPLICATION_CREDENTIALS` 指向的文件
 ///    - 默认位置 `~/.config/gcloud/application_default_credentials.json`
 ///    - GCE/GKE 实例元数据服务（在 Google Cloud 环境中运行时）
-#[derive(Deserialize, Serialize, SmartDefault, Clone, Validate)]
+#[derive(Deserialize, Serialize, SmartDefault, Clone, Validate, JsonSchema)]
 #[serde(default)]
 pub struct GcpGcsObjectStoreConfig {
     /// 存储桶名称
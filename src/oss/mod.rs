@@ -29,7 +29,7 @@ pub use object_store_manager::{ObjectStoreManager, ObjectStoreManagerConfig, Def
 pub use object_store_manager_types::{CpOptions, CpResult, LsOptions, RmOptions, RmResult};
 pub use uri::{OssUri, Provider, Location, is_remote_uri};
 
-use crate::cfg::register_trait;
+use crate::cfg::register_trait_fallible_with_schema;
 
 /// 注册所有 ObjectStore 实现
 ///
@@ -46,23 +46,27 @@ use crate::cfg::register_trait;
 /// ```
 pub fn register_object_store() {
     // 注册 S3
-    register_trait::<AwsS3ObjectStore, dyn ObjectStore, AwsS3ObjectStoreConfig>(
-        "AwsS3ObjectStore"
+    register_trait_fallible_with_schema::<AwsS3ObjectStore, dyn ObjectStore, AwsS3ObjectStoreConfig, _>(
+        "AwsS3ObjectStore",
+        |config| Ok(AwsS3ObjectStore::new(config)?),
     ).expect("Failed to register AwsS3ObjectStore");
 
     // 注册阿里云 OSS
-    register_trait::<AliOssObjectStore, dyn ObjectStore, AliOssObjectStoreConfig>(
-        "AliOssObjectStore"
+    register_trait_fallible_with_schema::<AliOssObjectStore, dyn ObjectStore, AliOssObjectStoreConfig, _>(
+        "AliOssObjectStore",
+        |config| Ok(AliOssObjectStore::new(config)?),
     ).expect("Failed to register AliOssObjectStore");
 
     // 注册 GCP GCS
-    register_trait::<GcpGcsObjectStore, dyn ObjectStore, GcpGcsObjectStoreConfig>(
-        "GcpGcsObjectStore"
+    register_trait_fallible_with_schema::<GcpGcsObjectStore, dyn ObjectStore, GcpGcsObjectStoreConfig, _>(
+        "GcpGcsObjectStore",
+        |config| Ok(GcpGcsObjectStore::new(config)?),
     ).expect("Failed to register GcpGcsObjectStore");
 
     // 注册 AOP ObjectStore
-    register_trait::<AopObjectStore, dyn ObjectStore, AopObjectStoreConfig>(
-        "AopObjectStore"
+    register_trait_fallible_with_schema::<AopObjectStore, dyn ObjectStore, AopObjectStoreConfig, _>(
+        "AopObjectStore",
+        |config| Ok(AopObjectStore::new(config)?),
     ).expect("Failed to register AopObjectStore");
 }
 
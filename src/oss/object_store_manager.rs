@@ -29,6 +29,7 @@ pub struct ObjectStoreManagerConfig {
     /// 对象存储配置列表
     ///
     /// 每个配置描述一个对象存储实例（S3、OSS、GCS 等）。
+    /// 按配置中的类型和 `bucket` 匹配 URI，因此不支持 `{"$instance": "name"}` 引用共享实例。
     pub object_stores: Vec<TypeOptions>,
 
     /// 操作的默认选项
@@ -154,6 +154,7 @@ fn create_apollo_source() -> Result<ApolloSource> {
         server_url: APOLLO_SERVER_URL.to_string(),
         app_id: APOLLO_APP_ID.to_string(),
         cluster: APOLLO_CLUSTER.to_string(),
+        logger: None,
     })
 }
 
@@ -324,6 +325,7 @@ mod tests {
             server_url: "http://localhost:19999".to_string(), // 不存在的端口
             app_id: APOLLO_APP_ID.to_string(),
             cluster: APOLLO_CLUSTER.to_string(),
+            logger: None,
         })?;
 
         let errors: Arc<RwLock<Vec<String>>> = Arc::new(RwLock::new(Vec::new()));